@@ -0,0 +1,395 @@
+//! Benchmarks the generator and both parts of every day that has an input in `input/2021/`.
+//!
+//! Pass a filter to run only some days, for example `cargo bench -- day13`. A summary table of
+//! the mean timings is printed at the end.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "arena")]
+use advent_of_code_2021::instrument::measure;
+#[cfg(feature = "arena")]
+use advent_of_code_2021::solver::Arena;
+use advent_of_code_2021::solver::Solver;
+use advent_of_code_2021::util::pq::{BucketQueue, IndexedHeap};
+use advent_of_code_2021::year2021::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, day_23, day_24,
+    day_25,
+};
+use advent_of_code_2021::{fetcher, geninput, input};
+use criterion::Criterion;
+
+const STEPS: [&str; 3] = ["parse", "part1", "part2"];
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Benchmarks one day. Returns `false` if the day was skipped for lack of a usable input.
+fn bench_day<S: Solver>(criterion: &mut Criterion, day: u8) -> bool {
+    let path = input::cached_path(root(), fetcher::YEAR, day);
+    let Ok(input) = input::read_file(&path) else {
+        eprintln!("Skipping day {day}: no input at {}", path.display());
+        return false;
+    };
+    let input = input.as_str();
+    let Ok(parsed) = S::parse(input) else {
+        eprintln!("Skipping day {day}: the input does not parse");
+        return false;
+    };
+    let mut group = criterion.benchmark_group(format!("day{day:02}"));
+    group.bench_function(STEPS[0], |b| b.iter(|| S::parse(black_box(input))));
+    group.bench_function(STEPS[1], |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function(STEPS[2], |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+    true
+}
+
+macro_rules! bench_days {
+    ($($day:literal => $solver:ty),* $(,)?) => {
+        fn bench_days(criterion: &mut Criterion) -> Vec<u8> {
+            let mut benched = Vec::new();
+            $(
+                if bench_day::<$solver>(criterion, $day) {
+                    benched.push($day);
+                }
+            )*
+            benched
+        }
+    };
+}
+
+bench_days! {
+    1 => day_01::Day01,
+    2 => day_02::Day02,
+    3 => day_03::Day03,
+    4 => day_04::Day04,
+    5 => day_05::Day05,
+    6 => day_06::Day06,
+    7 => day_07::Day07,
+    8 => day_08::Day08,
+    9 => day_09::Day09,
+    10 => day_10::Day10,
+    11 => day_11::Day11,
+    12 => day_12::Day12,
+    13 => day_13::Day13,
+    14 => day_14::Day14,
+    15 => day_15::Day15,
+    16 => day_16::Day16,
+    17 => day_17::Day17,
+    18 => day_18::Day18,
+    19 => day_19::Day19,
+    20 => day_20::Day20,
+    21 => day_21::Day21,
+    22 => day_22::Day22,
+    23 => day_23::Day23,
+    24 => day_24::Day24,
+    25 => day_25::Day25,
+}
+
+/// Compares the scalar and wide day 1 loops on a generated input, which is large enough for the
+/// difference to show.
+fn bench_day01_wide(criterion: &mut Criterion) {
+    let input = geninput::generate(1, 100, 2021).expect("day 1 has a generator");
+    let depths = day_01::Day01::parse(input.trim_end()).expect("generated inputs parse");
+    let mut group = criterion.benchmark_group("day01_wide");
+    for window in [1, 3] {
+        group.bench_function(format!("scalar/{window}"), |b| {
+            b.iter(|| day_01::count_increases(black_box(&depths), window));
+        });
+        group.bench_function(format!("wide/{window}"), |b| {
+            b.iter(|| day_01::count_increases_wide(black_box(&depths), window));
+        });
+    }
+    group.finish();
+}
+
+/// Plays day 4 with thousands of boards, where finding each number on each board dominates.
+fn bench_day04_many_boards(criterion: &mut Criterion) {
+    let input = geninput::generate(4, 50, 2021).expect("day 4 has a generator");
+    let bingo = day_04::Day04::parse(input.trim_end()).expect("generated inputs parse");
+    criterion.bench_function("day04_many_boards", |b| {
+        b.iter(|| day_04::solve_both(black_box(&bingo)));
+    });
+}
+
+/// Compares the sequential and parallel day 5 grids on a generated input with many lines.
+fn bench_day05_parallel(criterion: &mut Criterion) {
+    let input = geninput::generate(5, 20, 2021).expect("day 5 has a generator");
+    let lines = day_05::Day05::parse(input.trim_end()).expect("generated inputs parse");
+    let mut group = criterion.benchmark_group("day05_parallel");
+    group.bench_function("sequential", |b| {
+        b.iter(|| day_05::solve_both(black_box(&lines)));
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| day_05::solve_both_parallel(black_box(&lines)));
+    });
+    group.finish();
+}
+
+/// Compares decoding the day 8 displays in sequence and on the rayon threads.
+fn bench_day08_parallel(criterion: &mut Criterion) {
+    let input = geninput::generate(8, 100, 2021).expect("day 8 has a generator");
+    let displays = day_08::Day08::parse(input.trim_end()).expect("generated inputs parse");
+    let mut group = criterion.benchmark_group("day08_parallel");
+    group.bench_function("sequential", |b| {
+        b.iter(|| day_08::solve_both(black_box(&displays)));
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| day_08::solve_both_parallel(black_box(&displays)));
+    });
+    group.finish();
+}
+
+/// Compares the memoized day 12 count with the one split among the rayon threads.
+fn bench_day12_parallel(criterion: &mut Criterion) {
+    let input = geninput::generate(12, 8, 2021).expect("day 12 has a generator");
+    let caves = day_12::Day12::parse(input.trim_end()).expect("generated inputs parse");
+    let mut group = criterion.benchmark_group("day12_parallel");
+    group.bench_function("sequential", |b| {
+        b.iter(|| day_12::solve_both(black_box(&caves)));
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| day_12::solve_both_parallel(black_box(&caves)));
+    });
+    group.finish();
+}
+
+/// The risks of a generated day 15 cave, tiled five times as in part 2, by cell, and its width.
+fn day15_cave() -> (Vec<usize>, usize) {
+    let input = geninput::generate(15, 1, 2021).expect("day 15 has a generator");
+    let tile = input
+        .lines()
+        .map(|line| {
+            line.bytes()
+                .map(|b| usize::from(b - b'0'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let (height, width) = (tile.len(), tile[0].len());
+    let risks = (0..5 * height)
+        .flat_map(|r| (0..5 * width).map(move |c| (r, c)))
+        .map(|(r, c)| (tile[r % height][c % width] - 1 + r / height + c / width) % 9 + 1)
+        .collect();
+    (risks, 5 * width)
+}
+
+/// The cells next to `node`, with the risk of entering them.
+fn cave_steps(risks: &[usize], width: usize, node: usize) -> impl Iterator<Item = (usize, usize)> {
+    let (r, c) = (node / width, node % width);
+    let height = risks.len() / width;
+    [
+        (r.wrapping_sub(1), c),
+        (r + 1, c),
+        (r, c.wrapping_sub(1)),
+        (r, c + 1),
+    ]
+    .into_iter()
+    .filter(move |&(r, c)| r < height && c < width)
+    .map(move |(r, c)| (r * width + c, risks[r * width + c]))
+}
+
+/// Lowest total risk to the bottom right, with a `BinaryHeap` that may hold stale entries.
+fn lowest_risk_binary_heap(risks: &[usize], width: usize) -> usize {
+    let mut best = vec![usize::MAX; risks.len()];
+    let mut queue = BinaryHeap::from([Reverse((0, 0))]);
+    best[0] = 0;
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if node == risks.len() - 1 {
+            return cost;
+        }
+        if cost > best[node] {
+            continue;
+        }
+        for (next, risk) in cave_steps(risks, width, node) {
+            if cost + risk < best[next] {
+                best[next] = cost + risk;
+                queue.push(Reverse((cost + risk, next)));
+            }
+        }
+    }
+    unreachable!("the bottom right can always be reached")
+}
+
+/// Lowest total risk to the bottom right, with an `IndexedHeap` that lowers queued cells.
+fn lowest_risk_indexed_heap(risks: &[usize], width: usize) -> usize {
+    let mut queue = IndexedHeap::new(risks.len());
+    let mut done = vec![false; risks.len()];
+    queue.push_or_decrease(0, 0);
+    while let Some((node, cost)) = queue.pop() {
+        if node == risks.len() - 1 {
+            return cost;
+        }
+        done[node] = true;
+        for (next, risk) in cave_steps(risks, width, node) {
+            if !done[next] {
+                queue.push_or_decrease(next, cost + risk);
+            }
+        }
+    }
+    unreachable!("the bottom right can always be reached")
+}
+
+/// Lowest total risk to the bottom right, with a `BucketQueue` that may hold stale entries.
+fn lowest_risk_bucket_queue(risks: &[usize], width: usize) -> usize {
+    let mut best = vec![usize::MAX; risks.len()];
+    let mut queue = BucketQueue::new();
+    best[0] = 0;
+    queue.push(0, 0);
+    while let Some((node, cost)) = queue.pop() {
+        if node == risks.len() - 1 {
+            return cost;
+        }
+        if cost > best[node] {
+            continue;
+        }
+        for (next, risk) in cave_steps(risks, width, node) {
+            if cost + risk < best[next] {
+                best[next] = cost + risk;
+                queue.push(next, cost + risk);
+            }
+        }
+    }
+    unreachable!("the bottom right can always be reached")
+}
+
+/// Compares the two queues in `util::pq` with `BinaryHeap`, on Dijkstra's algorithm through a
+/// cave the size of day 15 part 2.
+fn bench_queues(criterion: &mut Criterion) {
+    let (risks, width) = day15_cave();
+    let expected = lowest_risk_binary_heap(&risks, width);
+    assert_eq!(lowest_risk_indexed_heap(&risks, width), expected);
+    assert_eq!(lowest_risk_bucket_queue(&risks, width), expected);
+    let mut group = criterion.benchmark_group("queues");
+    group.bench_function("binary_heap", |b| {
+        b.iter(|| lowest_risk_binary_heap(black_box(&risks), width));
+    });
+    group.bench_function("indexed_heap", |b| {
+        b.iter(|| lowest_risk_indexed_heap(black_box(&risks), width));
+    });
+    group.bench_function("bucket_queue", |b| {
+        b.iter(|| lowest_risk_bucket_queue(black_box(&risks), width));
+    });
+    group.finish();
+}
+
+/// Benchmarks parsing a generated input `scale` times the usual size.
+fn bench_parse<S: Solver>(criterion: &mut Criterion, day: u8, scale: usize) {
+    let input = geninput::generate(day, scale, 2021).expect("every day has a generator");
+    let input = input.trim_end();
+    criterion.bench_function(&format!("parse/day{day:02}"), |b| {
+        b.iter(|| S::parse(black_box(input)));
+    });
+}
+
+/// Parsing on inputs large enough for allocations per line to show.
+fn bench_parsers(criterion: &mut Criterion) {
+    bench_parse::<day_02::Day02>(criterion, 2, 20);
+    bench_parse::<day_03::Day03>(criterion, 3, 20);
+    bench_parse::<day_04::Day04>(criterion, 4, 20);
+    bench_parse::<day_05::Day05>(criterion, 5, 20);
+    bench_parse::<day_08::Day08>(criterion, 8, 20);
+    bench_parse::<day_12::Day12>(criterion, 12, 20);
+    bench_parse::<day_13::Day13>(criterion, 13, 10);
+    bench_parse::<day_14::Day14>(criterion, 14, 20);
+}
+
+/// Compares parsing with the scratch allocations on the heap and in an arena, reset after each
+/// parse as the runner does. With `alloc-stats`, also prints how many allocations one parse
+/// makes each way, once the arena has grown to fit the input.
+#[cfg(feature = "arena")]
+fn bench_parse_arena<S: Solver>(criterion: &mut Criterion, day: u8, scale: usize) {
+    let input = geninput::generate(day, scale, 2021).expect("every day has a generator");
+    let input = input.trim_end();
+    let mut arena = Arena::new();
+    let mut parse_in = move || {
+        let parsed = S::parse_in(black_box(input), &arena);
+        arena.reset();
+        parsed
+    };
+    let mut group = criterion.benchmark_group(format!("parse_arena/day{day:02}"));
+    group.bench_function("heap", |b| b.iter(|| S::parse(black_box(input))));
+    group.bench_function("arena", |b| b.iter(&mut parse_in));
+    group.finish();
+    let _ = parse_in();
+    let (_, heap) = measure(|| S::parse(input));
+    let (_, in_arena) = measure(parse_in);
+    if let (Some(heap), Some(in_arena)) = (heap.allocations, in_arena.allocations) {
+        println!(
+            "parse_arena/day{day:02}: {heap} allocations on the heap, {in_arena} with an arena"
+        );
+    }
+}
+
+/// The days whose parsing has an arena version, on the inputs of [`bench_parsers`].
+#[cfg(feature = "arena")]
+fn bench_parsers_arena(criterion: &mut Criterion) {
+    bench_parse_arena::<day_03::Day03>(criterion, 3, 20);
+    bench_parse_arena::<day_04::Day04>(criterion, 4, 20);
+    bench_parse_arena::<day_08::Day08>(criterion, 8, 20);
+    bench_parse_arena::<day_12::Day12>(criterion, 12, 20);
+}
+
+fn estimates_path(day: u8, step: &str) -> PathBuf {
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| root().join("target"), PathBuf::from);
+    target.join(format!("criterion/day{day:02}/{step}/new/estimates.json"))
+}
+
+/// The mean time criterion estimated for a step, if it was measured after `since`. Steps that
+/// were filtered out keep their estimate from an earlier run, and are left out.
+fn mean_since(day: u8, step: &str, since: SystemTime) -> Option<Duration> {
+    let path = estimates_path(day, step);
+    if fs::metadata(&path).and_then(|meta| meta.modified()).ok()? < since {
+        return None;
+    }
+    let estimates = fs::read_to_string(path).ok()?;
+    let (_, mean) = estimates.split_once("\"mean\"")?;
+    let (_, value) = mean.split_once("\"point_estimate\":")?;
+    let end = value.find([',', '}'])?;
+    let nanos = value[..end].trim().parse::<f64>().ok()?;
+    Some(Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn print_summary(days: &[u8], since: SystemTime) {
+    println!();
+    println!(
+        "{:>5} {:>12} {:>12} {:>12}",
+        "day", STEPS[0], STEPS[1], STEPS[2]
+    );
+    for &day in days {
+        let cells = STEPS.map(|step| {
+            mean_since(day, step, since)
+                .map_or_else(|| "-".to_string(), |mean| format!("{mean:.2?}"))
+        });
+        if cells.iter().all(|cell| cell == "-") {
+            continue;
+        }
+        println!(
+            "{day:>5} {:>12} {:>12} {:>12}",
+            cells[0], cells[1], cells[2]
+        );
+    }
+}
+
+fn main() {
+    let start = SystemTime::now();
+    let mut criterion = Criterion::default().configure_from_args();
+    let days = bench_days(&mut criterion);
+    bench_day01_wide(&mut criterion);
+    bench_day04_many_boards(&mut criterion);
+    bench_day05_parallel(&mut criterion);
+    bench_day08_parallel(&mut criterion);
+    bench_day12_parallel(&mut criterion);
+    bench_queues(&mut criterion);
+    bench_parsers(&mut criterion);
+    #[cfg(feature = "arena")]
+    bench_parsers_arena(&mut criterion);
+    criterion.final_summary();
+    print_summary(&days, start);
+}
@@ -0,0 +1,10 @@
+#![no_main]
+
+use advent_of_code_2021::solver;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(input) {
+        let _ = solver::solver(4).unwrap().check(input);
+    }
+});
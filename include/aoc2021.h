@@ -0,0 +1,58 @@
+#ifndef AOC2021_H
+#define AOC2021_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The outcome of a call to [`aoc2021_solve`].
+ */
+typedef enum Aoc2021Status {
+  /**
+   * The answer was written to the output buffer.
+   */
+  AOC2021_STATUS_OK = 0,
+  /**
+   * A pointer was null, or the input was not UTF-8. Nothing was written.
+   */
+  AOC2021_STATUS_INVALID_ARGUMENT = 1,
+  /**
+   * The day is not compiled in. A message was written to the output buffer.
+   */
+  AOC2021_STATUS_UNKNOWN_DAY = 2,
+  /**
+   * There is no such part, the input is malformed, or it has no solution. A message was
+   * written to the output buffer.
+   */
+  AOC2021_STATUS_SOLVE_FAILED = 3,
+  /**
+   * The output buffer is too small. Nothing was written, but the needed length was.
+   */
+  AOC2021_STATUS_BUFFER_TOO_SMALL = 4,
+} Aoc2021Status;
+
+/**
+ * Solves one part of a day. Answers are text, since some parts (day 13) answer with letters.
+ *
+ * The answer, or an error message, is written to `out` as UTF-8 followed by a NUL byte, and its
+ * length without the NUL is written to `written`. If `out_len` is too small, nothing is written
+ * to `out` and [`Aoc2021Status::BufferTooSmall`] is returned; call again with a buffer of at
+ * least `*written + 1` bytes. `out` may be null when `out_len` is 0, to ask for the length.
+ * Trailing line breaks in the input are ignored.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes, `out` to `out_len` writable bytes, and
+ * `written` to a writable `size_t`. The buffers must not overlap.
+ */
+enum Aoc2021Status aoc2021_solve(uint8_t day,
+                                 uint8_t part,
+                                 const uint8_t *input,
+                                 size_t input_len,
+                                 char *out,
+                                 size_t out_len,
+                                 size_t *written);
+
+#endif  /* AOC2021_H */
@@ -0,0 +1,161 @@
+//! [`solve_all`]: every implemented day of a year, solved on the inputs at hand.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use rayon::prelude::*;
+
+use crate::Error;
+use crate::input;
+use crate::solver::{self, TimedRun};
+
+/// Where [`solve_all`] finds the inputs.
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// The inputs cached under `root` by `aoc fetch`, including compressed ones.
+    Cache { root: PathBuf, year: u16 },
+    /// The inputs of `year`, by day.
+    Given {
+        year: u16,
+        inputs: BTreeMap<u8, String>,
+    },
+}
+
+impl InputSource {
+    /// The inputs of the current directory's cache, as the CLI uses them.
+    #[must_use]
+    pub const fn cache(year: u16) -> Self {
+        Self::Cache {
+            root: PathBuf::new(),
+            year,
+        }
+    }
+
+    const fn year(&self) -> u16 {
+        match self {
+            Self::Cache { year, .. } | Self::Given { year, .. } => *year,
+        }
+    }
+
+    /// The input of `day`, if there is one and it can be read.
+    fn input(&self, day: u8) -> Option<String> {
+        match self {
+            Self::Cache { root, year } => {
+                input::read_file(&input::cached_path(root, *year, day)).ok()
+            }
+            Self::Given { inputs, .. } => inputs.get(&day).cloned(),
+        }
+    }
+}
+
+/// The answers and timings of one day, or why it failed.
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u8,
+    pub result: Result<TimedRun, Error>,
+}
+
+/// Solves both parts of every implemented day that has an input, in order of day.
+///
+/// Days without an input are left out. The days are solved in parallel, so their timings are
+/// only a rough guide; [`crate::report::gather`] solves them one at a time.
+#[must_use]
+pub fn solve_all(inputs: &InputSource) -> Vec<DayResult> {
+    let mut results = solver::solvers_in(inputs.year())
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .filter_map(|(day, solver)| {
+            let input = inputs.input(day)?;
+            Some(DayResult {
+                day,
+                result: solver.solve_timed(&input, &[1, 2]),
+            })
+        })
+        .collect::<Vec<_>>();
+    results.sort_by_key(|result| result.day);
+    results
+}
+
+#[cfg(all(test, feature = "day01", feature = "day02"))]
+mod tests {
+    use super::*;
+    use crate::fetcher;
+
+    #[test]
+    fn test_solve_all() {
+        let inputs = InputSource::Given {
+            year: fetcher::YEAR,
+            inputs: BTreeMap::from([
+                (
+                    2,
+                    "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2".to_string(),
+                ),
+                (
+                    1,
+                    "199\n200\n208\n210\n200\n207\n240\n269\n260\n263".to_string(),
+                ),
+                (26, String::new()),
+            ]),
+        };
+        let results = solve_all(&inputs);
+        assert_eq!(
+            results.iter().map(|result| result.day).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        let answers = |result: &DayResult| {
+            result
+                .result
+                .as_ref()
+                .unwrap()
+                .parts
+                .iter()
+                .map(|part| part.answer.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(answers(&results[0]), ["7", "5"]);
+        assert_eq!(answers(&results[1]), ["150", "900"]);
+    }
+
+    #[test]
+    fn test_solve_all_errors() {
+        let inputs = InputSource::Given {
+            year: fetcher::YEAR,
+            inputs: BTreeMap::from([(1, "one\ntwo".to_string())]),
+        };
+        let results = solve_all(&inputs);
+        assert!(matches!(
+            results.as_slice(),
+            [DayResult {
+                day: 1,
+                result: Err(Error::InvalidInput(_))
+            }]
+        ));
+        assert!(
+            solve_all(&InputSource::Given {
+                year: 2015,
+                inputs: BTreeMap::from([(1, "1".to_string())]),
+            })
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_solve_all_cache() {
+        let root = std::env::temp_dir().join(format!("aoc-all-{}", std::process::id()));
+        let path = fetcher::input_path(&root, fetcher::YEAR, 1);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "1\n2\n").unwrap();
+        let results = solve_all(&InputSource::Cache {
+            root: root.clone(),
+            year: fetcher::YEAR,
+        });
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].result.as_ref().unwrap().parts[0]
+                .answer
+                .to_string(),
+            "1"
+        );
+    }
+}
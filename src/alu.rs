@@ -0,0 +1,471 @@
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Unknown register: {0}")]
+    UnknownRegister(String),
+    #[error("Unknown instruction: {0}")]
+    UnknownInstruction(String),
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum RunError {
+    #[error("Ran out of input at instruction {0}")]
+    MissingInput(usize),
+    #[error("Division by zero at instruction {0}")]
+    DivisionByZero(usize),
+    #[error("Invalid modulo at instruction {0}")]
+    InvalidModulo(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    pub const ALL: [Self; 4] = [Self::W, Self::X, Self::Y, Self::Z];
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::W => "w",
+            Self::X => "x",
+            Self::Y => "y",
+            Self::Z => "z",
+        })
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "w" => Self::W,
+            "x" => Self::X,
+            "y" => Self::Y,
+            "z" => Self::Z,
+            _ => return Err(ParseError::UnknownRegister(s.to_string())),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operand {
+    Register(Register),
+    Literal(i64),
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Register(register) => register.fmt(f),
+            Self::Literal(value) => value.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with(|ch: char| ch == '-' || ch.is_ascii_digit()) {
+            Ok(Self::Literal(s.parse()?))
+        } else {
+            Ok(Self::Register(s.parse()?))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, target, operand) = match *self {
+            Self::Inp(target) => return write!(f, "inp {target}"),
+            Self::Add(target, operand) => ("add", target, operand),
+            Self::Mul(target, operand) => ("mul", target, operand),
+            Self::Div(target, operand) => ("div", target, operand),
+            Self::Mod(target, operand) => ("mod", target, operand),
+            Self::Eql(target, operand) => ("eql", target, operand),
+        };
+        write!(f, "{name} {target} {operand}")
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_ascii_whitespace();
+        let name = parts.next().ok_or(ParseError::SyntaxError)?;
+        let target = parts.next().ok_or(ParseError::SyntaxError)?.parse()?;
+        if name == "inp" {
+            return match parts.next() {
+                None => Ok(Self::Inp(target)),
+                Some(_) => Err(ParseError::SyntaxError),
+            };
+        }
+        let operand = parts.next().ok_or(ParseError::SyntaxError)?.parse()?;
+        if parts.next().is_some() {
+            return Err(ParseError::SyntaxError);
+        }
+        Ok(match name {
+            "add" => Self::Add(target, operand),
+            "mul" => Self::Mul(target, operand),
+            "div" => Self::Div(target, operand),
+            "mod" => Self::Mod(target, operand),
+            "eql" => Self::Eql(target, operand),
+            _ => return Err(ParseError::UnknownInstruction(name.to_string())),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Registers<T>([T; 4]);
+
+impl<T> Registers<T> {
+    #[must_use]
+    pub const fn new(values: [T; 4]) -> Self {
+        Self(values)
+    }
+}
+
+impl<T> Index<Register> for Registers<T> {
+    type Output = T;
+
+    fn index(&self, index: Register) -> &Self::Output {
+        &self.0[index.index()]
+    }
+}
+
+impl<T> IndexMut<Register> for Registers<T> {
+    fn index_mut(&mut self, index: Register) -> &mut Self::Output {
+        &mut self.0[index.index()]
+    }
+}
+
+/// A closed range of possible register values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Interval {
+    #[must_use]
+    pub const fn new(min: i64, max: i64) -> Self {
+        Self { min, max }
+    }
+
+    #[must_use]
+    pub const fn exact(value: i64) -> Self {
+        Self::new(value, value)
+    }
+
+    #[must_use]
+    pub const fn contains(self, value: i64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    fn intersect(self, other: Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min <= max).then_some(Self::new(min, max))
+    }
+
+    fn hull(values: impl IntoIterator<Item = i64>) -> Self {
+        values
+            .into_iter()
+            .fold(Self::new(i64::MAX, i64::MIN), |acc, x| {
+                Self::new(acc.min.min(x), acc.max.max(x))
+            })
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    const fn add(self, other: Self) -> Self {
+        Self::new(
+            self.min.saturating_add(other.min),
+            self.max.saturating_add(other.max),
+        )
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self::hull([
+            self.min.saturating_mul(other.min),
+            self.min.saturating_mul(other.max),
+            self.max.saturating_mul(other.min),
+            self.max.saturating_mul(other.max),
+        ])
+    }
+
+    /// Truncating division, assuming the divisor is never zero at runtime.
+    fn div(self, other: Self) -> Option<Self> {
+        let quotients = |d: Self| {
+            Self::hull([
+                self.min / d.min,
+                self.min / d.max,
+                self.max / d.min,
+                self.max / d.max,
+            ])
+        };
+        let negative = other.intersect(Self::new(i64::MIN, -1)).map(quotients);
+        let positive = other.intersect(Self::new(1, i64::MAX)).map(quotients);
+        match (negative, positive) {
+            (Some(a), Some(b)) => Some(a.union(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Remainder, assuming a non-negative dividend and a positive divisor at runtime.
+    fn rem(self, other: Self) -> Option<Self> {
+        let a = self.intersect(Self::new(0, i64::MAX))?;
+        let b = other.intersect(Self::new(1, i64::MAX))?;
+        let modulus = b.min;
+        if b.max == modulus && a.max - a.min < modulus && a.min % modulus <= a.max % modulus {
+            Some(Self::new(a.min % modulus, a.max % modulus))
+        } else {
+            Some(Self::new(0, a.max.min(b.max - 1)))
+        }
+    }
+
+    fn eql(self, other: Self) -> Self {
+        if self.min == self.max && self == other {
+            Self::exact(1)
+        } else if self.intersect(other).is_none() {
+            Self::exact(0)
+        } else {
+            Self::new(0, 1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+/// Writes one instruction per line, without a line ending after the last.
+impl Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            instruction.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let instructions = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { instructions })
+    }
+}
+
+impl Program {
+    #[must_use]
+    pub const fn new(instructions: Vec<Instruction>) -> Self {
+        Self { instructions }
+    }
+
+    #[must_use]
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// The number of `inp` instructions.
+    #[must_use]
+    pub fn input_count(&self) -> usize {
+        self.instructions
+            .iter()
+            .filter(|instr| matches!(instr, Instruction::Inp(_)))
+            .count()
+    }
+
+    /// Runs the program on concrete inputs, starting with all registers at zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program reads past the end of `inputs`, divides by zero, or takes
+    /// the remainder of a negative number or by a non-positive number.
+    pub fn run(&self, inputs: &[i64]) -> Result<Registers<i64>, RunError> {
+        let mut regs = Registers::<i64>::default();
+        let mut inputs = inputs.iter().copied();
+        for (i, &instr) in self.instructions.iter().enumerate() {
+            let value = |regs: &Registers<i64>, operand| match operand {
+                Operand::Register(reg) => regs[reg],
+                Operand::Literal(n) => n,
+            };
+            match instr {
+                Instruction::Inp(a) => regs[a] = inputs.next().ok_or(RunError::MissingInput(i))?,
+                Instruction::Add(a, b) => regs[a] = regs[a].wrapping_add(value(&regs, b)),
+                Instruction::Mul(a, b) => regs[a] = regs[a].wrapping_mul(value(&regs, b)),
+                Instruction::Div(a, b) => {
+                    regs[a] = regs[a]
+                        .checked_div(value(&regs, b))
+                        .ok_or(RunError::DivisionByZero(i))?;
+                }
+                Instruction::Mod(a, b) => {
+                    let b = value(&regs, b);
+                    if regs[a] < 0 || b <= 0 {
+                        return Err(RunError::InvalidModulo(i));
+                    }
+                    regs[a] %= b;
+                }
+                Instruction::Eql(a, b) => regs[a] = i64::from(regs[a] == value(&regs, b)),
+            }
+        }
+        Ok(regs)
+    }
+
+    /// Propagates intervals through the program. Each `inp` reads the next interval from
+    /// `inputs`, or `1..=9` once they run out. Returns `None` if some instruction can never
+    /// execute without an error, so no input in the given ranges is valid.
+    #[must_use]
+    pub fn bounds(&self, inputs: &[Interval]) -> Option<Registers<Interval>> {
+        let mut regs = Registers::new([Interval::exact(0); 4]);
+        let mut inputs = inputs.iter().copied();
+        for &instr in &self.instructions {
+            let value = |regs: &Registers<Interval>, operand| match operand {
+                Operand::Register(reg) => regs[reg],
+                Operand::Literal(n) => Interval::exact(n),
+            };
+            match instr {
+                Instruction::Inp(a) => regs[a] = inputs.next().unwrap_or(Interval::new(1, 9)),
+                Instruction::Add(a, b) => regs[a] = regs[a].add(value(&regs, b)),
+                Instruction::Mul(a, b) => regs[a] = regs[a].mul(value(&regs, b)),
+                Instruction::Div(a, b) => regs[a] = regs[a].div(value(&regs, b))?,
+                Instruction::Mod(a, b) => regs[a] = regs[a].rem(value(&regs, b))?,
+                Instruction::Eql(a, b) => regs[a] = regs[a].eql(value(&regs, b)),
+            }
+        }
+        Some(regs)
+    }
+
+    /// Whether `register` may end up holding `target` when the first inputs are fixed to
+    /// `prefix` and the remaining inputs range over `1..=9`. A `false` answer is exact; a `true`
+    /// answer may be a false positive.
+    #[must_use]
+    pub fn may_produce(&self, prefix: &[i64], register: Register, target: i64) -> bool {
+        let inputs = prefix
+            .iter()
+            .map(|&digit| Interval::exact(digit))
+            .collect::<Vec<_>>();
+        self.bounds(&inputs)
+            .is_some_and(|regs| regs[register].contains(target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TO_BINARY: &str = "\
+        inp w\n\
+        add z w\n\
+        mod z 2\n\
+        div w 2\n\
+        add y w\n\
+        mod y 2\n\
+        div w 2\n\
+        add x w\n\
+        mod x 2\n\
+        div w 2\n\
+        mod w 2\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let program = "inp x\nmul x -1".parse::<Program>().unwrap();
+        assert_eq!(
+            program.instructions(),
+            [
+                Instruction::Inp(Register::X),
+                Instruction::Mul(Register::X, Operand::Literal(-1)),
+            ]
+        );
+        assert!("mul x".parse::<Program>().is_err());
+        assert!("nop x 1".parse::<Program>().is_err());
+        assert!("add q 1".parse::<Program>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let program = TO_BINARY.parse::<Program>().unwrap();
+        assert_eq!(program.to_string(), TO_BINARY);
+        let program = "inp x\nmul x -1\neql z w".parse::<Program>().unwrap();
+        assert_eq!(program.to_string(), "inp x\nmul x -1\neql z w");
+    }
+
+    #[test]
+    fn test_run() {
+        let program = TO_BINARY.parse::<Program>().unwrap();
+        let regs = program.run(&[13]).unwrap();
+        assert_eq!(Register::ALL.map(|reg| regs[reg]), [1, 1, 0, 1]);
+        assert_eq!(program.run(&[]), Err(RunError::MissingInput(0)));
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let program = "inp x\ndiv x 0".parse::<Program>().unwrap();
+        assert_eq!(program.run(&[5]), Err(RunError::DivisionByZero(1)));
+        let program = "inp x\nmod x 3".parse::<Program>().unwrap();
+        assert_eq!(program.run(&[-5]), Err(RunError::InvalidModulo(1)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let program = TO_BINARY.parse::<Program>().unwrap();
+        let regs = program.bounds(&[]).unwrap();
+        for reg in Register::ALL {
+            assert!(regs[reg].min >= 0 && regs[reg].max <= 1);
+        }
+        let program = "inp x\ninp y\neql x y".parse::<Program>().unwrap();
+        assert!(program.may_produce(&[3], Register::X, 1));
+        assert!(!program.may_produce(&[3, 4], Register::X, 1));
+        let program = "inp x\nmul x 0\ndiv y x".parse::<Program>().unwrap();
+        assert_eq!(program.bounds(&[]), None);
+    }
+}
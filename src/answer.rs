@@ -0,0 +1,142 @@
+//! The answer to a part, whatever its type, so that every day can be printed, compared and
+//! submitted the same way.
+
+use std::fmt::{self, Display};
+
+use crate::solver::NoAnswer;
+use crate::util::geometry::Point;
+use crate::util::grid::Grid;
+use crate::util::ocr;
+use crate::util::render::{self, Backend};
+
+/// A picture of lit and unlit pixels, such as the letters of day 13.
+pub type BinaryImage = Grid<bool>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    /// Letters drawn in pixels. Shown as the letters when they can be read, and as the picture
+    /// otherwise.
+    Grid(BinaryImage),
+}
+
+impl Answer {
+    /// The number, if the answer is one.
+    #[must_use]
+    pub const fn as_int(&self) -> Option<i128> {
+        match self {
+            Self::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The text to type in as the answer. A picture only has one if its letters can be read.
+    #[must_use]
+    pub fn submittable(&self) -> Option<String> {
+        match self {
+            Self::Grid(image) => Self::letters(image),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// The letters in a picture answer, or `None` if they can not be read.
+    #[must_use]
+    pub fn letters(image: &BinaryImage) -> Option<String> {
+        let points = image
+            .positions()
+            .filter(|&pos| image[pos])
+            .map(|[row, col]| Point { x: col, y: row })
+            .collect::<Vec<_>>();
+        ocr::recognize(points)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => value.fmt(f),
+            Self::Text(text) => text.fmt(f),
+            Self::Grid(image) => match Self::letters(image) {
+                Some(letters) => f.write_str(&letters),
+                // Starts on a new line, so that the picture lines up under a label.
+                None => write!(f, "\n{}", render::render(image, Backend::Plain)),
+            },
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty)*) => {$(
+        impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                Self::Int(i128::from(value))
+            }
+        }
+    )*};
+}
+
+from_int!(u8 u16 u32 u64 i8 i16 i32 i64 i128);
+
+/// Integers that may not fit an `i128`, which are kept as text if they do not.
+macro_rules! try_from_int {
+    ($($int:ty)*) => {$(
+        impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                i128::try_from(value).map_or_else(|_| Self::Text(value.to_string()), Self::Int)
+            }
+        }
+    )*};
+}
+
+try_from_int!(u128 usize isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<BinaryImage> for Answer {
+    fn from(image: BinaryImage) -> Self {
+        Self::Grid(image)
+    }
+}
+
+impl From<NoAnswer> for Answer {
+    fn from(NoAnswer: NoAnswer) -> Self {
+        Self::Text(NoAnswer.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(7_u8), Answer::Int(7));
+        assert_eq!(Answer::from(-7_i64).as_int(), Some(-7));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from(u128::MAX).as_int(), None);
+        assert_eq!(Answer::from("EFJ"), Answer::Text("EFJ".to_string()));
+        assert_eq!(Answer::from(NoAnswer).to_string(), "-");
+    }
+
+    #[test]
+    fn test_grid() {
+        let points = ocr::draw("FU").unwrap();
+        let image = render::from_points(points).unwrap();
+        assert_eq!(Answer::from(image).to_string(), "FU");
+        let square = Grid::new(vec![true, true, true, false], 2, 2);
+        assert_eq!(Answer::from(square.clone()).to_string(), "\n##\n#.");
+        assert_eq!(Answer::Grid(square).submittable(), None);
+        assert_eq!(Answer::Int(-3).submittable(), Some("-3".to_string()));
+    }
+}
@@ -0,0 +1,723 @@
+#![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+#[cfg(feature = "day10")]
+use std::io::Read;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+#[cfg(any(feature = "day04", feature = "day11"))]
+use std::time::Duration;
+use std::time::Instant;
+
+use advent_of_code_2021::fetcher::{self, Fetched};
+use advent_of_code_2021::input::{self, Source};
+use advent_of_code_2021::solver::TimedRun;
+#[cfg(any(feature = "day04", feature = "day11"))]
+use advent_of_code_2021::util::animation::Pace;
+#[cfg(feature = "day05")]
+use advent_of_code_2021::util::grid::Grid;
+use advent_of_code_2021::util::render::Backend;
+use advent_of_code_2021::util::style::{self, ColorChoice};
+use advent_of_code_2021::verify::{self, Outcome};
+use advent_of_code_2021::{
+    DayResult, InputSource, geninput, json, repl, report, serve, solve_all, solver, submit,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+#[derive(Debug, Parser)]
+#[command(about = "Runs the Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// When to color pictures. `auto` colors a terminal, unless `NO_COLOR` is set.
+    #[arg(long, global = true, value_enum, default_value_t = Color::Auto)]
+    color: Color,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solves one day's puzzle and prints the answers.
+    Run(RunArgs),
+    /// Downloads a day's puzzle input, using the session cookie in `AOC_SESSION`.
+    Fetch(FetchArgs),
+    /// Solves one part on the cached input and submits the answer.
+    Submit(SubmitArgs),
+    /// Checks the solutions against the answers recorded in `input/2021/answers.toml`.
+    Verify(VerifyArgs),
+    /// Runs the golden cases in `tests/data` and checks their answers.
+    Check(CheckArgs),
+    /// Solves every day that has a cached input, in parallel, and prints a table of the results.
+    All(AllArgs),
+    /// Solves every day that has a cached input and writes the answers, timings and pictures to
+    /// one HTML page.
+    Report(ReportArgs),
+    /// Answers `POST /solve/{day}/{part}` requests, with the input as the body, over HTTP.
+    Serve(ServeArgs),
+    /// Solves inputs pasted into the terminal, and solves them again after editing.
+    Repl(ReplArgs),
+    /// Prints a random puzzle input, for stress tests.
+    Gen(GenArgs),
+    /// Steers the day 2 submarine by commands read from standard input, and prints where it is
+    /// after each one.
+    #[cfg(feature = "day02")]
+    Pilot,
+    /// Checks the day 10 chunks read from standard input, and prints where each corrupted line
+    /// goes wrong.
+    #[cfg(feature = "day10")]
+    Diagnose,
+    /// Draws a puzzle in the terminal: the day 4 bingo game, a heatmap of the day 5 vents, the
+    /// day 9 basins, or the day 11 octopuses flashing.
+    #[cfg(any(
+        feature = "day04",
+        feature = "day05",
+        feature = "day09",
+        feature = "day11"
+    ))]
+    Viz(VizArgs),
+    /// Shows every day's answers and timings in a terminal dashboard.
+    #[cfg(feature = "tui")]
+    Tui,
+    /// Saves a picture of the puzzle: a PNG for days 5, 9, 13 and 20, and a GIF for day 11.
+    #[cfg(feature = "image")]
+    Export(ExportArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// The year of the puzzle.
+    #[arg(long, default_value_t = fetcher::YEAR)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Only solve this part. Both parts are solved by default.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// The puzzle input. Defaults to `input/<YEAR>/day<N>.txt`.
+    #[arg(long, conflicts_with = "stdin")]
+    input: Option<PathBuf>,
+    /// Read the puzzle input from standard input.
+    #[arg(long)]
+    stdin: bool,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Also draw the puzzle, for days that have a picture. Only drawn with the text format.
+    #[arg(long, value_enum)]
+    picture: Option<Picture>,
+    /// Solve while reading the input, without holding all of it in memory. Only days 1, 2 and
+    /// 10, and part 1 of day 7, can be solved this way.
+    #[arg(long, conflicts_with = "picture")]
+    stream: bool,
+    /// Also print what the solution works out on the way to the answers, such as the paper after
+    /// each day 13 fold. Only printed with the text format.
+    #[arg(long, conflicts_with = "stream")]
+    explain: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    /// One JSON object per line and part, with the answer and timings in nanoseconds.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+impl From<Color> for ColorChoice {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Auto => Self::Auto,
+            Color::Always => Self::Always,
+            Color::Never => Self::Never,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Picture {
+    /// Two pixels per character.
+    HalfBlocks,
+    /// Eight pixels per character.
+    Braille,
+    /// One pixel per character, as `#` and `.`.
+    Plain,
+}
+
+impl From<Picture> for Backend {
+    fn from(picture: Picture) -> Self {
+        match picture {
+            Picture::HalfBlocks => Self::HalfBlocks,
+            Picture::Braille => Self::Braille,
+            Picture::Plain => Self::Plain,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct FetchArgs {
+    /// The year of the puzzle.
+    #[arg(long, default_value_t = fetcher::YEAR)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Debug, Args)]
+struct SubmitArgs {
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// Only check this day. Every day with recorded answers is checked by default.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+#[derive(Debug, Args)]
+struct CheckArgs {
+    /// Only check this day's cases.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Where the cases are, as `dayNN/input/<name>.txt` with the answers in
+    /// `dayNN/expected/<name>.txt`.
+    #[arg(long, default_value = verify::CASES_DIR)]
+    dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct AllArgs {
+    /// The year of the puzzle.
+    #[arg(long, default_value_t = fetcher::YEAR)]
+    year: u16,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Debug, Args)]
+struct ReportArgs {
+    /// The year of the puzzle.
+    #[arg(long, default_value_t = fetcher::YEAR)]
+    year: u16,
+    /// How many times to solve each day, for the sparkline of its solve times.
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+    #[arg(long, default_value = "report.html")]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+    #[arg(long, default_value_t = 2021)]
+    port: u16,
+}
+
+#[derive(Debug, Args)]
+struct ReplArgs {
+    /// The line that ends a pasted input.
+    #[arg(long, default_value = "EOF")]
+    eof: String,
+}
+
+#[derive(Debug, Args)]
+struct GenArgs {
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// How many times larger than a real input to make it.
+    #[arg(long, default_value_t = 1)]
+    scale: usize,
+    /// The same seed always produces the same input.
+    #[arg(long, default_value_t = 2021)]
+    seed: u64,
+}
+
+#[cfg(feature = "image")]
+#[derive(Debug, Args)]
+struct ExportArgs {
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The puzzle input. Defaults to `input/2021/day<N>.txt`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    #[arg(long)]
+    output: PathBuf,
+    /// Only save this part of the day 5 vents, given as `X,Y,WIDTH,HEIGHT`.
+    #[arg(long, value_parser = parse_crop)]
+    crop: Option<[usize; 4]>,
+}
+
+#[cfg(any(
+    feature = "day04",
+    feature = "day05",
+    feature = "day09",
+    feature = "day11"
+))]
+#[derive(Debug, Args)]
+struct VizArgs {
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The puzzle input. Defaults to `input/2021/day<N>.txt`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Milliseconds between frames.
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+    /// Wait for Enter before each frame, instead of `--delay`.
+    #[arg(long)]
+    step: bool,
+    /// Stop after this many steps, if the animation has not ended by itself.
+    #[arg(long, default_value_t = 1000)]
+    steps: usize,
+    /// Only draw this part of the day 5 vents, given as `X,Y,WIDTH,HEIGHT`.
+    #[arg(long, value_parser = parse_crop)]
+    crop: Option<[usize; 4]>,
+    /// Draw squares of this many points as one, to fit the day 5 vents in the terminal. By
+    /// default they are zoomed out to 120 columns.
+    #[arg(long)]
+    zoom: Option<usize>,
+}
+
+#[cfg(any(feature = "day04", feature = "day11"))]
+impl VizArgs {
+    /// How the animation moves on to its next frame, from `--step` and `--delay`.
+    const fn pace(&self) -> Pace {
+        if self.step {
+            Pace::Step
+        } else {
+            Pace::Auto(Duration::from_millis(self.delay))
+        }
+    }
+}
+
+fn source(args: &RunArgs) -> Source {
+    if args.stdin {
+        Source::Stdin
+    } else {
+        Source::File(
+            args.input
+                .clone()
+                .unwrap_or_else(|| input::cached_path(Path::new(""), args.year, args.day)),
+        )
+    }
+}
+
+fn print_json(day: u8, run: &TimedRun) {
+    for line in json::timed_parts(day, run) {
+        println!("{line}");
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    let solver = solver::solver_in(args.year, args.day).ok_or("No solver for that day")?;
+    let parts = args.part.map_or_else(|| vec![1, 2], |part| vec![part]);
+    let source = source(args);
+    let (input, run) = if args.stream {
+        let run = input::solve_streaming(args.day, &parts, source.reader()?)
+            .ok_or("That day can not be solved while streaming its input")??;
+        (None, run)
+    } else {
+        let input = source.read()?;
+        let run = solver.solve_timed(&input, &parts)?;
+        (Some(input), run)
+    };
+    match args.format {
+        Format::Text => {
+            for part in &run.parts {
+                println!("Day {} part {}: {}", args.day, part.part, part.answer);
+            }
+            println!(
+                "Parse: {:?}, solve: {:?}",
+                run.parse.elapsed, run.solve.elapsed
+            );
+            if let Some(parse_peak) = run.parse.peak_bytes {
+                println!(
+                    "Peak heap: {parse_peak} bytes parsing, {} bytes solving",
+                    run.solve.peak_bytes.unwrap_or(0)
+                );
+            }
+            if let Some(parse_allocations) = run.parse.allocations {
+                println!(
+                    "Allocations: {parse_allocations} parsing, {} solving",
+                    run.solve.allocations.unwrap_or(0)
+                );
+            }
+            if args.explain
+                && let Some(input) = &input
+            {
+                let explain = solver.explain(input)?;
+                if explain.is_empty() {
+                    println!("Day {} has nothing to explain", args.day);
+                } else {
+                    print!("{explain}");
+                }
+            }
+            if let Some(picture) = args.picture
+                && let Some(input) = &input
+                && let Some(picture) = solver.visualize(input, picture.into())?
+            {
+                println!("{picture}");
+            }
+        }
+        Format::Json => print_json(args.day, &run),
+    }
+    Ok(())
+}
+
+fn fetch(args: &FetchArgs) -> Result<(), Box<dyn Error>> {
+    let session = fetcher::session()?;
+    match fetcher::fetch_input(Path::new(""), args.year, args.day, &session)? {
+        Fetched::Cached(path) => println!("Already cached: {}", path.display()),
+        Fetched::Downloaded(path) => println!("Downloaded: {}", path.display()),
+    }
+    Ok(())
+}
+
+fn submit(args: &SubmitArgs) -> Result<(), Box<dyn Error>> {
+    let session = fetcher::session()?;
+    let solver = solver::solver(args.day).ok_or("No solver for that day")?;
+    let path = input::cached_path(Path::new(""), fetcher::YEAR, args.day);
+    let input = input::read_file(&path)
+        .map_err(|error| format!("Can not read {}: {error}", path.display()))?;
+    let answer = solver.solve_part(&input, args.part)?;
+    let verdict = submit::submit(Path::new(""), args.day, args.part, &answer, &session)?;
+    println!("Day {} part {}: {answer} is {verdict}", args.day, args.part);
+    Ok(())
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), Box<dyn Error>> {
+    let root = Path::new("");
+    let answers = verify::load_answers(root).map_err(|error| {
+        format!(
+            "Can not load {}: {error}",
+            verify::answers_path(root).display()
+        )
+    })?;
+    let days = args
+        .day
+        .map_or_else(|| answers.days().collect(), |day| vec![day]);
+    let mut failures = 0;
+    for day in days {
+        for report in verify::verify_day(root, day, &answers) {
+            failures += print_report(&format!("Day {}", report.day), &report);
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{failures} answers did not match").into());
+    }
+    Ok(())
+}
+
+/// Prints how a part went after `label`, and returns 1 if it did not pass.
+fn print_report(label: &str, report: &verify::Report) -> usize {
+    let status = match &report.outcome {
+        Outcome::Pass => "ok".to_string(),
+        Outcome::Fail { expected, actual } => {
+            format!("FAILED, expected {expected} but got {actual}")
+        }
+        Outcome::Error(message) => format!("ERROR, {message}"),
+    };
+    println!("{label} part {}: {status}", report.part);
+    usize::from(report.outcome != Outcome::Pass)
+}
+
+fn check(args: &CheckArgs) -> Result<(), Box<dyn Error>> {
+    let cases = verify::discover_cases(&args.dir)
+        .map_err(|error| format!("Can not list {}: {error}", args.dir.display()))?;
+    let mut failures = 0;
+    let mut checked = 0;
+    for case in cases {
+        if args.day.is_some_and(|day| day != case.day) {
+            continue;
+        }
+        checked += 1;
+        let label = format!("Day {} {}", case.day, case.name);
+        for report in verify::check_case(&case) {
+            failures += print_report(&label, &report);
+        }
+    }
+    if checked == 0 {
+        return Err(format!("No cases in {}", args.dir.display()).into());
+    }
+    if failures > 0 {
+        return Err(format!("{failures} answers did not match").into());
+    }
+    Ok(())
+}
+
+/// Solves every day with a cached input on the rayon thread pool. Peak memory is not reported,
+/// since the days share the allocator while they run.
+fn all(args: &AllArgs) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    let results = solve_all(&InputSource::cache(args.year));
+    let wall = start.elapsed();
+    let mut failures = 0;
+    match args.format {
+        Format::Text => {
+            println!(
+                "{:>3} {:>20} {:>20} {:>12} {:>12}",
+                "day", "part 1", "part 2", "parse", "solve"
+            );
+            for DayResult { day, result } in &results {
+                match result {
+                    Ok(run) => {
+                        let answer = |i: usize| {
+                            run.parts
+                                .get(i)
+                                .map_or_else(String::new, |part| part.answer.to_string())
+                        };
+                        println!(
+                            "{day:>3} {:>20} {:>20} {:>12} {:>12}",
+                            answer(0),
+                            answer(1),
+                            format!("{:.2?}", run.parse.elapsed),
+                            format!("{:.2?}", run.solve.elapsed)
+                        );
+                    }
+                    Err(error) => {
+                        failures += 1;
+                        println!("{day:>3} Error: {error}");
+                    }
+                }
+            }
+            println!("Solved {} days in {wall:.2?}", results.len() - failures);
+        }
+        Format::Json => {
+            for DayResult { day, result } in &results {
+                match result {
+                    Ok(run) => print_json(*day, run),
+                    Err(error) => {
+                        failures += 1;
+                        eprintln!("Day {day}: {error}");
+                    }
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(format!("Failed days: {failures}").into());
+    }
+    Ok(())
+}
+
+fn write_report(args: &ReportArgs) -> Result<(), Box<dyn Error>> {
+    let days = report::gather(Path::new(""), args.year, args.runs);
+    if days.is_empty() {
+        return Err("No cached inputs to report on".into());
+    }
+    fs::write(&args.output, report::html(args.year, &days))?;
+    println!("Wrote {}", args.output.display());
+    Ok(())
+}
+
+fn serve(args: &ServeArgs) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind((args.host.as_str(), args.port))?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    serve::serve(&listener, &serve::Runner::Child(env::current_exe()?));
+    Ok(())
+}
+
+fn generate(args: &GenArgs) -> Result<(), Box<dyn Error>> {
+    let input = geninput::generate(args.day, args.scale, args.seed).ok_or("No such day")?;
+    print!("{input}");
+    Ok(())
+}
+
+#[cfg(feature = "image")]
+fn export(args: &ExportArgs) -> Result<(), Box<dyn Error>> {
+    use advent_of_code_2021::solver::Solver;
+    use advent_of_code_2021::year2021 as aoc;
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| input::cached_path(Path::new(""), fetcher::YEAR, args.day));
+    let input = input::read_file(&path)?;
+    let input = input.as_str();
+    let output = args.output.as_path();
+    match args.day {
+        #[cfg(feature = "day05")]
+        5 => {
+            let counts = aoc::day_05::overlap_counts(&aoc::day_05::Day05::parse(input)?)?;
+            aoc::day_05::export_png(&crop_counts(counts, args.crop), output)?;
+        }
+        #[cfg(feature = "day09")]
+        9 => aoc::day_09::export_png(&aoc::day_09::Day09::parse(input)?, output)?,
+        #[cfg(feature = "day11")]
+        11 => aoc::day_11::export_gif(&aoc::day_11::Day11::parse(input)?, 1000, output)?,
+        #[cfg(feature = "day13")]
+        13 => aoc::day_13::export_png(&aoc::day_13::Day13::parse(input)?.folded()?, output)?,
+        #[cfg(feature = "day20")]
+        20 => aoc::day_20::export_png(&aoc::day_20::Day20::parse(input)?, 50, output)?,
+        _ => return Err("No picture for that day".into()),
+    }
+    println!("Saved {}", output.display());
+    Ok(())
+}
+
+#[cfg(any(
+    feature = "day04",
+    feature = "day05",
+    feature = "day09",
+    feature = "day11",
+    feature = "image"
+))]
+fn parse_crop(s: &str) -> Result<[usize; 4], String> {
+    let values = s
+        .split(',')
+        .map(str::parse::<usize>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| error.to_string())?;
+    values
+        .try_into()
+        .map_err(|_| "Expected X,Y,WIDTH,HEIGHT".to_string())
+}
+
+/// The part of the day 5 vent counts to draw, from `--crop`.
+#[cfg(feature = "day05")]
+fn crop_counts(counts: Grid<u8>, crop: Option<[usize; 4]>) -> Grid<u8> {
+    match crop {
+        Some([x, y, width, height]) => counts.crop([y, x], width, height),
+        None => counts,
+    }
+}
+
+#[cfg(any(
+    feature = "day04",
+    feature = "day05",
+    feature = "day09",
+    feature = "day11"
+))]
+fn viz(args: &VizArgs) -> Result<(), Box<dyn Error>> {
+    use advent_of_code_2021::solver::Solver;
+    use advent_of_code_2021::year2021 as aoc;
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| input::cached_path(Path::new(""), fetcher::YEAR, args.day));
+    let input = input::read_file(&path)?;
+    let input = input.as_str();
+    let style = style::Style::current();
+    match args.day {
+        #[cfg(feature = "day04")]
+        4 => {
+            let bingo = aoc::day_04::Day04::parse(input)?;
+            aoc::day_04::visualize(&bingo, args.pace(), style, &mut io::stdout().lock())?;
+        }
+        #[cfg(feature = "day05")]
+        5 => {
+            let counts = aoc::day_05::overlap_counts(&aoc::day_05::Day05::parse(input)?)?;
+            let counts = crop_counts(counts, args.crop);
+            // Real inputs are about 1000 points wide, which would not fit a terminal.
+            let zoom = args.zoom.unwrap_or_else(|| counts.width().div_ceil(120));
+            print!("{}", aoc::day_05::heatmap(&counts, zoom, style));
+        }
+        #[cfg(feature = "day09")]
+        9 => {
+            let grid = aoc::day_09::Day09::parse(input)?;
+            print!(
+                "{}",
+                aoc::day_09::basin_map(&grid, &aoc::day_09::basins(&grid), style)
+            );
+        }
+        #[cfg(feature = "day11")]
+        11 => {
+            let grid = aoc::day_11::Day11::parse(input)?;
+            aoc::day_11::visualize(
+                &grid,
+                args.steps,
+                args.pace(),
+                style,
+                &mut io::stdout().lock(),
+            )?;
+        }
+        _ => return Err("No picture for that day".into()),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "day10")]
+fn diagnose() -> Result<(), Box<dyn Error>> {
+    let mut input = Vec::new();
+    io::stdin().read_to_end(&mut input)?;
+    for diagnostic in advent_of_code_2021::year2021::day_10::diagnostics(&input) {
+        println!("{diagnostic}");
+    }
+    Ok(())
+}
+
+/// Logs spans and events to standard error when `RUST_LOG` is set, for example to
+/// `RUST_LOG=debug` or `RUST_LOG=advent_of_code_2021::year2021::day_23=trace`. Each span logs its
+/// duration when it closes.
+fn init_tracing() {
+    if let Ok(filter) = EnvFilter::try_from_default_env() {
+        tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(io::stderr)
+            .init();
+    }
+}
+
+fn main() -> ExitCode {
+    init_tracing();
+    let cli = Cli::parse();
+    style::set_color_choice(cli.color.into());
+    let result = match cli.command {
+        Command::Run(args) => run(&args),
+        Command::Fetch(args) => fetch(&args),
+        Command::Submit(args) => submit(&args),
+        Command::Verify(args) => verify(&args),
+        Command::Check(args) => check(&args),
+        Command::All(args) => all(&args),
+        Command::Report(args) => write_report(&args),
+        Command::Serve(args) => serve(&args),
+        Command::Repl(args) => repl::run(
+            io::stdin().lock(),
+            &mut io::stdout().lock(),
+            &args.eof,
+            repl::external_editor,
+        )
+        .map_err(Into::into),
+        Command::Gen(args) => generate(&args),
+        #[cfg(feature = "day02")]
+        Command::Pilot => advent_of_code_2021::year2021::day_02::pilot(
+            io::stdin().lock(),
+            &mut io::stdout().lock(),
+        )
+        .map_err(Into::into),
+        #[cfg(feature = "day10")]
+        Command::Diagnose => diagnose(),
+        #[cfg(any(
+            feature = "day04",
+            feature = "day05",
+            feature = "day09",
+            feature = "day11"
+        ))]
+        Command::Viz(args) => viz(&args),
+        #[cfg(feature = "tui")]
+        Command::Tui => advent_of_code_2021::tui::run().map_err(Into::into),
+        #[cfg(feature = "image")]
+        Command::Export(args) => export(&args),
+    };
+    if let Err(error) = result {
+        eprintln!("Error: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
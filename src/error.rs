@@ -0,0 +1,20 @@
+use std::error::Error as StdError;
+
+use thiserror::Error;
+
+/// Why a day's `solve` could not produce its answers.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid input: {0}")]
+    InvalidInput(Box<dyn StdError + Send + Sync>),
+    #[error("The input has no solution")]
+    NoSolution,
+    #[error("There is no part {0}")]
+    UnknownPart(u8),
+}
+
+impl Error {
+    pub fn invalid_input(error: impl StdError + Send + Sync + 'static) -> Self {
+        Self::InvalidInput(Box::new(error))
+    }
+}
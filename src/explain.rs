@@ -0,0 +1,80 @@
+//! What a solution works out on the way to its answers, such as the rates of day 3 or the paper
+//! of day 13 after each fold, for `aoc run --explain`.
+
+use std::fmt::{self, Display};
+
+/// One thing worked out along the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub title: String,
+    /// May span several lines, as a picture does.
+    pub detail: String,
+}
+
+/// The steps a solution explains, in the order they were worked out.
+///
+/// Solvers only fill one in from `Solver::explain`, which is not called when solving, so
+/// explaining costs nothing unless asked for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explain {
+    steps: Vec<Step>,
+}
+
+impl Explain {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Adds a step, titled such as `gamma rate`.
+    pub fn step(&mut self, title: impl Into<String>, detail: impl Display) {
+        self.steps.push(Step {
+            title: title.into(),
+            detail: detail.to_string(),
+        });
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// One step per line as `title: detail`, with a detail of several lines starting on a line of
+/// its own.
+impl Display for Explain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            let detail = step.detail.trim_end_matches('\n');
+            if detail.contains('\n') {
+                writeln!(f, "{}:\n{detail}", step.title)?;
+            } else {
+                writeln!(f, "{}: {detail}", step.title)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut explain = Explain::new();
+        assert!(explain.is_empty());
+        explain.step("gamma rate", "10110 = 22");
+        explain.step("board 3", "14* 21*\n 9   2*\n");
+        assert_eq!(explain.steps()[0].detail, "10110 = 22");
+        assert_eq!(
+            explain.to_string(),
+            "gamma rate: 10110 = 22\nboard 3:\n14* 21*\n 9   2*\n"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+pub const YEAR: u16 = 2021;
+pub const SESSION_VAR: &str = "AOC_SESSION";
+const USER_AGENT: &str = "github.com/MizardX/AdventOfCode_2021";
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("The {SESSION_VAR} environment variable is not set")]
+    MissingSession,
+    #[error("Request failed: {0}")]
+    Http(#[from] ureq::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("The answer is a picture whose letters can not be read")]
+    UnreadableAnswer,
+}
+
+/// Where a fetched input ended up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already cached, and was not downloaded again.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// The cached input for `day` of `year`, relative to `root`. This is the same path cargo-aoc uses.
+#[must_use]
+pub fn input_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(format!("input/{year}/day{day}.txt"))
+}
+
+/// The session cookie, from the `AOC_SESSION` environment variable.
+///
+/// # Errors
+///
+/// Returns an error if the variable is not set.
+pub fn session() -> Result<String, FetchError> {
+    env::var(SESSION_VAR)
+        .ok()
+        .filter(|session| !session.trim().is_empty())
+        .ok_or(FetchError::MissingSession)
+}
+
+fn get(url: &str, session: &str) -> Result<String, FetchError> {
+    Ok(ureq::get(url)
+        .header("Cookie", format!("session={}", session.trim()))
+        .header("User-Agent", USER_AGENT)
+        .call()?
+        .body_mut()
+        .read_to_string()?)
+}
+
+pub(crate) fn post(url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, FetchError> {
+    Ok(ureq::post(url)
+        .header("Cookie", format!("session={}", session.trim()))
+        .header("User-Agent", USER_AGENT)
+        .send_form(form.iter().copied())?
+        .body_mut()
+        .read_to_string()?)
+}
+
+/// Downloads the input for `day` of `year` into the cache under `root`, unless it is already
+/// there.
+///
+/// # Errors
+///
+/// Returns an error if the download fails, or the input can not be written.
+pub fn fetch_input(root: &Path, year: u16, day: u8, session: &str) -> Result<Fetched, FetchError> {
+    let path = input_path(root, year, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = get(
+        &format!("https://adventofcode.com/{year}/day/{day}/input"),
+        session,
+    )?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, input)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_input_is_not_downloaded() {
+        let root = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let path = input_path(&root, YEAR, 3);
+        assert!(path.ends_with("input/2021/day3.txt"));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "00100\n").unwrap();
+        let result = fetch_input(&root, YEAR, 3, "not a session");
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(result.unwrap(), Fetched::Cached(path));
+    }
+}
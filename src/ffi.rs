@@ -0,0 +1,130 @@
+//! C bindings for embedding the solvers in other languages. The header is generated with
+//! `cbindgen --config cbindgen.toml --output include/aoc2021.h src/ffi.rs`.
+
+use std::ffi::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+use crate::solver;
+
+/// The outcome of a call to [`aoc2021_solve`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aoc2021Status {
+    /// The answer was written to the output buffer.
+    Ok = 0,
+    /// A pointer was null, or the input was not UTF-8. Nothing was written.
+    InvalidArgument = 1,
+    /// The day is not compiled in. A message was written to the output buffer.
+    UnknownDay = 2,
+    /// There is no such part, the input is malformed, or it has no solution. A message was
+    /// written to the output buffer.
+    SolveFailed = 3,
+    /// The output buffer is too small. Nothing was written, but the needed length was.
+    BufferTooSmall = 4,
+}
+
+/// Solves one part of a day. Answers are text, since some parts (day 13) answer with letters.
+///
+/// The answer, or an error message, is written to `out` as UTF-8 followed by a NUL byte, and its
+/// length without the NUL is written to `written`. If `out_len` is too small, nothing is written
+/// to `out` and [`Aoc2021Status::BufferTooSmall`] is returned; call again with a buffer of at
+/// least `*written + 1` bytes. `out` may be null when `out_len` is 0, to ask for the length.
+/// Trailing line breaks in the input are ignored.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, `out` to `out_len` writable bytes, and
+/// `written` to a writable `size_t`. The buffers must not overlap.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc2021_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out: *mut c_char,
+    out_len: usize,
+    written: *mut usize,
+) -> Aoc2021Status {
+    if input.is_null() || written.is_null() || (out.is_null() && out_len != 0) {
+        return Aoc2021Status::InvalidArgument;
+    }
+    // SAFETY: Checked for null above; the caller promises the length.
+    let input = unsafe { slice::from_raw_parts(input, input_len) };
+    let Ok(input) = str::from_utf8(input) else {
+        return Aoc2021Status::InvalidArgument;
+    };
+    let (status, text) = solve(day, part, input);
+    // SAFETY: Checked for null above.
+    unsafe { written.write(text.len()) };
+    if text.len() >= out_len {
+        return Aoc2021Status::BufferTooSmall;
+    }
+    // SAFETY: `out` has room for the text and the NUL, and does not overlap it.
+    unsafe {
+        ptr::copy_nonoverlapping(text.as_ptr(), out.cast::<u8>(), text.len());
+        out.add(text.len()).write(0);
+    }
+    status
+}
+
+fn solve(day: u8, part: u8, input: &str) -> (Aoc2021Status, String) {
+    let Some(solver) = solver::solver(day) else {
+        return (
+            Aoc2021Status::UnknownDay,
+            format!("Day {day} is not available"),
+        );
+    };
+    let input = input.trim_end_matches(['\r', '\n']);
+    // Unwinding into a C caller would abort the whole host.
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.solve_part(input, part))) {
+        Ok(Ok(answer)) => (Aoc2021Status::Ok, answer.to_string()),
+        Ok(Err(error)) => (Aoc2021Status::SolveFailed, error.to_string()),
+        Err(_) => (
+            Aoc2021Status::SolveFailed,
+            "The solver panicked".to_string(),
+        ),
+    }
+}
+
+#[cfg(all(test, feature = "day13"))]
+mod tests {
+    use super::*;
+
+    fn call(day: u8, part: u8, input: &str, out: &mut [u8]) -> (Aoc2021Status, usize) {
+        let mut written = 0;
+        // SAFETY: All pointers come from live slices and references.
+        let status = unsafe {
+            aoc2021_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().cast(),
+                out.len(),
+                &raw mut written,
+            )
+        };
+        (status, written)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "0,0\n1,0\n2,0\n3,0\n0,1\n0,2\n1,2\n2,2\n0,3\n0,4\n0,5\n\n";
+        let mut out = [0xff; 32];
+        assert_eq!(call(13, 2, input, &mut out), (Aoc2021Status::Ok, 1));
+        assert_eq!(&out[..2], b"F\0");
+        assert_eq!(
+            call(13, 3, input, &mut out),
+            (Aoc2021Status::SolveFailed, 18)
+        );
+        assert_eq!(&out[..19], b"There is no part 3\0");
+        assert_eq!(call(26, 1, input, &mut out).0, Aoc2021Status::UnknownDay);
+        let mut small = [0xff; 1];
+        assert_eq!(
+            call(13, 2, input, &mut small),
+            (Aoc2021Status::BufferTooSmall, 1)
+        );
+        assert_eq!(small, [0xff]);
+    }
+}
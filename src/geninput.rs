@@ -0,0 +1,765 @@
+//! Random puzzle inputs of any size, for stress tests and benchmarks.
+//!
+//! At scale 1 an input is about as large as a real one, and larger scales grow it roughly
+//! linearly. Grids grow in area rather than side. Some days can not grow: day 11 only simulates
+//! ten by ten grids, the ALU of day 24 checks fourteen digit numbers, and days 17, 21 and 23
+//! have no size at all. Day 12 caps the number of caves, since its answers grow exponentially.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use crate::util::ocr;
+use crate::util::symmetry::CubeRotation;
+
+/// A small, seedable random number generator (`SplitMix64`). The same seed always produces the
+/// same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        // The remainder is below `len`, so it fits back in a usize.
+        usize::try_from(self.next_u64() % len as u64).unwrap_or_default()
+    }
+
+    pub const fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = range.end().abs_diff(*range.start()) + 1;
+        range.start().wrapping_add_unsigned(self.next_u64() % span)
+    }
+
+    /// A digit in `range`, as an ASCII character.
+    pub fn digit(&mut self, range: RangeInclusive<u8>) -> char {
+        let span = usize::from(range.end() - range.start()) + 1;
+        char::from(b'0' + range.start() + u8::try_from(self.index(span)).unwrap_or_default())
+    }
+
+    /// `true` with probability `numerator / denominator`.
+    pub const fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// Generates an input for `day`, or `None` if there is no such day.
+#[must_use]
+pub fn generate(day: u8, scale: usize, seed: u64) -> Option<String> {
+    let scale = scale.max(1);
+    let rng = &mut Rng::new(seed ^ u64::from(day) << 56);
+    let mut out = String::new();
+    let generator = match day {
+        1 => day_01,
+        2 => day_02,
+        3 => day_03,
+        4 => day_04,
+        5 => day_05,
+        6 => day_06,
+        7 => day_07,
+        8 => day_08,
+        9 => day_09,
+        10 => day_10,
+        11 => day_11,
+        12 => day_12,
+        13 => day_13,
+        14 => day_14,
+        15 => day_15,
+        16 => day_16,
+        17 => day_17,
+        18 => day_18,
+        19 => day_19,
+        20 => day_20,
+        21 => day_21,
+        22 => day_22,
+        23 => day_23,
+        24 => day_24,
+        25 => day_25,
+        _ => return None,
+    };
+    // Writing to a `String` can not fail.
+    let _ = generator(rng, scale, &mut out);
+    Some(out)
+}
+
+/// A few small inputs for `day`, with the seed of each, for comparing alternate solutions.
+#[cfg(test)]
+#[cfg_attr(not(feature = "full"), allow(dead_code))]
+pub(crate) fn samples(day: u8) -> impl Iterator<Item = (u64, String)> {
+    (0..8).filter_map(move |seed| {
+        let input = generate(day, 1, seed)?;
+        Some((seed, input.trim_end().to_string()))
+    })
+}
+
+type Out<'a> = &'a mut String;
+
+/// The side of a square grid with `scale` times the area of one with side `base`.
+const fn side(base: usize, scale: usize) -> usize {
+    (base * base * scale).isqrt()
+}
+
+fn grid(
+    rng: &mut Rng,
+    width: usize,
+    height: usize,
+    out: Out,
+    mut cell: impl FnMut(&mut Rng) -> char,
+) -> std::fmt::Result {
+    for _ in 0..height {
+        let row = (0..width).map(|_| cell(rng)).collect::<String>();
+        writeln!(out, "{row}")?;
+    }
+    Ok(())
+}
+
+fn day_01(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut depth = rng.range(100..=200);
+    for _ in 0..2000 * scale {
+        depth = (depth + rng.range(-10..=30)).max(0);
+        writeln!(out, "{depth}")?;
+    }
+    Ok(())
+}
+
+fn day_02(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    for _ in 0..1000 * scale {
+        let command = rng.pick(&["forward", "forward", "down", "up"]);
+        writeln!(out, "{command} {}", rng.range(1..=9))?;
+    }
+    Ok(())
+}
+
+/// Whether filtering `numbers` by the least common bits leaves a CO2 scrubber rating. It does
+/// not if some bit is the same in every number that is left.
+fn has_co2_rating(numbers: &[u32]) -> bool {
+    let mut numbers = numbers.to_vec();
+    for bit in (0..12).rev() {
+        if numbers.len() <= 1 {
+            break;
+        }
+        let ones = numbers.iter().filter(|&&n| n >> bit & 1 == 1).count();
+        if ones == 0 || ones == numbers.len() {
+            return false;
+        }
+        let keep = u32::from(2 * ones < numbers.len());
+        numbers.retain(|&n| n >> bit & 1 == keep);
+    }
+    true
+}
+
+/// The numbers are distinct, so there are at most 4096 of them.
+fn day_03(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut numbers = (0..1 << 12).collect::<Vec<u32>>();
+    let count = numbers.len().min(1000 * scale);
+    loop {
+        rng.shuffle(&mut numbers);
+        if has_co2_rating(&numbers[..count]) {
+            break;
+        }
+    }
+    for number in &numbers[..count] {
+        writeln!(out, "{number:012b}")?;
+    }
+    Ok(())
+}
+
+fn day_04(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut numbers = (0..100).collect::<Vec<u8>>();
+    rng.shuffle(&mut numbers);
+    let drawn = numbers.iter().map(u8::to_string).collect::<Vec<_>>();
+    writeln!(out, "{}", drawn.join(","))?;
+    for _ in 0..100 * scale {
+        rng.shuffle(&mut numbers);
+        writeln!(out)?;
+        for row in numbers[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{n:2}")).collect::<Vec<_>>();
+            writeln!(out, "{}", row.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+fn day_05(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    for _ in 0..500 * scale {
+        let (x1, y1) = (rng.range(10..=989), rng.range(10..=989));
+        let length = rng.range(-300..=300);
+        let (x2, y2) = match rng.index(3) {
+            0 => (x1 + length, y1),
+            1 => (x1, y1 + length),
+            _ => (x1 + length, y1 + length * rng.pick(&[-1, 1])),
+        };
+        let (x2, y2) = (x2.clamp(0, 999), y2.clamp(0, 999));
+        // Clamping can bend a diagonal, so shorten both axes to the same length.
+        let length = (x2 - x1).abs().min((y2 - y1).abs());
+        let (x2, y2) = if x1 != x2 && y1 != y2 {
+            (
+                x1 + length * (x2 - x1).signum(),
+                y1 + length * (y2 - y1).signum(),
+            )
+        } else {
+            (x2, y2)
+        };
+        writeln!(out, "{x1},{y1} -> {x2},{y2}")?;
+    }
+    Ok(())
+}
+
+fn comma_separated(out: Out, count: usize, mut item: impl FnMut() -> i64) -> std::fmt::Result {
+    for i in 0..count {
+        if i > 0 {
+            out.push(',');
+        }
+        write!(out, "{}", item())?;
+    }
+    writeln!(out)
+}
+
+fn day_06(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    comma_separated(out, 300 * scale, || rng.range(1..=5))
+}
+
+fn day_07(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    comma_separated(out, 1000 * scale, || {
+        let position = rng.range(0..=1999);
+        position.min(rng.range(0..=1999))
+    })
+}
+
+fn day_08(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    for _ in 0..200 * scale {
+        let mut wiring = *b"abcdefg";
+        rng.shuffle(&mut wiring);
+        let wire = |rng: &mut Rng, digit: &str| {
+            let mut wires = digit
+                .bytes()
+                .map(|segment| char::from(wiring[usize::from(segment - b'a')]))
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut wires);
+            wires.into_iter().collect::<String>()
+        };
+        let mut patterns = DIGITS.map(|digit| wire(rng, digit));
+        let output = [(); 4].map(|()| {
+            let digit = DIGITS[rng.index(10)];
+            wire(rng, digit)
+        });
+        rng.shuffle(&mut patterns);
+        writeln!(out, "{} | {}", patterns.join(" "), output.join(" "))?;
+    }
+    Ok(())
+}
+
+fn day_09(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let side = side(100, scale);
+    grid(rng, side, side, out, |rng| rng.digit(0..=9))
+}
+
+/// Every line is either corrupted or incomplete, like the real input. Lines never nest deeper
+/// than fifteen chunks, so the completion scores fit in a `u64`.
+fn day_10(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    for _ in 0..100 * scale {
+        let mut stack = Vec::new();
+        let corrupt = rng.chance(1, 2);
+        for _ in 0..rng.range(80..=110) {
+            if stack.is_empty() || (stack.len() < 15 && rng.chance(3, 5)) {
+                let (open, close) = *rng.pick(&PAIRS);
+                out.push(open);
+                stack.push(close);
+            } else if let Some(close) = stack.pop() {
+                out.push(close);
+            }
+        }
+        if corrupt {
+            let expected = stack.last().copied().unwrap_or(')');
+            let wrong = PAIRS
+                .iter()
+                .map(|&(_, close)| close)
+                .find(|&c| c != expected);
+            out.extend(wrong);
+        } else if stack.is_empty() {
+            out.push('(');
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Whether the octopuses all flash at once within a thousand steps.
+fn synchronizes(mut energy: [u8; 100]) -> bool {
+    for _ in 0..1000 {
+        let mut flashing = (0..100).collect::<Vec<usize>>();
+        for e in &mut energy {
+            *e += 1;
+        }
+        flashing.retain(|&i| energy[i] > 9);
+        while let Some(i) = flashing.pop() {
+            for dy in -1..=1_isize {
+                for dx in -1..=1_isize {
+                    let (Some(x), Some(y)) = (
+                        (i % 10).checked_add_signed(dx),
+                        (i / 10).checked_add_signed(dy),
+                    ) else {
+                        continue;
+                    };
+                    if x < 10 && y < 10 && energy[y * 10 + x] <= 9 {
+                        energy[y * 10 + x] += 1;
+                        if energy[y * 10 + x] > 9 {
+                            flashing.push(y * 10 + x);
+                        }
+                    }
+                }
+            }
+        }
+        energy.iter_mut().filter(|e| **e > 9).for_each(|e| *e = 0);
+        if energy.iter().all(|&e| e == 0) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Random grids do not always synchronize, so new ones are drawn until one does.
+fn day_11(rng: &mut Rng, _scale: usize, out: Out) -> std::fmt::Result {
+    let energy = loop {
+        let energy = [(); 100].map(|()| u8::try_from(rng.index(10)).unwrap_or_default());
+        if synchronizes(energy) {
+            break energy;
+        }
+    };
+    for row in energy.chunks(10) {
+        let row = row
+            .iter()
+            .map(|&e| char::from(b'0' + e))
+            .collect::<String>();
+        writeln!(out, "{row}")?;
+    }
+    Ok(())
+}
+
+/// Small caves come in chains, joined by large caves that never touch each other.
+fn day_12(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let small_count = (5 + scale).min(40);
+    let large_count = (2 + scale / 2).min(20);
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng, large: bool| loop {
+        let base = if large { b'A' } else { b'a' };
+        let name = [0; 2].map(|_| char::from(base + u8::try_from(rng.index(26)).unwrap_or(0)));
+        let name = name.iter().collect::<String>();
+        if names.insert(name.clone()) {
+            break name;
+        }
+    };
+    let mut small = vec!["start".to_string(), "end".to_string()];
+    small.extend((0..small_count).map(|_| name(rng, false)));
+    let large = (0..large_count)
+        .map(|_| name(rng, true))
+        .collect::<Vec<_>>();
+    let mut edges = HashSet::new();
+    // A chain through every small cave keeps the system connected.
+    for pair in small[2..].windows(2) {
+        edges.insert((pair[0].clone(), pair[1].clone()));
+    }
+    edges.insert(("start".to_string(), small[2].clone()));
+    edges.insert((small[small.len() - 1].clone(), "end".to_string()));
+    for cave in &large {
+        for _ in 0..rng.range(2..=4) {
+            edges.insert((cave.clone(), rng.pick(&small).clone()));
+        }
+    }
+    let mut edges = edges.into_iter().collect::<Vec<_>>();
+    edges.sort();
+    rng.shuffle(&mut edges);
+    for (a, b) in edges {
+        writeln!(out, "{a}-{b}")?;
+    }
+    Ok(())
+}
+
+/// Draws random letters and unfolds them twelve times, so part 2 reads back the letters.
+fn day_13(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let letters = ocr::letters().collect::<Vec<_>>();
+    let text = (0..8 * scale)
+        .map(|_| *rng.pick(&letters))
+        .collect::<String>();
+    let mut points = ocr::draw(&text)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|point| (point.x, point.y))
+        .collect::<Vec<_>>();
+    let (mut width, mut height) = (text.len() * 5, 6);
+    let mut folds = Vec::new();
+    for fold in 0..12 {
+        let along_x = fold % 2 == 0;
+        let line = if along_x { width } else { height };
+        let mirror = |coordinate| 2 * line - coordinate;
+        points = points
+            .into_iter()
+            .flat_map(|(x, y)| {
+                let mirrored = if along_x {
+                    (mirror(x), y)
+                } else {
+                    (x, mirror(y))
+                };
+                match rng.index(8) {
+                    0..=2 => vec![(x, y)],
+                    3..=5 => vec![mirrored],
+                    _ => vec![(x, y), mirrored],
+                }
+            })
+            .collect();
+        if along_x {
+            width = 2 * width + 1;
+        } else {
+            height = 2 * height + 1;
+        }
+        folds.push((if along_x { 'x' } else { 'y' }, line));
+    }
+    rng.shuffle(&mut points);
+    for (x, y) in points {
+        writeln!(out, "{x},{y}")?;
+    }
+    writeln!(out)?;
+    for (axis, line) in folds.into_iter().rev() {
+        writeln!(out, "fold along {axis}={line}")?;
+    }
+    Ok(())
+}
+
+/// Ten elements picked from the whole alphabet, like the real inputs that each use their own.
+fn day_14(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut letters = ('A'..='Z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let elements = &letters[..10];
+    out.extend((0..20 * scale).map(|_| *rng.pick(elements)));
+    writeln!(out, "\n")?;
+    for a in elements {
+        for b in elements {
+            writeln!(out, "{a}{b} -> {}", rng.pick(elements))?;
+        }
+    }
+    Ok(())
+}
+
+fn day_15(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let side = side(100, scale);
+    grid(rng, side, side, out, |rng| rng.digit(1..=9))
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u64, count: usize) {
+    bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+/// Writes a packet with at most `budget` packets in total. Products and comparisons only have
+/// literal operands, so the value never overflows.
+fn day_16_packet(rng: &mut Rng, budget: usize, bits: &mut Vec<bool>) {
+    let version = rng.next_u64() % 8;
+    let literal = |rng: &mut Rng, bits: &mut Vec<bool>, max: i64| {
+        push_bits(bits, rng.next_u64() % 8, 3);
+        push_bits(bits, 4, 3);
+        let value = rng.range(0..=max).unsigned_abs();
+        let nibbles = (1..=16)
+            .find(|&n| n == 16 || value >> (4 * n) == 0)
+            .unwrap_or(16);
+        for nibble in (0..nibbles).rev() {
+            push_bits(bits, u64::from(nibble > 0), 1);
+            push_bits(bits, value >> (4 * nibble) & 0xF, 4);
+        }
+    };
+    if budget <= 1 {
+        literal(rng, bits, 100_000);
+        return;
+    }
+    let type_id = if budget > 4 {
+        *rng.pick(&[0, 0, 2, 3])
+    } else {
+        *rng.pick(&[1, 5, 6, 7])
+    };
+    let mut children = Vec::new();
+    let count = match type_id {
+        1 => {
+            let count = rng.range(1..=3).unsigned_abs();
+            for _ in 0..count {
+                literal(rng, &mut children, 99);
+            }
+            count
+        }
+        5..=7 => {
+            for _ in 0..2 {
+                literal(rng, &mut children, 9);
+            }
+            2
+        }
+        _ => {
+            let count = rng.index(4) + 2;
+            for _ in 0..count {
+                day_16_packet(rng, (budget - 1) / count, &mut children);
+            }
+            count as u64
+        }
+    };
+    push_bits(bits, version, 3);
+    push_bits(bits, type_id, 3);
+    if children.len() < 1 << 15 && rng.chance(1, 2) {
+        push_bits(bits, 0, 1);
+        push_bits(bits, children.len() as u64, 15);
+    } else {
+        push_bits(bits, 1, 1);
+        push_bits(bits, count, 11);
+    }
+    bits.append(&mut children);
+}
+
+fn day_16(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut bits = Vec::new();
+    day_16_packet(rng, 250 * scale, &mut bits);
+    for nibble in bits.chunks(4) {
+        let value = nibble
+            .iter()
+            .chain([false; 4].iter())
+            .take(4)
+            .fold(0, |acc, &bit| acc << 1 | u32::from(bit));
+        out.extend(char::from_digit(value, 16).map(|c| c.to_ascii_uppercase()));
+    }
+    writeln!(out)
+}
+
+fn day_17(rng: &mut Rng, _scale: usize, out: Out) -> std::fmt::Result {
+    let x_min = rng.range(20..=250);
+    let y_max = rng.range(-150..=-5);
+    writeln!(
+        out,
+        "target area: x={x_min}..{}, y={}..{y_max}",
+        x_min + rng.range(5..=60),
+        y_max - rng.range(5..=50)
+    )
+}
+
+fn day_18_number(rng: &mut Rng, depth: usize, out: Out) -> std::fmt::Result {
+    if depth == 4 || (depth > 0 && rng.chance(1, 3)) {
+        return write!(out, "{}", rng.range(0..=9));
+    }
+    out.push('[');
+    day_18_number(rng, depth + 1, out)?;
+    out.push(',');
+    day_18_number(rng, depth + 1, out)?;
+    out.push(']');
+    Ok(())
+}
+
+fn day_18(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    for _ in 0..100 * scale {
+        day_18_number(rng, 0, out)?;
+        out.push('\n');
+    }
+    Ok(())
+}
+
+/// Places scanners in a random tree, where each one overlaps its parent by at least twelve
+/// beacons, and reports every beacon within range of each scanner in a random orientation.
+fn day_19(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    const RANGE: i64 = 1000;
+    let in_range = |scanner: [i64; 3], beacon: [i64; 3]| {
+        (0..3).all(|axis| (beacon[axis] - scanner[axis]).abs() <= RANGE)
+    };
+    let mut scanners = vec![[0; 3]];
+    let mut beacons = HashSet::new();
+    let distance = |a: [i64; 3], b: [i64; 3]| (0..3).map(|axis| (a[axis] - b[axis]).abs()).max();
+    while scanners.len() < 30 * scale {
+        let parent = *rng.pick(&scanners);
+        let scanner = parent.map(|c| c + rng.range(-1300..=1300));
+        // Keep the scanners apart, so each one sees about as many beacons as in a real input.
+        if scanners
+            .iter()
+            .any(|&other| distance(other, scanner) < Some(RANGE))
+        {
+            continue;
+        }
+        for _ in 0..12 {
+            let beacon = [0, 1, 2].map(|axis| {
+                let low = parent[axis].max(scanner[axis]) - RANGE;
+                let high = parent[axis].min(scanner[axis]) + RANGE;
+                rng.range(low..=high)
+            });
+            beacons.insert(beacon);
+        }
+        scanners.push(scanner);
+    }
+    for &scanner in &scanners {
+        for _ in 0..10 {
+            beacons.insert(scanner.map(|c| c + rng.range(-RANGE..=RANGE)));
+        }
+    }
+    let mut beacons = beacons.into_iter().collect::<Vec<_>>();
+    beacons.sort_unstable();
+    let rotations = CubeRotation::all();
+    for (index, &scanner) in scanners.iter().enumerate() {
+        if index > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "--- scanner {index} ---")?;
+        let rotation = *rng.pick(&rotations);
+        let mut seen = beacons
+            .iter()
+            .filter(|&&beacon| in_range(scanner, beacon))
+            .map(|&beacon| rotation.apply([0, 1, 2].map(|axis| beacon[axis] - scanner[axis])))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut seen);
+        for [x, y, z] in seen {
+            writeln!(out, "{x},{y},{z}")?;
+        }
+    }
+    Ok(())
+}
+
+/// An algorithm that lights every dark pixel also darkens every lit one, so the infinite
+/// background blinks instead of staying lit.
+fn day_20(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let mut algorithm = (0..512)
+        .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+        .collect::<Vec<_>>();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+    out.extend(algorithm);
+    writeln!(out, "\n")?;
+    let side = side(100, scale);
+    grid(rng, side, side, out, |rng| {
+        if rng.chance(1, 2) { '#' } else { '.' }
+    })
+}
+
+fn day_21(rng: &mut Rng, _scale: usize, out: Out) -> std::fmt::Result {
+    writeln!(out, "Player 1 starting position: {}", rng.range(1..=10))?;
+    writeln!(out, "Player 2 starting position: {}", rng.range(1..=10))
+}
+
+/// Twenty small steps within the initialization region, then larger ones all around.
+fn day_22(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    for step in 0..420 * scale {
+        let (reach, size) = if step < 20 {
+            (50, 30)
+        } else {
+            (100_000, 40_000)
+        };
+        let on = step < 10 || rng.chance(1, 2);
+        write!(out, "{}", if on { "on " } else { "off " })?;
+        for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+            let min = rng.range(-reach..=reach - size);
+            let max = min + rng.range(1..=size);
+            let separator = if axis == 0 { "" } else { "," };
+            write!(out, "{separator}{name}={min}..{max}")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn day_23(rng: &mut Rng, _scale: usize, out: Out) -> std::fmt::Result {
+    let mut amphipods = *b"AABBCCDD";
+    rng.shuffle(&mut amphipods);
+    let row = |row: &[u8]| {
+        row.iter()
+            .map(|&a| char::from(a).to_string())
+            .collect::<Vec<_>>()
+            .join("#")
+    };
+    writeln!(out, "#############")?;
+    writeln!(out, "#...........#")?;
+    writeln!(out, "###{}###", row(&amphipods[..4]))?;
+    writeln!(out, "  #{}#", row(&amphipods[4..]))?;
+    writeln!(out, "  #########")
+}
+
+/// MONAD: seven blocks push a digit onto a base-26 stack in `z`, and seven pop one and compare
+/// it with the current digit. Every pair of blocks has digits that satisfy it.
+fn day_24(rng: &mut Rng, _scale: usize, out: Out) -> std::fmt::Result {
+    let mut stack = Vec::new();
+    let (mut open, mut closed) = (0, 0);
+    for _ in 0..14 {
+        let push = closed + stack.len() < 7 && (stack.is_empty() || rng.chance(1, 2));
+        let (div, check, offset) = if push {
+            open += 1;
+            let offset = rng.range(0..=16);
+            stack.push(offset);
+            (1, rng.range(10..=16), offset)
+        } else {
+            closed += 1;
+            let pushed = stack.pop().unwrap_or_default();
+            (26, rng.range(-8..=8) - pushed, rng.range(0..=16))
+        };
+        writeln!(
+            out,
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {div}\nadd x {check}"
+        )?;
+        writeln!(
+            out,
+            "eql x w\neql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y"
+        )?;
+        writeln!(out, "mul y 0\nadd y w\nadd y {offset}\nmul y x\nadd z y")?;
+    }
+    debug_assert_eq!((open, closed), (7, 7));
+    Ok(())
+}
+
+fn day_25(rng: &mut Rng, scale: usize, out: Out) -> std::fmt::Result {
+    let side = side(138, scale);
+    grid(rng, side + 2, side, out, |rng| *rng.pick(&['>', 'v', '.']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let first = (0..5).map(|_| rng.range(-3..=3)).collect::<Vec<_>>();
+        assert!(first.iter().all(|n| (-3..=3).contains(n)));
+        assert_eq!(
+            first,
+            (0..5)
+                .map({
+                    let mut rng = Rng::new(7);
+                    move |_| rng.range(-3..=3)
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(generate(26, 1, 0), None);
+        assert_eq!(generate(1, 1, 5), generate(1, 1, 5));
+    }
+
+    #[test]
+    #[cfg(feature = "full")]
+    fn test_generated_inputs_solve() {
+        for (day, solver) in crate::solver::solvers() {
+            let input = generate(day, 1, 2021).unwrap();
+            let result = solver.solve(input.trim_end());
+            assert!(result.is_ok(), "Day {day}: {result:?}");
+        }
+    }
+}
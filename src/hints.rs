@@ -0,0 +1,141 @@
+/// Progressively more specific hints per day, from a gentle nudge to an outline of the approach.
+const HINTS: &[(u8, &[&str])] = &[
+    (
+        1,
+        &[
+            "Pairs of neighbouring values are all you need for the first part.",
+            "Two overlapping windows share all but one element.",
+            "Comparing the sums of windows starting at i and i + 1 is the same as comparing values i and i + 3.",
+        ],
+    ),
+    (
+        2,
+        &[
+            "Keep a small amount of state and update it for each command.",
+            "In the second part, up and down no longer move you; they change how forward moves you.",
+        ],
+    ),
+    (
+        3,
+        &[
+            "Look at one bit position at a time, across all numbers.",
+            "For the second part, filter the list repeatedly instead of counting once.",
+            "Sorting the numbers turns each filtering step into a binary search for where the bit flips.",
+        ],
+    ),
+    (
+        4,
+        &[
+            "Mark numbers on every board as they are drawn and check only the board that changed.",
+            "A board wins when a whole row or column is marked; diagonals do not count.",
+            "Knowing at which draw each number is called lets you compute when each board wins without simulating.",
+        ],
+    ),
+    (
+        5,
+        &[
+            "The coordinates are small enough to count hits on a grid directly.",
+            "Step along each line one unit at a time, using the sign of the difference in each axis.",
+        ],
+    ),
+    (
+        6,
+        &[
+            "Individual fish with the same timer behave identically.",
+            "Track how many fish have each timer value, not each fish.",
+            "Nine counters shifted once per day are enough; a ring buffer avoids moving them.",
+        ],
+    ),
+    (
+        7,
+        &[
+            "Think about which target minimises the sum of absolute distances.",
+            "For linear cost the median is optimal.",
+            "For triangular cost the optimum is within one of the mean.",
+        ],
+    ),
+    (
+        8,
+        &[
+            "Some digits can be identified by their segment count alone.",
+            "Set operations between the unknown patterns and the known ones separate the rest.",
+            "The number of segments shared with the patterns for 1 and 4 distinguishes every remaining digit.",
+        ],
+    ),
+    (
+        9,
+        &[
+            "A low point is lower than all four orthogonal neighbours.",
+            "Basins are bounded by height 9; flood fill from each low point.",
+        ],
+    ),
+    (
+        10,
+        &[
+            "A stack of expected closing characters solves both parts.",
+            "Whatever remains on the stack at the end of a line is exactly what is missing.",
+        ],
+    ),
+    (
+        11,
+        &[
+            "Handle flashes with a queue so each octopus flashes at most once per step.",
+            "Run the simulation until every octopus flashes in the same step.",
+        ],
+    ),
+    (
+        12,
+        &[
+            "Depth-first search over paths, remembering which small caves are on the current path.",
+            "For the second part, carry a flag telling whether the single revisit is already used.",
+            "Small caves fit in a bitmask, which makes the visited set cheap to copy.",
+        ],
+    ),
+    (
+        13,
+        &[
+            "A fold maps each coordinate beyond the line to its mirror image.",
+            "Store the dots in a set so overlapping dots merge.",
+            "Print the remaining dots as a grid to read the letters.",
+        ],
+    ),
+    (
+        14,
+        &[
+            "The string grows too fast to build for the second part.",
+            "Count pairs of adjacent elements instead of building the string.",
+            "Each rule turns one pair into two pairs; count elements from the first letter of each pair, plus the last element.",
+        ],
+    ),
+];
+
+/// The hint for `day` at `level`, counting from 1. Returns `None` past the last hint.
+#[must_use]
+pub fn hint(day: u8, level: usize) -> Option<&'static str> {
+    let (_, hints) = HINTS.iter().find(|&&(d, _)| d == day)?;
+    hints.get(level.checked_sub(1)?).copied()
+}
+
+/// The number of hints available for `day`.
+#[must_use]
+pub fn levels(day: u8) -> usize {
+    HINTS
+        .iter()
+        .find(|&&(d, _)| d == day)
+        .map_or(0, |(_, hints)| hints.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint() {
+        assert_eq!(levels(6), 3);
+        assert!(hint(6, 1).is_some());
+        assert!(hint(6, 3).is_some());
+        assert_eq!(hint(6, 0), None);
+        assert_eq!(hint(6, 4), None);
+        assert_eq!(hint(26, 1), None);
+    }
+}
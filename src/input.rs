@@ -0,0 +1,443 @@
+//! Loads puzzle inputs for the runner: read whole into a `String`, or streamed to the days that
+//! can solve while reading, so that large generated inputs never have to fit in memory.
+//!
+//! Inputs compressed with gzip or zstd are decompressed while reading, with the `compressed`
+//! feature.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::fetcher;
+use crate::instrument::{Measurement, measure};
+use crate::solver::{TimedPart, TimedRun};
+use crate::{Answer, Error};
+
+/// How much of the input is read at a time when streaming.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// The input, read in chunks of a megabyte, and decompressed if it is compressed. A file whose
+    /// first bytes do not tell is decompressed if it is named `.gz`, `.zst` or `.zstd`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be opened, or is compressed in a way that can not be
+    /// read.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Self::File(path) => {
+                let mut reader = BufReader::with_capacity(CHUNK_SIZE, File::open(path)?);
+                let compression = match Compression::detect(reader.fill_buf()?) {
+                    Compression::None => Compression::from_extension(path),
+                    compression => compression,
+                };
+                decompress_as(reader, compression)
+            }
+            Self::Stdin => decompress(BufReader::with_capacity(CHUNK_SIZE, io::stdin())),
+        }
+    }
+
+    /// The whole input, decompressed, without the line endings at its end.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input can not be read, or is not UTF-8.
+    pub fn read(&self) -> io::Result<String> {
+        let mut input = String::new();
+        self.reader()?.read_to_string(&mut input)?;
+        Ok(trim_line_endings(input))
+    }
+}
+
+/// How an input is compressed, told by its first bytes, or by the name of its file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// The compression that `start`, the first bytes of an input, announces.
+    #[must_use]
+    pub fn detect(start: &[u8]) -> Self {
+        if start.starts_with(&[0x1f, 0x8b]) {
+            Self::Gzip
+        } else if start.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) || is_skippable_frame(start) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// The compression that the extension of `path` announces, for files whose first bytes do
+    /// not, so that a damaged archive is reported rather than read as text.
+    #[must_use]
+    pub fn from_extension(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") => Self::Gzip,
+            Some("zst" | "zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+}
+
+/// `reader`, decompressed on the fly if it starts like a gzip or zstd file, and as it is
+/// otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the start can not be read, or is compressed and the `compressed` feature
+/// is disabled.
+pub fn decompress<'a>(mut reader: impl BufRead + 'a) -> io::Result<Box<dyn BufRead + 'a>> {
+    let compression = Compression::detect(reader.fill_buf()?);
+    decompress_as(reader, compression)
+}
+
+/// `reader`, decompressed on the fly as `compression` says.
+///
+/// # Errors
+///
+/// Returns an error if `reader` is compressed and the `compressed` feature is disabled, or the
+/// start of a zstd stream can not be read.
+pub fn decompress_as<'a>(
+    reader: impl BufRead + 'a,
+    compression: Compression,
+) -> io::Result<Box<dyn BufRead + 'a>> {
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        #[cfg(feature = "compressed")]
+        Compression::Gzip => Ok(Box::new(BufReader::with_capacity(
+            CHUNK_SIZE,
+            flate2::bufread::MultiGzDecoder::new(reader),
+        ))),
+        #[cfg(feature = "compressed")]
+        Compression::Zstd => {
+            let mut reader = reader;
+            skip_skippable_frames(&mut reader)?;
+            let decoder = ruzstd::decoding::StreamingDecoder::new(reader)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            Ok(Box::new(BufReader::with_capacity(CHUNK_SIZE, decoder)))
+        }
+        #[cfg(not(feature = "compressed"))]
+        Compression::Gzip | Compression::Zstd => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "The input is compressed with {compression:?}; build with the `compressed` feature to read it"
+            ),
+        )),
+    }
+}
+
+/// Whether `start` is the start of a zstd skippable frame, which holds metadata rather than
+/// data, and may come before the compressed frame.
+fn is_skippable_frame(start: &[u8]) -> bool {
+    matches!(start, [0x50..=0x5f, 0x2a, 0x4d, 0x18, ..])
+}
+
+/// Reads past the skippable frames at the start of a zstd stream, which the decoder does not.
+#[cfg(feature = "compressed")]
+fn skip_skippable_frames(reader: &mut impl BufRead) -> io::Result<()> {
+    while is_skippable_frame(reader.fill_buf()?) {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+        let [_, _, _, _, length @ ..] = header;
+        let length = u64::from(u32::from_le_bytes(length));
+        let skipped = io::copy(&mut reader.by_ref().take(length), &mut io::sink())?;
+        if skipped < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+    }
+    Ok(())
+}
+
+/// An input held in memory, such as `include_bytes!` of an archived input, decompressed if it
+/// is compressed and without the line endings at its end.
+///
+/// # Errors
+///
+/// Returns an error if it can not be decompressed, or is not UTF-8.
+pub fn decode(bytes: &[u8]) -> io::Result<String> {
+    let mut input = String::new();
+    decompress(bytes)?.read_to_string(&mut input)?;
+    Ok(trim_line_endings(input))
+}
+
+/// The whole input in `path`, like [`Source::read`].
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read or decompressed, or is not UTF-8.
+pub fn read_file(path: &Path) -> io::Result<String> {
+    Source::File(path.to_path_buf()).read()
+}
+
+/// Where the input of `day` is cached under `root`: `input/<YEAR>/day<N>.txt`, or the same
+/// with `.gz` or `.zst` added if only an archived copy is there.
+#[must_use]
+pub fn cached_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    let path = fetcher::input_path(root, year, day);
+    if path.exists() {
+        return path;
+    }
+    ["gz", "zst"]
+        .into_iter()
+        .map(|extension| path.with_extension(format!("txt.{extension}")))
+        .find(|archived| archived.exists())
+        .unwrap_or(path)
+}
+
+/// Drops the line endings at the end of `input`.
+fn trim_line_endings(mut input: String) -> String {
+    let trimmed = input.trim_end_matches(['\r', '\n']).len();
+    input.truncate(trimmed);
+    input
+}
+
+/// The days that can be solved while reading their input, with the parts that can.
+pub const STREAMING: [(u8, &[u8]); 4] = [(1, &[1, 2]), (2, &[1, 2]), (7, &[1]), (10, &[1, 2])];
+
+/// Solves `parts` of `day` while reading the input from `reader`.
+///
+/// Only what the day needs to remember is held, rather than the whole input. Returns `None` if
+/// the day can not solve one of the parts that way, or its feature is disabled.
+///
+/// Reading and solving are one step, so the whole time is reported as solving, and parsing
+/// takes no time.
+pub fn solve_streaming(
+    day: u8,
+    parts: &[u8],
+    reader: impl BufRead,
+) -> Option<Result<TimedRun, Error>> {
+    let (_, streamable) = STREAMING.iter().find(|&&(streaming, _)| streaming == day)?;
+    if !parts.iter().all(|part| streamable.contains(part)) {
+        return None;
+    }
+    let (answers, solve) = measure(|| stream_answers(day, reader));
+    let answers = match answers? {
+        Ok(answers) => answers,
+        Err(error) => return Some(Err(error)),
+    };
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let answer = answers[usize::from(part) - 1].clone();
+            Ok(TimedPart {
+                part,
+                answer: answer.ok_or(Error::NoSolution)?,
+                solve: None,
+            })
+        })
+        .collect::<Result<_, Error>>();
+    Some(parts.map(|parts| TimedRun {
+        parse: Measurement {
+            elapsed: Duration::ZERO,
+            peak_bytes: solve.peak_bytes.map(|_| 0),
+            allocations: solve.allocations.map(|_| 0),
+        },
+        solve,
+        parts,
+    }))
+}
+
+/// The answers of both parts, or `None` for a part that has no answer or can not be streamed.
+#[cfg_attr(
+    not(all(
+        feature = "day01",
+        feature = "day02",
+        feature = "day07",
+        feature = "day10"
+    )),
+    allow(unused_variables, clippy::needless_pass_by_value)
+)]
+fn stream_answers(day: u8, reader: impl BufRead) -> Option<Result<[Option<Answer>; 2], Error>> {
+    match day {
+        #[cfg(feature = "day01")]
+        1 => Some(
+            crate::year2021::day_01::solve_stream(reader)
+                .map(|(part_1, part_2)| [Some(part_1.into()), Some(part_2.into())])
+                .map_err(Error::invalid_input),
+        ),
+        #[cfg(feature = "day02")]
+        2 => Some(
+            crate::year2021::day_02::solve_stream(reader)
+                .map(|answers| <[Option<i64>; 2]>::from(answers).map(|part| part.map(Into::into)))
+                .map_err(Error::invalid_input),
+        ),
+        #[cfg(feature = "day07")]
+        7 => Some(
+            crate::year2021::day_07::solve_stream_part_1(reader)
+                .map(|part_1| [Some(part_1.into()), None])
+                .map_err(Error::invalid_input),
+        ),
+        #[cfg(feature = "day10")]
+        10 => Some(
+            crate::year2021::day_10::solve_stream(reader)
+                .map(|(part_1, part_2)| [Some(part_1.into()), Some(part_2.into())])
+                .map_err(Error::invalid_input),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn test_solve_streaming() {
+        let run = solve_streaming(1, &[1, 2], &b"1\n3\n2\n4\n5\n6\n"[..])
+            .unwrap()
+            .unwrap();
+        let answers = run
+            .parts
+            .iter()
+            .map(|part| (part.part, part.answer.as_int()));
+        assert_eq!(answers.collect::<Vec<_>>(), [(1, Some(4)), (2, Some(3))]);
+        assert!(solve_streaming(7, &[2], &b"16,1,2"[..]).is_none());
+        assert!(solve_streaming(13, &[1], &b""[..]).is_none());
+        assert!(
+            solve_streaming(2, &[1], &b"sideways 3"[..])
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_streaming_agrees() {
+        for &(day, parts) in &STREAMING {
+            let input = crate::geninput::generate(day, 1, 0).unwrap();
+            let run = solve_streaming(day, parts, input.as_bytes())
+                .unwrap()
+                .unwrap();
+            for part in run.parts {
+                let expected = solver::solver(day)
+                    .unwrap()
+                    .solve_part(input.trim_end(), part.part)
+                    .unwrap();
+                assert_eq!(part.answer, expected, "day {day} part {}", part.part);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let path = std::env::temp_dir().join(format!("aoc2021-input-{}.txt", std::process::id()));
+        std::fs::write(&path, "1\n2\r\n\n").unwrap();
+        let source = Source::File(path.clone());
+        assert_eq!(source.read().unwrap(), "1\n2");
+        let mut lines = source.reader().unwrap().lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "1");
+        std::fs::remove_file(path).unwrap();
+        assert!(
+            Source::File(PathBuf::from("no/such/input.txt"))
+                .read()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Compression::detect(b"1\n2"), Compression::None);
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 8]), Compression::Gzip);
+        assert_eq!(
+            Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Compression::Zstd
+        );
+        assert_eq!(Compression::detect(&[0x28, 0xb5]), Compression::None);
+        assert_eq!(
+            Compression::detect(&[0x5a, 0x2a, 0x4d, 0x18, 0, 0, 0, 0]),
+            Compression::Zstd
+        );
+        assert_eq!(decode(b"1\n2\r\n").unwrap(), "1\n2");
+    }
+
+    #[cfg(feature = "compressed")]
+    #[test]
+    fn test_decompress() {
+        use std::io::Write;
+
+        let input = "1\n3\n2\n4\n5\n6\n";
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzip.write_all(input.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let zstd = ruzstd::encoding::compress_to_vec(
+            input.as_bytes(),
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+        for bytes in [&gzip, &zstd] {
+            assert_eq!(decode(bytes).unwrap(), input.trim_end());
+            let run = solve_streaming(1, &[1, 2], decompress(&bytes[..]).unwrap())
+                .unwrap()
+                .unwrap();
+            assert_eq!(run.parts[1].answer.as_int(), Some(3));
+        }
+        assert!(decode(&gzip[..10]).is_err());
+    }
+
+    #[cfg(feature = "compressed")]
+    #[test]
+    fn test_skippable_frame() {
+        let mut bytes = vec![0x50, 0x2a, 0x4d, 0x18, 4, 0, 0, 0, b'a', b'o', b'c', b'!'];
+        bytes.extend(ruzstd::encoding::compress_to_vec(
+            &b"1\n3\n2\n"[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        ));
+        assert_eq!(Compression::detect(&bytes), Compression::Zstd);
+        assert_eq!(decode(&bytes).unwrap(), "1\n3\n2");
+        assert!(decode(&bytes[..10]).is_err());
+    }
+
+    #[cfg(not(feature = "compressed"))]
+    #[test]
+    fn test_decompress_disabled() {
+        let error = decode(&[0x1f, 0x8b, 8, 0]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(
+            Compression::from_extension(Path::new("day1.txt.gz")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_extension(Path::new("day1.zstd")),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_extension(Path::new("day1.txt")),
+            Compression::None
+        );
+        let dir = std::env::temp_dir().join(format!("aoc2021-extension-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day1.txt"), "1\n2\n").unwrap();
+        std::fs::write(dir.join("day1.txt.gz"), "1\n2\n").unwrap();
+        assert_eq!(read_file(&dir.join("day1.txt")).unwrap(), "1\n2");
+        assert!(read_file(&dir.join("day1.txt.gz")).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cached_path() {
+        let root = std::env::temp_dir().join(format!("aoc2021-cached-{}", std::process::id()));
+        let dir = root.join("input/2021");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(cached_path(&root, 2021, 1), dir.join("day1.txt"));
+        std::fs::write(dir.join("day1.txt.zst"), "").unwrap();
+        assert_eq!(cached_path(&root, 2021, 1), dir.join("day1.txt.zst"));
+        std::fs::write(dir.join("day1.txt"), "").unwrap();
+        assert_eq!(cached_path(&root, 2021, 1), dir.join("day1.txt"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+
+/// What a step cost to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub elapsed: Duration,
+    /// The most heap memory in use during the step, above what was in use before it. Only
+    /// measured with the `alloc-stats` feature.
+    pub peak_bytes: Option<usize>,
+    /// How many times the step allocated or resized a block of heap memory. Only counted with the
+    /// `alloc-stats` feature.
+    pub allocations: Option<usize>,
+}
+
+/// Runs `step` and measures it. Allocations made by other threads at the same time are counted
+/// too, so memory is only accurate when steps run one at a time.
+pub fn measure<T>(step: impl FnOnce() -> T) -> (T, Measurement) {
+    let base = counting::reset_peak();
+    let allocations = counting::allocations();
+    let start = Instant::now();
+    let result = step();
+    let elapsed = start.elapsed();
+    let peak_bytes = counting::peak().map(|peak| peak.saturating_sub(base.unwrap_or(0)));
+    let allocations =
+        counting::allocations().map(|after| after.saturating_sub(allocations.unwrap_or(0)));
+    (
+        result,
+        Measurement {
+            elapsed,
+            peak_bytes,
+            allocations,
+        },
+    )
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+    /// The system allocator, keeping track of the bytes in use, their peak, and how many blocks
+    /// were allocated.
+    struct CountingAllocator;
+
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: Forwarded unchanged from the caller.
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: Forwarded unchanged from the caller.
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            // SAFETY: Forwarded unchanged from the caller.
+            unsafe { System.dealloc(ptr, layout) };
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            // SAFETY: Forwarded unchanged from the caller.
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                grow(new_size);
+                shrink(layout.size());
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Starts a new peak from the bytes in use now, and returns that amount.
+    #[allow(clippy::unnecessary_wraps)] // Matches the version without the feature.
+    pub fn reset_peak() -> Option<usize> {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        Some(current)
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn peak() -> Option<usize> {
+        Some(PEAK.load(Ordering::Relaxed))
+    }
+
+    /// How many blocks have been allocated or resized so far.
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn allocations() -> Option<usize> {
+        Some(ALLOCATIONS.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+mod counting {
+    pub const fn reset_peak() -> Option<usize> {
+        None
+    }
+
+    pub const fn peak() -> Option<usize> {
+        None
+    }
+
+    pub const fn allocations() -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (vec, measurement) = measure(|| vec![0_u64; 1000]);
+        assert_eq!(vec.len(), 1000);
+        if cfg!(feature = "alloc-stats") {
+            assert!(measurement.peak_bytes >= Some(8000));
+            assert!(measurement.allocations >= Some(1));
+        } else {
+            assert_eq!(measurement.peak_bytes, None);
+            assert_eq!(measurement.allocations, None);
+        }
+    }
+}
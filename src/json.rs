@@ -0,0 +1,64 @@
+//! The JSON written by `aoc run --format json` and `aoc serve`.
+
+use std::fmt::Write;
+
+use crate::solver::TimedRun;
+
+/// Quotes a string.
+#[must_use]
+pub fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            ch if ch.is_control() => {
+                // Writing to a `String` can not fail.
+                let _ = write!(quoted, "\\u{:04x}", u32::from(ch));
+            }
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// One object per solved part, with the answer, timings in nanoseconds, and peak heap use. When
+/// both parts were solved together, `solved_together` is true and both objects have the same
+/// solve timings.
+pub fn timed_parts(day: u8, run: &TimedRun) -> impl Iterator<Item = String> + '_ {
+    let bytes = |peak: Option<usize>| peak.map_or_else(|| "null".to_string(), |b| b.to_string());
+    run.parts.iter().map(move |part| {
+        let solve = part.solve.unwrap_or(run.solve);
+        format!(
+            r#"{{"day":{day},"part":{},"answer":{},"parse_ns":{},"solve_ns":{},"parse_peak_bytes":{},"solve_peak_bytes":{},"solved_together":{}}}"#,
+            part.part,
+            string(&part.answer.to_string()),
+            run.parse.elapsed.as_nanos(),
+            solve.elapsed.as_nanos(),
+            bytes(run.parse.peak_bytes),
+            bytes(solve.peak_bytes),
+            part.solve.is_none(),
+        )
+    })
+}
+
+/// An object holding an error message.
+#[must_use]
+pub fn error(message: &str) -> String {
+    format!(r#"{{"error":{}}}"#, string(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("EFJ"), r#""EFJ""#);
+        assert_eq!(string("a\"b\\c\nd\te"), r#""a\"b\\c\nd\u0009e""#);
+        assert_eq!(error("No"), r#"{"error":"No"}"#);
+    }
+}
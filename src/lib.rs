@@ -5,19 +5,44 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
-mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod day_09;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
+pub mod answer;
+mod error;
+pub mod solver;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod all;
+pub mod alu;
+pub mod explain;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fetcher;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod geninput;
+pub mod hints;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod input;
+pub mod instrument;
+pub mod json;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod repl;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod report;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod serve;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod submit;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod util;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod year2021;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use all::{DayResult, InputSource, solve_all};
+pub use answer::Answer;
+pub use error::Error;
 
 aoc_lib! { year = 2021 }
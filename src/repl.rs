@@ -0,0 +1,284 @@
+//! `aoc repl`: solves inputs pasted into the terminal, for when the input is not saved yet.
+//!
+//! `day N` picks a day and starts reading its input, up to a line holding only the end marker.
+//! `paste` reads another input for the same day, `again` solves the last input again, and
+//! `edit` opens it in an editor first.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use crate::Error;
+use crate::solver;
+
+const HELP: &str = "\
+day N   pick a day and paste its input
+paste   paste another input for the same day
+again   solve the last input again
+edit    edit the last input, then solve it
+quit    leave";
+
+/// Where the next input to solve comes from.
+enum Action {
+    Paste,
+    Again,
+    Edit,
+}
+
+/// Reads commands and inputs from `reader` until `quit`, and writes the answers to `out`.
+///
+/// Inputs end at a line that is only `marker`. `edit` is given the last input and returns it
+/// changed, such as by [`external_editor`].
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails. An editor that fails is reported and the
+/// session goes on.
+pub fn run(
+    mut reader: impl BufRead,
+    out: &mut impl Write,
+    marker: &str,
+    mut edit: impl FnMut(&str) -> io::Result<String>,
+) -> io::Result<()> {
+    let mut day = None;
+    let mut last = None::<String>;
+    let mut line = String::new();
+    loop {
+        write!(out, "aoc> ")?;
+        out.flush()?;
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut words = line.split_whitespace();
+        let (command, argument) = (words.next().unwrap_or(""), words.next());
+        let action = match (command, argument) {
+            ("", _) => continue,
+            ("quit" | "exit", _) => return Ok(()),
+            ("help", _) => {
+                writeln!(out, "{HELP}")?;
+                continue;
+            }
+            ("day", Some(argument)) => match argument.parse() {
+                Ok(number) if solver::solver(number).is_some() => {
+                    day = Some(number);
+                    Ok(Action::Paste)
+                }
+                _ => Err(format!("There is no solver for day {argument}")),
+            },
+            ("paste", None) => Ok(Action::Paste),
+            ("again", None) => Ok(Action::Again),
+            ("edit", None) => Ok(Action::Edit),
+            _ => Err(format!("Unknown command {:?}, try help", line.trim())),
+        };
+        let input = match (action, day, &last) {
+            (Err(message), _, _) => {
+                writeln!(out, "{message}")?;
+                continue;
+            }
+            (Ok(_), None, _) => {
+                writeln!(out, "Pick a day first, with day N")?;
+                continue;
+            }
+            (Ok(Action::Paste), Some(day), _) => {
+                writeln!(
+                    out,
+                    "Paste the day {day} input, then {marker} on a line of its own"
+                )?;
+                read_input(&mut reader, marker)?
+            }
+            (Ok(Action::Again | Action::Edit), _, None) => {
+                writeln!(out, "Nothing pasted yet")?;
+                continue;
+            }
+            (Ok(Action::Again), _, Some(last)) => last.clone(),
+            (Ok(Action::Edit), _, Some(last)) => match edit(last) {
+                Ok(edited) => trim_line_endings(edited),
+                Err(error) => {
+                    writeln!(out, "Could not edit the input: {error}")?;
+                    continue;
+                }
+            },
+        };
+        if let Some(day) = day {
+            solve(out, day, &input)?;
+        }
+        last = Some(input);
+    }
+}
+
+/// The lines up to `marker`, or to the end of `reader`.
+fn read_input(reader: &mut impl BufRead, marker: &str) -> io::Result<String> {
+    let mut input = String::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end_matches(['\r', '\n']) == marker {
+            return Ok(trim_line_endings(input));
+        }
+        input.push_str(&line);
+    }
+}
+
+/// Drops the line endings at the end of `input`, like [`crate::input::Source::read`].
+fn trim_line_endings(mut input: String) -> String {
+    let trimmed = input.trim_end_matches(['\r', '\n']).len();
+    input.truncate(trimmed);
+    input
+}
+
+/// Prints what the input looks like, then the answers or why there are none.
+fn solve(out: &mut impl Write, day: u8, input: &str) -> io::Result<()> {
+    let Some(solver) = solver::solver(day) else {
+        return Ok(());
+    };
+    writeln!(
+        out,
+        "{} lines, {} bytes",
+        input.lines().count(),
+        input.len()
+    )?;
+    for warning in warnings(input) {
+        writeln!(out, "Warning: {warning}")?;
+    }
+    match solver.solve_timed(input, &[1, 2]) {
+        Ok(run) => {
+            for part in &run.parts {
+                writeln!(out, "Day {day} part {}: {}", part.part, part.answer)?;
+            }
+            writeln!(
+                out,
+                "Parse: {:.2?}, solve: {:.2?}",
+                run.parse.elapsed, run.solve.elapsed
+            )
+        }
+        Err(error @ Error::InvalidInput(_)) => writeln!(out, "Parse failed. {error}"),
+        Err(error) => writeln!(out, "Parsed, but could not solve. {error}"),
+    }
+}
+
+/// Signs that the input was pasted wrong. Line endings and trailing spaces are tidied before
+/// parsing, but may mean that something else went wrong too.
+fn warnings(input: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if input.is_empty() {
+        warnings.push("the input is empty".to_string());
+        return warnings;
+    }
+    if input.starts_with(['\r', '\n']) {
+        warnings.push("the input starts with an empty line".to_string());
+    }
+    let trailing = input
+        .lines()
+        .filter(|line| line.ends_with([' ', '\t']))
+        .count();
+    match trailing {
+        0 => {}
+        1 => warnings.push("1 line ends in spaces or tabs".to_string()),
+        _ => warnings.push(format!("{trailing} lines end in spaces or tabs")),
+    }
+    if input.contains('\r') {
+        warnings.push("the lines end in \\r\\n".to_string());
+    }
+    warnings
+}
+
+/// Opens `text` in `$VISUAL` or `$EDITOR`, or `vi` if neither is set, and returns it as saved.
+///
+/// # Errors
+///
+/// Returns an error if the editor can not be started or fails, or if the file can not be
+/// written or read back.
+pub fn external_editor(text: &str) -> io::Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let path = env::temp_dir().join(format!("aoc-repl-{}.txt", process::id()));
+    fs::write(&path, format!("{text}\n"))?;
+    let status = process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status();
+    let edited = status.and_then(|status| {
+        if status.success() {
+            fs::read_to_string(&path)
+        } else {
+            Err(io::Error::other(format!("{program} exited with {status}")))
+        }
+    });
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use super::*;
+
+    fn session(commands: &str, edit: impl FnMut(&str) -> io::Result<String>) -> String {
+        let mut out = Vec::new();
+        run(commands.as_bytes(), &mut out, "EOF", edit).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        let out = session(
+            "day 1\n1\n3\n2\n4\n5\n6\nEOF\nagain\nquit\nday 1\n",
+            |_| unreachable!(),
+        );
+        assert!(out.contains("6 lines, 11 bytes\n"), "{out}");
+        assert_eq!(
+            out.matches("Day 1 part 1: 4\nDay 1 part 2: 3\n").count(),
+            2,
+            "{out}"
+        );
+        assert!(out.ends_with("aoc> "), "{out}");
+    }
+
+    #[test]
+    fn test_edit() {
+        let out = session("edit\nday 1\n1\n2\nEOF\nedit\n", |input| {
+            assert_eq!(input, "1\n2");
+            Ok("1\n2\n3\n4\n\n".to_string())
+        });
+        assert!(out.contains("aoc> Pick a day first"), "{out}");
+        assert!(out.contains("Day 1 part 1: 1\n"), "{out}");
+        assert!(
+            out.contains("4 lines, 7 bytes\nDay 1 part 1: 3\nDay 1 part 2: 1\n"),
+            "{out}"
+        );
+        let failed = session("day 1\n1\nEOF\nedit\n", |_| {
+            Err(io::Error::other("no editor"))
+        });
+        assert!(
+            failed.contains("Could not edit the input: no editor"),
+            "{failed}"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let out = session("day 1\n\n1 \nx\nEOF\nday 26\nfold\n", |_| unreachable!());
+        assert!(
+            out.contains("Warning: the input starts with an empty line"),
+            "{out}"
+        );
+        assert!(
+            out.contains("Warning: 1 line ends in spaces or tabs"),
+            "{out}"
+        );
+        assert!(out.contains("Parse failed. Invalid input:"), "{out}");
+        assert!(out.contains("There is no solver for day 26"), "{out}");
+        assert!(out.contains("Unknown command \"fold\", try help"), "{out}");
+    }
+
+    #[test]
+    fn test_warnings() {
+        assert!(warnings("1\n2").is_empty());
+        assert_eq!(warnings(""), ["the input is empty"]);
+        assert_eq!(warnings("1\r\n2"), ["the lines end in \\r\\n"]);
+    }
+}
@@ -0,0 +1,369 @@
+//! The HTML page written by `aoc report`: every day's answers and timings, a sparkline of the
+//! solve time over several runs, and pictures of the puzzles, in one file with nothing to fetch.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::input;
+use crate::solver::{self, TimedRun};
+
+/// What the report shows of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u8,
+    /// The answers and timings of the first run, or why the day could not be solved.
+    pub run: Result<TimedRun, String>,
+    /// How long solving took in each run, drawn as a sparkline.
+    pub solve_times: Vec<Duration>,
+    pub pictures: Vec<Picture>,
+}
+
+/// A picture of a puzzle, as text that may color itself with terminal escape codes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    pub title: String,
+    pub text: String,
+}
+
+/// Solves every day of `year` that has a cached input under `root`, `runs` times each, one day
+/// at a time so that the timings do not disturb each other.
+#[must_use]
+pub fn gather(root: &Path, year: u16, runs: usize) -> Vec<DayReport> {
+    solver::solvers_in(year)
+        .filter_map(|(day, solver)| {
+            let input = input::read_file(&input::cached_path(root, year, day)).ok()?;
+            let input = input.as_str();
+            let mut first = None;
+            let mut solve_times = Vec::new();
+            for _ in 0..runs.max(1) {
+                match solver.solve_timed(input, &[1, 2]) {
+                    Ok(run) => {
+                        solve_times.push(run.solve.elapsed);
+                        first.get_or_insert(run);
+                    }
+                    Err(error) => {
+                        return Some(DayReport {
+                            day,
+                            run: Err(error.to_string()),
+                            solve_times,
+                            pictures: Vec::new(),
+                        });
+                    }
+                }
+            }
+            Some(DayReport {
+                day,
+                run: first.ok_or_else(String::new),
+                solve_times,
+                pictures: pictures(day, input),
+            })
+        })
+        .collect()
+}
+
+/// The pictures worth sharing: the day 9 basins and the day 13 letters.
+#[cfg_attr(
+    not(all(feature = "day09", feature = "day13")),
+    allow(unused_variables)
+)]
+fn pictures(day: u8, input: &str) -> Vec<Picture> {
+    let picture = |title: &str, text| Picture {
+        title: title.to_string(),
+        text,
+    };
+    let input = crate::util::parse::normalize(input);
+    match day {
+        #[cfg(feature = "day09")]
+        9 => {
+            use crate::year2021::day_09::{self, Day09};
+            <Day09 as solver::Solver>::parse(&input).map_or_else(
+                |_| Vec::new(),
+                |grid| {
+                    let basins = day_09::basins(&grid);
+                    let style = crate::util::style::Style::COLOR;
+                    vec![picture(
+                        "The basins",
+                        day_09::basin_map(&grid, &basins, style),
+                    )]
+                },
+            )
+        }
+        #[cfg(feature = "day13")]
+        13 => {
+            use crate::year2021::day_13::{self, Day13};
+            let folded = <Day13 as solver::Solver>::parse(&input)
+                .ok()
+                .and_then(|page| page.folded().ok());
+            folded.map_or_else(Vec::new, |dots| {
+                let text = day_13::render(&dots, crate::util::render::Backend::HalfBlocks);
+                vec![picture("The folded paper", text.trim_start().to_string())]
+            })
+        }
+        _ => Vec::new(),
+    }
+}
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em auto; max-width: 60em; padding: 0 1em; \
+background: #0f0f23; color: #cccccc; }
+h1, h2 { color: #ffff66; font-weight: normal; }
+table { border-collapse: collapse; }
+th, td { padding: 0.25em 0.75em; text-align: right; vertical-align: middle; }
+th { border-bottom: 1px solid #444; }
+td.answer { font-family: monospace; white-space: pre; color: #ffffff; }
+td.error { text-align: left; color: #ff6666; }
+svg polyline { fill: none; stroke: #00cc00; stroke-width: 1.5; }
+pre { line-height: 1; font-size: 0.8em; overflow-x: auto; }";
+
+/// The whole page, for the days in `days`.
+#[must_use]
+pub fn html(year: u16, days: &[DayReport]) -> String {
+    let mut page = String::new();
+    // Writing to a `String` can not fail.
+    let _ = write!(
+        page,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code {year}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n\
+         <h1>Advent of Code {year}</h1>\n<table>\n<thead><tr><th>Day</th><th>Part 1</th>\
+         <th>Part 2</th><th>Parse</th><th>Solve</th><th>Solve times</th></tr></thead>\n<tbody>\n"
+    );
+    for report in days {
+        let day = report.day;
+        let _ = match &report.run {
+            Ok(run) => {
+                let answer = |index: usize| {
+                    run.parts
+                        .get(index)
+                        .map_or_else(String::new, |part| escape(&part.answer.to_string()))
+                };
+                writeln!(
+                    page,
+                    "<tr><td>{day}</td><td class=\"answer\">{}</td><td class=\"answer\">{}</td>\
+                     <td>{:.2?}</td><td>{:.2?}</td><td>{}</td></tr>",
+                    answer(0),
+                    answer(1),
+                    run.parse.elapsed,
+                    run.solve.elapsed,
+                    sparkline(&report.solve_times),
+                )
+            }
+            Err(error) => writeln!(
+                page,
+                "<tr><td>{day}</td><td class=\"error\" colspan=\"5\">{}</td></tr>",
+                escape(error)
+            ),
+        };
+    }
+    page.push_str("</tbody>\n</table>\n");
+    for report in days {
+        for picture in &report.pictures {
+            let _ = writeln!(
+                page,
+                "<h2>Day {}: {}</h2>\n<pre>{}</pre>",
+                report.day,
+                escape(&picture.title),
+                terminal_to_html(&picture.text)
+            );
+        }
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+/// Escapes the characters that HTML gives a meaning.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+const SPARKLINE_WIDTH: f64 = 120.0;
+const SPARKLINE_HEIGHT: f64 = 20.0;
+
+/// The times as a line rising with the slower runs, with the fastest and slowest in its tooltip.
+fn sparkline(times: &[Duration]) -> String {
+    let (Some(&fastest), Some(&slowest)) = (times.iter().min(), times.iter().max()) else {
+        return String::new();
+    };
+    let range = slowest.saturating_sub(fastest).as_secs_f64();
+    let gaps = u32::try_from(times.len().max(2) - 1).unwrap_or(u32::MAX);
+    let step = SPARKLINE_WIDTH / f64::from(gaps);
+    let mut points = (0_u32..)
+        .zip(times)
+        .map(|(index, &time)| {
+            let height = if range > 0.0 {
+                time.saturating_sub(fastest).as_secs_f64() / range
+            } else {
+                0.5
+            };
+            let y = (1.0 - height).mul_add(SPARKLINE_HEIGHT - 2.0, 1.0);
+            format!("{:.1},{y:.1}", f64::from(index) * step)
+        })
+        .collect::<Vec<_>>();
+    if let [only] = points.as_slice() {
+        let (_, y) = only.split_once(',').unwrap_or_default();
+        points.push(format!("{SPARKLINE_WIDTH:.1},{y}"));
+    }
+    format!(
+        "<svg width=\"{SPARKLINE_WIDTH}\" height=\"{SPARKLINE_HEIGHT}\"><title>{} runs, \
+         {fastest:.2?} to {slowest:.2?}</title><polyline points=\"{}\"/></svg>",
+        times.len(),
+        points.join(" ")
+    )
+}
+
+/// Turns the colors set by SGR escape codes, as written by [`crate::util::style::Style`], into
+/// styled spans, and escapes the rest.
+fn terminal_to_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut color = None::<String>;
+    let mut weight = None::<&str>;
+    // The style of the open span, if one is open.
+    let mut open = String::new();
+    let mut rest = text;
+    while let Some((before, after)) = rest.split_once("\x1b[") {
+        html.push_str(&escape(before));
+        let Some((codes, after)) = after.split_once('m') else {
+            rest = after;
+            break;
+        };
+        rest = after;
+        let codes = codes
+            .split(';')
+            .map(|code| code.parse::<u8>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let mut codes = codes.iter().copied();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => (color, weight) = (None, None),
+                1 => weight = Some("font-weight:bold"),
+                2 => weight = Some("opacity:0.5"),
+                22 => weight = None,
+                30..=37 | 90..=97 => color = Some(PALETTE[usize::from(code % 10)].to_string()),
+                39 => color = None,
+                38 => {
+                    if let (Some(2), Some(r), Some(g), Some(b)) =
+                        (codes.next(), codes.next(), codes.next(), codes.next())
+                    {
+                        color = Some(format!("#{r:02x}{g:02x}{b:02x}"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let style = [
+            color.as_deref().map(|color| format!("color:{color}")),
+            weight.map(str::to_string),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(";");
+        // Runs of the same color, like a whole basin, share one span.
+        if style != open {
+            if !open.is_empty() {
+                html.push_str("</span>");
+            }
+            if !style.is_empty() {
+                let _ = write!(html, "<span style=\"{style}\">");
+            }
+            open = style;
+        }
+    }
+    html.push_str(&escape(rest));
+    if !open.is_empty() {
+        html.push_str("</span>");
+    }
+    html
+}
+
+/// The eight basic terminal colors, which the bright ones share.
+const PALETTE: [&str; 8] = [
+    "#808080", "#ff5555", "#55ff55", "#ffff55", "#5555ff", "#ff55ff", "#55ffff", "#ffffff",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use crate::instrument::Measurement;
+    use crate::solver::TimedPart;
+
+    #[test]
+    fn test_terminal_to_html() {
+        assert_eq!(terminal_to_html("a<b"), "a&lt;b");
+        assert_eq!(
+            terminal_to_html("\x1b[1;38;2;230;75;60m1\x1b[22;90m9\x1b[0m\n"),
+            "<span style=\"color:#e64b3c;font-weight:bold\">1</span>\
+             <span style=\"color:#808080\">9</span>\n"
+        );
+        assert_eq!(
+            terminal_to_html("\x1b[90ma\x1b[90mb\x1b[0m"),
+            "<span style=\"color:#808080\">ab</span>"
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        let times = [3, 1, 2].map(Duration::from_millis);
+        let line = sparkline(&times);
+        assert!(line.contains("points=\"0.0,1.0 60.0,19.0 120.0,10.0\""));
+        assert!(line.contains("3 runs, 1.00ms to 3.00ms"));
+        let flat = sparkline(&[Duration::from_millis(1)]);
+        assert!(flat.contains("points=\"0.0,10.0 120.0,10.0\""));
+    }
+
+    #[test]
+    fn test_html() {
+        let measurement = Measurement {
+            elapsed: Duration::from_micros(1500),
+            peak_bytes: None,
+            allocations: None,
+        };
+        let part = |part, answer| TimedPart {
+            part,
+            answer,
+            solve: None,
+        };
+        let days = [
+            DayReport {
+                day: 1,
+                run: Ok(TimedRun {
+                    parse: measurement,
+                    solve: measurement,
+                    parts: vec![part(1, Answer::Int(7)), part(2, Answer::from("<A&B>"))],
+                }),
+                solve_times: vec![measurement.elapsed; 2],
+                pictures: vec![Picture {
+                    title: "Dots".to_string(),
+                    text: "#.\n.#".to_string(),
+                }],
+            },
+            DayReport {
+                day: 2,
+                run: Err("Invalid input: Syntax error".to_string()),
+                solve_times: Vec::new(),
+                pictures: Vec::new(),
+            },
+        ];
+        let page = html(2021, &days);
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains(
+            "<tr><td>1</td><td class=\"answer\">7</td><td class=\"answer\">&lt;A&amp;B&gt;</td>\
+             <td>1.50ms</td><td>1.50ms</td><td><svg"
+        ));
+        assert!(page.contains("colspan=\"5\">Invalid input: Syntax error</td>"));
+        assert!(page.contains("<h2>Day 1: Dots</h2>\n<pre>#.\n.#</pre>"));
+        assert!(!page.contains("http"));
+    }
+}
@@ -0,0 +1,411 @@
+//! A small HTTP server for `aoc serve`. It answers `POST /solve/{day}/{part}`, with the puzzle
+//! input as the request body, with the same JSON object as `aoc run --format json`.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::solver::DynSolver;
+use crate::{Error, json, solver};
+
+/// Request bodies above this size are refused. The largest puzzle input is about 30 kB.
+const MAX_BODY: usize = 1 << 20;
+
+/// Request lines and header lines above this size are refused.
+const MAX_LINE: usize = 8 << 10;
+
+/// Requests with more headers than this are refused.
+const MAX_HEADERS: usize = 64;
+
+/// How long a connection may leave a read or a write waiting before it is dropped.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections beyond this many at once are refused with a 503.
+const MAX_CONNECTIONS: usize = 32;
+
+/// Solves in a child process are stopped after this long. Every day takes well under a second.
+const SOLVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait after accepting a connection fails, such as when there are no file
+/// descriptors left, before trying again.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Where [`serve`] and [`handle`] solve the requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Runner {
+    /// On the connection's thread, as [`route`] does. A stack overflow in a solver takes the
+    /// whole process down with it.
+    InProcess,
+    /// In a child process running `aoc run` from this program, the `aoc` binary, so that a
+    /// crash only loses its own request. Solves that take longer than 30 seconds are stopped.
+    Child(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// A JSON object.
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json::error(message),
+        }
+    }
+
+    const fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            414 => "URI Too Long",
+            422 => "Unprocessable Content",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            _ => "",
+        }
+    }
+}
+
+/// Answers a request, solving it in this process.
+#[must_use]
+pub fn route(method: &str, path: &str, body: &str) -> Response {
+    route_with(&Runner::InProcess, method, path, body)
+}
+
+fn route_with(runner: &Runner, method: &str, path: &str, body: &str) -> Response {
+    let Some(rest) = path.strip_prefix("/solve/") else {
+        return Response::error(404, "Not found");
+    };
+    if method != "POST" {
+        return Response::error(405, "Use POST");
+    }
+    let Some((day, part)) = rest
+        .split_once('/')
+        .and_then(|(day, part)| Some((day.parse::<u8>().ok()?, part.parse::<u8>().ok()?)))
+    else {
+        return Response::error(404, "Expected /solve/<day>/<part>");
+    };
+    let Some(solver) = solver::solver(day) else {
+        return Response::error(404, &format!("Day {day} is not available"));
+    };
+    let input = body.trim_end_matches(['\r', '\n']);
+    match runner {
+        Runner::InProcess => solve_here(solver, day, part, input),
+        Runner::Child(program) => solve_in_child(program, day, part, input),
+    }
+}
+
+fn solve_here(solver: &dyn DynSolver, day: u8, part: u8, input: &str) -> Response {
+    match solver.solve_timed(input, &[part]) {
+        Ok(run) => Response {
+            status: 200,
+            body: json::timed_parts(day, &run).collect(),
+        },
+        Err(error) => Response::error(422, &error.to_string()),
+    }
+}
+
+fn solve_in_child(program: &Path, day: u8, part: u8, input: &str) -> Response {
+    if !matches!(part, 1 | 2) {
+        return Response::error(422, &Error::UnknownPart(part).to_string());
+    }
+    run_child(program, day, part, input).unwrap_or_else(|error| {
+        eprintln!("Running the solver failed: {error}");
+        Response::error(500, "The solver could not be run")
+    })
+}
+
+/// Runs `aoc run` in a child process and turns its output into a response: its JSON line, or
+/// the message of the error it failed with.
+fn run_child(program: &Path, day: u8, part: u8, input: &str) -> io::Result<Response> {
+    let mut child = Command::new(program)
+        .args(["run", "--stdin", "--format", "json", "--day"])
+        .arg(day.to_string())
+        .arg("--part")
+        .arg(part.to_string())
+        .env_remove("RUST_LOG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // The child reads all of its input before it writes anything, so this can not wait on it.
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(error) = stdin.write_all(input.as_bytes())
+        && error.kind() != io::ErrorKind::BrokenPipe
+    {
+        return Err(error);
+    }
+    let deadline = Instant::now() + SOLVE_TIMEOUT;
+    while child.try_wait()?.is_none() {
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(Response::error(422, "The input took too long to solve"));
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(Response {
+            status: 200,
+            body: String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string(),
+        });
+    }
+    // `aoc` exits with 1 after printing an error, and any other way if it crashed.
+    if output.status.code() != Some(1) {
+        return Ok(Response::error(500, "The solver crashed"));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix("Error: "))
+        .unwrap_or("The solver failed");
+    Ok(Response::error(422, message))
+}
+
+/// Reads a line of at most `MAX_LINE` bytes into `line`, or returns `None` if it is longer.
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> io::Result<Option<usize>> {
+    line.clear();
+    let read = reader.take(MAX_LINE as u64).read_line(line)?;
+    if read == MAX_LINE && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(read))
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Result<(String, String, String), Response>> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if read_line(&mut reader, &mut line)?.is_none() {
+        return Ok(Err(Response::error(414, "The request line is too long")));
+    }
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Ok(Err(Response::error(400, "Malformed request line")));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+    let mut length = None;
+    for headers in 0.. {
+        let Some(read) = read_line(&mut reader, &mut line)? else {
+            return Ok(Err(Response::error(431, "A header is too long")));
+        };
+        if read == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if headers == MAX_HEADERS {
+            return Ok(Err(Response::error(431, "There are too many headers")));
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = match length {
+        Some(length) if length > MAX_BODY => {
+            return Ok(Err(Response::error(413, "The input is too large")));
+        }
+        Some(length) => length,
+        None if method == "POST" => {
+            return Ok(Err(Response::error(411, "Content-Length is required")));
+        }
+        None => 0,
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let Ok(body) = String::from_utf8(body) else {
+        return Ok(Err(Response::error(400, "The input is not UTF-8")));
+    };
+    Ok(Ok((method, path, body)))
+}
+
+/// Reads one request from the connection and writes the response, solving it with `runner`.
+///
+/// # Errors
+///
+/// Returns an error if the connection fails or times out.
+pub fn handle(mut stream: TcpStream, runner: &Runner) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let response = match read_request(&stream)? {
+        Ok((method, path, body)) => route_with(runner, &method, &path, &body),
+        Err(response) => response,
+    };
+    respond(&mut stream, &response)
+}
+
+fn respond(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// One of the `MAX_CONNECTIONS` connections that may be handled at once, given back when it is
+/// dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()?;
+        Some(Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Answers connections until the process is stopped, each on its own thread and solved with
+/// `runner`. Connections that fail, or can not be accepted, are logged and do not stop it.
+pub fn serve(listener: &TcpListener, runner: &Runner) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("Accepting a connection failed: {error}");
+                thread::sleep(ACCEPT_BACKOFF);
+                continue;
+            }
+        };
+        let Some(slot) = Slot::take(&active) else {
+            let busy = Response::error(503, "Too many connections");
+            if let Err(error) = stream
+                .set_write_timeout(Some(IO_TIMEOUT))
+                .and_then(|()| respond(&mut stream, &busy))
+            {
+                eprintln!("Connection failed: {error}");
+            }
+            continue;
+        };
+        let runner = runner.clone();
+        let spawned = thread::Builder::new().spawn(move || {
+            let _slot = slot;
+            if let Err(error) = handle(stream, &runner) {
+                eprintln!("Connection failed: {error}");
+            }
+        });
+        if let Err(error) = spawned {
+            eprintln!("Starting a thread failed: {error}");
+        }
+    }
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn test_route() {
+        let response = route("POST", "/solve/1/2", INPUT);
+        assert_eq!(response.status, 200);
+        assert!(
+            response
+                .body
+                .starts_with(r#"{"day":1,"part":2,"answer":"5","parse_ns":"#)
+        );
+        assert_eq!(route("GET", "/solve/1/2", INPUT).status, 405);
+        assert_eq!(route("POST", "/solve/1", INPUT).status, 404);
+        assert_eq!(route("POST", "/solve/0/1", INPUT).status, 404);
+        assert_eq!(route("POST", "/solve/1/3", INPUT).status, 422);
+        assert_eq!(route("POST", "/", INPUT).status, 404);
+    }
+
+    /// Sends `request` to [`handle`] and returns the response.
+    fn exchange(request: &[u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server =
+            thread::spawn(move || handle(listener.accept().unwrap().0, &Runner::InProcess));
+        let mut client = TcpStream::connect(address).unwrap();
+        client.write_all(request).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap().unwrap();
+        response
+    }
+
+    #[test]
+    fn test_handle() {
+        let response = exchange(
+            format!(
+                "POST /solve/1/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{INPUT}",
+                INPUT.len()
+            )
+            .as_bytes(),
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(r#""answer":"7""#));
+    }
+
+    #[test]
+    fn test_handle_limits() {
+        // Each request ends where the server stops reading, as the unread rest of a request
+        // could reset the connection before the response is read.
+        let long_path = format!("POST /{}", "a".repeat(MAX_LINE - 6));
+        assert!(exchange(long_path.as_bytes()).starts_with("HTTP/1.1 414 "));
+        let long_header = format!(
+            "POST /solve/1/1 HTTP/1.1\r\nX: {}",
+            "a".repeat(MAX_LINE - 3)
+        );
+        assert!(exchange(long_header.as_bytes()).starts_with("HTTP/1.1 431 "));
+        let many_headers = format!(
+            "POST /solve/1/1 HTTP/1.1\r\n{}",
+            "X: a\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert!(exchange(many_headers.as_bytes()).starts_with("HTTP/1.1 431 "));
+        let headers = format!(
+            "POST /solve/1/1 HTTP/1.1\r\n{}Content-Length: 3\r\n\r\n1\n2",
+            "X: a\r\n".repeat(MAX_HEADERS - 1)
+        );
+        assert!(exchange(headers.as_bytes()).starts_with("HTTP/1.1 200 "));
+    }
+
+    #[test]
+    fn test_serve_connection_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(&listener, &Runner::InProcess));
+        // Connections that send nothing hold their slots until they time out.
+        let idle = (0..MAX_CONNECTIONS)
+            .map(|_| TcpStream::connect(address).unwrap())
+            .collect::<Vec<_>>();
+        let mut response = String::new();
+        TcpStream::connect(address)
+            .unwrap()
+            .read_to_string(&mut response)
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 "));
+        drop(idle);
+    }
+}
@@ -0,0 +1,523 @@
+use std::any;
+#[cfg(feature = "arena")]
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+
+use tracing::{debug_span, info_span};
+
+use crate::{Answer, Error};
+
+use crate::explain::Explain;
+use crate::instrument::{Measurement, measure};
+use crate::util::parse::normalize;
+use crate::util::render::Backend;
+
+/// Where [`Solver::parse_in`] makes its scratch allocations.
+#[cfg(feature = "arena")]
+pub use bumpalo::Bump as Arena;
+
+/// A day's puzzle, split into parsing and the two parts.
+pub trait Solver {
+    type Parsed;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    fn parse(input: &str) -> Result<Self::Parsed, Error>;
+
+    /// Parses the input with the allocations that are only needed while parsing, such as lines
+    /// collected before their count is known, made in `arena`.
+    ///
+    /// The runner resets the arena after each parse, which frees them all at once. Days that
+    /// have such allocations override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    #[cfg(feature = "arena")]
+    fn parse_in(input: &str, _arena: &Arena) -> Result<Self::Parsed, Error> {
+        Self::parse(&normalize(input))
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the input has no solution.
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, Error>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the input has no solution.
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, Error>;
+
+    /// Solves both parts together. Days where part 2 repeats the work of part 1 override this to
+    /// share it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input has no solution.
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), Error> {
+        Ok((Self::part1(parsed)?, Self::part2(parsed)?))
+    }
+
+    /// Parses the input once, after [`normalize`], and solves both parts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed or has no solution.
+    fn solve(input: &str) -> Result<(Self::Part1, Self::Part2), Error> {
+        Self::solve_both(&Self::parse(&normalize(input))?)
+    }
+
+    /// A picture of the puzzle, for days that have one.
+    fn visualize(_parsed: &Self::Parsed, _backend: Backend) -> Option<String> {
+        None
+    }
+
+    /// Adds what the solution works out on the way to the answers to `explain`, for days that
+    /// have something to show. Only called when asked for, so solving never pays for it.
+    fn explain(_parsed: &Self::Parsed, _explain: &mut Explain) {}
+}
+
+/// The answer to a part that does not exist, such as the second part of the last day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("-")
+    }
+}
+
+/// The answers from `DynSolver::solve_timed`, with the time and memory each step took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedRun {
+    pub parse: Measurement,
+    /// Solving every part, together or one at a time.
+    pub solve: Measurement,
+    pub parts: Vec<TimedPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedPart {
+    pub part: u8,
+    pub answer: Answer,
+    /// Solving this part alone, or `None` if both parts were solved together.
+    pub solve: Option<Measurement>,
+}
+
+/// A `Solver` with its types erased, so that different days can be stored together.
+pub trait DynSolver: Sync {
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed or has no solution.
+    fn solve(&self, input: &str) -> Result<[Answer; 2], Error>;
+
+    /// Only parses the input, to check that it is well formed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    fn check(&self, input: &str) -> Result<(), Error>;
+
+    /// Solves a single part, 1 or 2.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such part, or if the input is malformed or has no
+    /// solution.
+    fn solve_part(&self, input: &str, part: u8) -> Result<Answer, Error>;
+
+    /// Parses the input once and solves the given parts, timing each step separately. When both
+    /// parts are asked for, they are solved together with `Solver::solve_both`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such part, or if the input is malformed or has no
+    /// solution.
+    fn solve_timed(&self, input: &str, parts: &[u8]) -> Result<TimedRun, Error>;
+
+    /// Parses the input and draws it, if the day has a picture.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    fn visualize(&self, input: &str, backend: Backend) -> Result<Option<String>, Error>;
+
+    /// Parses the input and explains how the answers are reached. Days that have nothing to show
+    /// leave it empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is malformed.
+    fn explain(&self, input: &str) -> Result<Explain, Error>;
+}
+
+/// The solver's type name without its path, such as `Day12`, for tracing.
+fn name<S>() -> &'static str {
+    let name = any::type_name::<S>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(feature = "arena")]
+thread_local! {
+    /// The arena that parsing uses on this thread. Resetting keeps its largest chunk, so that
+    /// later parses of a similar input allocate nothing for their scratch space.
+    static ARENA: RefCell<Arena> = RefCell::new(Arena::new());
+}
+
+/// Parses `input`, after [`normalize`], with the arena of this thread.
+#[cfg(feature = "arena")]
+fn parse<S: Solver>(input: &str) -> Result<S::Parsed, Error> {
+    debug_span!("parse").in_scope(|| {
+        ARENA.with_borrow_mut(|arena| {
+            let parsed = S::parse_in(&normalize(input), arena);
+            arena.reset();
+            parsed
+        })
+    })
+}
+
+/// Parses `input`, after [`normalize`].
+#[cfg(not(feature = "arena"))]
+fn parse<S: Solver>(input: &str) -> Result<S::Parsed, Error> {
+    debug_span!("parse").in_scope(|| S::parse(&normalize(input)))
+}
+
+fn part<S: Solver>(parsed: &S::Parsed, part: u8) -> Result<Answer, Error> {
+    debug_span!("part", part).in_scope(|| match part {
+        1 => S::part1(parsed).map(Into::into),
+        2 => S::part2(parsed).map(Into::into),
+        _ => Err(Error::UnknownPart(part)),
+    })
+}
+
+impl<S: Solver + Sync> DynSolver for S {
+    fn solve(&self, input: &str) -> Result<[Answer; 2], Error> {
+        let _span = info_span!("solve", solver = name::<S>()).entered();
+        let (part1, part2) = S::solve_both(&parse::<S>(input)?)?;
+        Ok([part1.into(), part2.into()])
+    }
+
+    fn check(&self, input: &str) -> Result<(), Error> {
+        parse::<S>(input).map(drop)
+    }
+
+    fn solve_part(&self, input: &str, part: u8) -> Result<Answer, Error> {
+        if !matches!(part, 1 | 2) {
+            return Err(Error::UnknownPart(part));
+        }
+        let _span = info_span!("solve", solver = name::<S>()).entered();
+        self::part::<S>(&parse::<S>(input)?, part)
+    }
+
+    fn solve_timed(&self, input: &str, parts: &[u8]) -> Result<TimedRun, Error> {
+        let _span = info_span!("solve", solver = name::<S>()).entered();
+        let (parsed, parse) = measure(|| parse::<S>(input));
+        let parsed = parsed?;
+        if parts == [1, 2] {
+            let (answers, solve) =
+                measure(|| debug_span!("solve_both").in_scope(|| S::solve_both(&parsed)));
+            let (first, second) = answers?;
+            let part = |part, answer: Answer| TimedPart {
+                part,
+                answer,
+                solve: None,
+            };
+            return Ok(TimedRun {
+                parse,
+                solve,
+                parts: vec![part(1, first.into()), part(2, second.into())],
+            });
+        }
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let (answer, solve) = measure(|| self::part::<S>(&parsed, part));
+                Ok(TimedPart {
+                    part,
+                    answer: answer?,
+                    solve: Some(solve),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let measurements = parts.iter().filter_map(|part| part.solve);
+        let solve = Measurement {
+            elapsed: measurements.clone().map(|m| m.elapsed).sum(),
+            peak_bytes: measurements.clone().filter_map(|m| m.peak_bytes).max(),
+            allocations: measurements.map(|m| m.allocations).sum::<Option<usize>>(),
+        };
+        Ok(TimedRun {
+            parse,
+            solve,
+            parts,
+        })
+    }
+
+    fn visualize(&self, input: &str, backend: Backend) -> Result<Option<String>, Error> {
+        Ok(S::visualize(&parse::<S>(input)?, backend))
+    }
+
+    fn explain(&self, input: &str) -> Result<Explain, Error> {
+        let parsed = parse::<S>(input)?;
+        let mut explain = Explain::new();
+        debug_span!("explain").in_scope(|| S::explain(&parsed, &mut explain));
+        Ok(explain)
+    }
+}
+
+macro_rules! registry {
+    ($($year:literal => $module:ident {
+        $($day:literal => $feature:literal $day_module:ident::$solver:ident,)*
+    })*) => {
+        /// Every year that has puzzles in this crate, oldest first.
+        pub const YEARS: &[u16] = &[$($year),*];
+
+        /// The solver for `day` of `year`, counting days from 1. Days whose feature is disabled
+        /// have no solver.
+        #[must_use]
+        pub fn solver_in(year: u16, day: u8) -> Option<&'static dyn DynSolver> {
+            match (year, day) {
+                $($(
+                    #[cfg(feature = $feature)]
+                    ($year, $day) => Some(&crate::$module::$day_module::$solver),
+                )*)*
+                _ => None,
+            }
+        }
+    };
+}
+
+registry! {
+    2021 => year2021 {
+        1 => "day01" day_01::Day01,
+        2 => "day02" day_02::Day02,
+        3 => "day03" day_03::Day03,
+        4 => "day04" day_04::Day04,
+        5 => "day05" day_05::Day05,
+        6 => "day06" day_06::Day06,
+        7 => "day07" day_07::Day07,
+        8 => "day08" day_08::Day08,
+        9 => "day09" day_09::Day09,
+        10 => "day10" day_10::Day10,
+        11 => "day11" day_11::Day11,
+        12 => "day12" day_12::Day12,
+        13 => "day13" day_13::Day13,
+        14 => "day14" day_14::Day14,
+        15 => "day15" day_15::Day15,
+        16 => "day16" day_16::Day16,
+        17 => "day17" day_17::Day17,
+        18 => "day18" day_18::Day18,
+        19 => "day19" day_19::Day19,
+        20 => "day20" day_20::Day20,
+        21 => "day21" day_21::Day21,
+        22 => "day22" day_22::Day22,
+        23 => "day23" day_23::Day23,
+        24 => "day24" day_24::Day24,
+        25 => "day25" day_25::Day25,
+    }
+}
+
+/// The 2021 solver for `day`, counting from 1. Days whose feature is disabled have no solver.
+#[must_use]
+pub fn solver(day: u8) -> Option<&'static dyn DynSolver> {
+    solver_in(2021, day)
+}
+
+/// Every day of `year` that has a solver, with its solver.
+pub fn solvers_in(year: u16) -> impl Iterator<Item = (u8, &'static dyn DynSolver)> {
+    (1..=25).filter_map(move |day| Some((day, solver_in(year, day)?)))
+}
+
+/// Every day of 2021 that has a solver, with its solver.
+pub fn solvers() -> impl Iterator<Item = (u8, &'static dyn DynSolver)> {
+    solvers_in(2021)
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    #[test]
+    fn test_registry() {
+        assert!(solver(0).is_none());
+        assert!(solver(26).is_none());
+        assert_eq!(solvers().count(), 25);
+        assert_eq!(YEARS, [2021]);
+        assert!(solver_in(2020, 1).is_none());
+        assert_eq!(solvers_in(2021).count(), 25);
+        let answers = solver(1).unwrap().solve("1\n3\n2\n4\n5\n6").unwrap();
+        assert_eq!(answers, [Answer::Int(4), Answer::Int(3)]);
+        assert_eq!(
+            solver(1)
+                .unwrap()
+                .solve_part("1\n3\n2\n4\n5\n6", 2)
+                .unwrap(),
+            Answer::Int(3)
+        );
+        assert!(
+            solver(1)
+                .unwrap()
+                .solve_part("1\n3\n2\n4\n5\n6", 3)
+                .is_err()
+        );
+        let run = solver(1)
+            .unwrap()
+            .solve_timed("1\n3\n2\n4\n5\n6", &[2])
+            .unwrap();
+        assert_eq!(run.parts.len(), 1);
+        assert_eq!(
+            (run.parts[0].part, &run.parts[0].answer),
+            (2, &Answer::Int(3))
+        );
+        assert_eq!(
+            run.parts[0].solve.map(|m| m.elapsed),
+            Some(run.solve.elapsed)
+        );
+        let run = solver(4)
+            .unwrap()
+            .solve_timed(
+                crate::geninput::generate(4, 1, 0).unwrap().trim_end(),
+                &[1, 2],
+            )
+            .unwrap();
+        assert_eq!(run.parts.len(), 2);
+        assert!(run.parts.iter().all(|part| part.solve.is_none()));
+        let answers = solver(25).unwrap().solve(">>").unwrap();
+        assert_eq!(answers[1].to_string(), "-");
+        assert!(solver(2).unwrap().solve("sideways 3").is_err());
+    }
+
+    /// Parses a generated input, and checks that it serializes to the same JSON after a round
+    /// trip. Maps are compared regardless of their order.
+    #[cfg(feature = "serde")]
+    fn round_trip<S>(day: u8)
+    where
+        S: Solver,
+        S::Parsed: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        for (seed, input) in crate::geninput::samples(day) {
+            let parsed = S::parse(&input).unwrap();
+            let json = serde_json::to_value(&parsed).unwrap();
+            let restored = serde_json::from_value::<S::Parsed>(json.clone()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&restored).unwrap(),
+                json,
+                "day {day}, seed {seed}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use crate::year2021::*;
+        round_trip::<day_01::Day01>(1);
+        round_trip::<day_02::Day02>(2);
+        round_trip::<day_03::Day03>(3);
+        round_trip::<day_04::Day04>(4);
+        round_trip::<day_05::Day05>(5);
+        round_trip::<day_06::Day06>(6);
+        round_trip::<day_07::Day07>(7);
+        round_trip::<day_08::Day08>(8);
+        round_trip::<day_09::Day09>(9);
+        round_trip::<day_10::Day10>(10);
+        round_trip::<day_11::Day11>(11);
+        round_trip::<day_12::Day12>(12);
+        round_trip::<day_13::Day13>(13);
+        round_trip::<day_14::Day14>(14);
+        round_trip::<day_15::Day15>(15);
+        round_trip::<day_16::Day16>(16);
+        round_trip::<day_17::Day17>(17);
+        round_trip::<day_18::Day18>(18);
+        round_trip::<day_19::Day19>(19);
+        round_trip::<day_20::Day20>(20);
+        round_trip::<day_21::Day21>(21);
+        round_trip::<day_22::Day22>(22);
+        round_trip::<day_23::Day23>(23);
+        round_trip::<day_24::Day24>(24);
+        round_trip::<day_25::Day25>(25);
+    }
+
+    /// Parses a generated input, writes it back out with `format`, and checks that parsing that
+    /// gives the same.
+    fn display_round_trip<S>(day: u8, format: impl Fn(&S::Parsed) -> String)
+    where
+        S: Solver,
+        S::Parsed: PartialEq + std::fmt::Debug,
+    {
+        for (seed, input) in crate::geninput::samples(day) {
+            let parsed = S::parse(&input).unwrap();
+            let written = format(&parsed);
+            assert_eq!(
+                S::parse(&written).unwrap(),
+                parsed,
+                "day {day}, seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        use crate::util::parse::join_lines;
+        use crate::year2021::*;
+        display_round_trip::<day_02::Day02>(2, |commands| join_lines(commands));
+        display_round_trip::<day_04::Day04>(4, ToString::to_string);
+        display_round_trip::<day_05::Day05>(5, |lines| join_lines(lines));
+        display_round_trip::<day_08::Day08>(8, |displays| join_lines(displays));
+        display_round_trip::<day_12::Day12>(12, ToString::to_string);
+        display_round_trip::<day_13::Day13>(13, ToString::to_string);
+        display_round_trip::<day_14::Day14>(14, ToString::to_string);
+        display_round_trip::<day_16::Day16>(16, ToString::to_string);
+        display_round_trip::<day_17::Day17>(17, ToString::to_string);
+        display_round_trip::<day_18::Day18>(18, |numbers| join_lines(numbers));
+        display_round_trip::<day_19::Day19>(19, |scanners| day_19::join_scanners(scanners));
+        display_round_trip::<day_20::Day20>(20, ToString::to_string);
+        display_round_trip::<day_21::Day21>(21, ToString::to_string);
+        display_round_trip::<day_22::Day22>(22, |steps| join_lines(steps));
+        display_round_trip::<day_23::Day23>(23, ToString::to_string);
+        display_round_trip::<day_24::Day24>(24, ToString::to_string);
+    }
+
+    #[test]
+    fn test_untidy_inputs() {
+        for (day, solver) in solvers() {
+            let (_, input) = crate::geninput::samples(day).next().unwrap();
+            assert_eq!(
+                solver.solve(&untidy(&input)).unwrap(),
+                solver.solve(&input).unwrap(),
+                "day {day}"
+            );
+        }
+    }
+
+    /// Checks that parsing in an arena gives what parsing on the heap does, for generated
+    /// inputs and for `malformed`, with the arena reused and reset in between.
+    #[cfg(feature = "arena")]
+    fn parses_in_arena<S>(day: u8, malformed: &str)
+    where
+        S: Solver,
+        S::Parsed: PartialEq + fmt::Debug,
+    {
+        let mut arena = Arena::new();
+        for (seed, input) in crate::geninput::samples(day) {
+            let parsed = S::parse_in(&input, &arena).unwrap();
+            arena.reset();
+            assert_eq!(parsed, S::parse(&input).unwrap(), "day {day}, seed {seed}");
+        }
+        assert!(S::parse(malformed).is_err());
+        assert!(S::parse_in(malformed, &arena).is_err(), "day {day}");
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_parse_in() {
+        use crate::year2021::*;
+        parses_in_arena::<day_03::Day03>(3, "101\n10");
+        parses_in_arena::<day_04::Day04>(4, "1,2\n\n1 2\n3 4\n\n1");
+        parses_in_arena::<day_08::Day08>(8, "ab | cd");
+        parses_in_arena::<day_12::Day12>(12, "start-A\nA end");
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::Answer;
+use crate::fetcher::{self, FetchError, YEAR};
+
+/// How adventofcode.com judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Another answer was submitted too recently. Nothing was judged.
+    RateLimited,
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// The response did not match any known message.
+    Unknown,
+}
+
+impl Verdict {
+    /// Reads the verdict from the page returned after submitting.
+    #[must_use]
+    pub fn from_response(html: &str) -> Self {
+        if html.contains("That's the right answer") {
+            Self::Correct
+        } else if html.contains("That's not the right answer") {
+            if html.contains("too high") {
+                Self::TooHigh
+            } else if html.contains("too low") {
+                Self::TooLow
+            } else {
+                Self::Incorrect
+            }
+        } else if html.contains("You gave an answer too recently") {
+            Self::RateLimited
+        } else if html.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Whether the verdict says anything about the answer itself.
+    #[must_use]
+    pub const fn is_final(self) -> bool {
+        matches!(
+            self,
+            Self::Correct | Self::TooHigh | Self::TooLow | Self::Incorrect
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too high",
+            Self::TooLow => "too low",
+            Self::Incorrect => "incorrect",
+            Self::RateLimited => "rate limited",
+            Self::WrongLevel => "wrong level",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Self::Correct,
+            "too high" => Self::TooHigh,
+            "too low" => Self::TooLow,
+            "incorrect" => Self::Incorrect,
+            "rate limited" => Self::RateLimited,
+            "wrong level" => Self::WrongLevel,
+            "unknown" => Self::Unknown,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// The log of verdicts, next to the cached inputs. Each line holds the day, part, answer and
+/// verdict, separated by tabs.
+#[must_use]
+pub fn verdicts_path(root: &Path) -> PathBuf {
+    root.join(format!("input/{YEAR}/verdicts.tsv"))
+}
+
+/// The final verdict recorded earlier for this exact answer, if any.
+///
+/// # Errors
+///
+/// Returns an error if the log exists but can not be read.
+pub fn previous_verdict(
+    root: &Path,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> io::Result<Option<Verdict>> {
+    let log = match fs::read_to_string(verdicts_path(root)) {
+        Ok(log) => log,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let key = format!("{day}\t{part}\t{answer}\t");
+    Ok(log
+        .lines()
+        .filter_map(|line| line.strip_prefix(&key)?.parse::<Verdict>().ok())
+        .find(|verdict| verdict.is_final()))
+}
+
+/// Appends a verdict to the log.
+///
+/// # Errors
+///
+/// Returns an error if the log can not be written.
+pub fn record_verdict(
+    root: &Path,
+    day: u8,
+    part: u8,
+    answer: &str,
+    verdict: Verdict,
+) -> io::Result<()> {
+    let path = verdicts_path(root);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(log, "{day}\t{part}\t{answer}\t{verdict}")
+}
+
+/// Submits an answer and records the verdict. An answer that already has a final verdict in the
+/// log is not submitted again; the earlier verdict is returned instead.
+///
+/// # Errors
+///
+/// Returns an error if the answer is a picture that can not be read, the request fails, or the
+/// log can not be read or written.
+pub fn submit(
+    root: &Path,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    session: &str,
+) -> Result<Verdict, FetchError> {
+    let answer = &answer.submittable().ok_or(FetchError::UnreadableAnswer)?;
+    if let Some(verdict) = previous_verdict(root, day, part, answer)? {
+        return Ok(verdict);
+    }
+    let response = fetcher::post(
+        &format!("https://adventofcode.com/{YEAR}/day/{day}/answer"),
+        session,
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+    let verdict = Verdict::from_response(&response);
+    record_verdict(root, day, part, answer, verdict)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response() {
+        let wrong = "<p>That's not the right answer; your answer is too low. If you're stuck...";
+        assert_eq!(Verdict::from_response(wrong), Verdict::TooLow);
+        let right = "<p>That's the right answer!  You are one gold star closer...";
+        assert_eq!(Verdict::from_response(right), Verdict::Correct);
+        let limited = "<p>You gave an answer too recently; you have to wait...";
+        assert_eq!(Verdict::from_response(limited), Verdict::RateLimited);
+        assert_eq!(Verdict::from_response("<html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_log() {
+        let root = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        record_verdict(&root, 7, 1, "37", Verdict::RateLimited).unwrap();
+        record_verdict(&root, 7, 1, "37", Verdict::TooHigh).unwrap();
+        record_verdict(&root, 7, 2, "168", Verdict::Correct).unwrap();
+        let high = previous_verdict(&root, 7, 1, "37").unwrap();
+        let other = previous_verdict(&root, 7, 1, "36").unwrap();
+        let correct = submit(&root, 7, 2, &Answer::Int(168), "not a session").unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(high, Some(Verdict::TooHigh));
+        assert_eq!(other, None);
+        assert_eq!(correct, Verdict::Correct);
+    }
+}
@@ -0,0 +1,285 @@
+//! The `aoc tui` dashboard: every day with its answers and timings, solved on the cached inputs.
+
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::fetcher;
+use crate::input;
+use crate::solver::{self, TimedRun};
+use crate::util::render::Backend;
+
+const DAYS: u8 = 25;
+
+const HELP: &str =
+    "↑/↓ select   r re-run day   a re-run all   v show picture   b toggle Braille   q quit";
+
+#[derive(Debug)]
+enum Status {
+    NotImplemented,
+    NoInput,
+    Running,
+    Solved(TimedRun),
+    Failed(String),
+}
+
+fn read_input(day: u8) -> Option<String> {
+    input::read_file(&input::cached_path(Path::new(""), fetcher::YEAR, day)).ok()
+}
+
+fn solve_day(day: u8) -> Status {
+    let Some(solver) = solver::solver(day) else {
+        return Status::NotImplemented;
+    };
+    let Some(input) = read_input(day) else {
+        return Status::NoInput;
+    };
+    match solver.solve_timed(&input, &[1, 2]) {
+        Ok(run) => Status::Solved(run),
+        Err(error) => Status::Failed(error.to_string()),
+    }
+}
+
+fn picture(day: u8, backend: Backend) -> String {
+    let Some(solver) = solver::solver(day) else {
+        return format!("Day {day} is not implemented");
+    };
+    let Some(input) = read_input(day) else {
+        return format!("Day {day} has no cached input");
+    };
+    match solver.visualize(&input, backend) {
+        Ok(Some(picture)) => picture,
+        Ok(None) => format!("Day {day} has no picture"),
+        Err(error) => error.to_string(),
+    }
+}
+
+fn row(day: u8, status: &Status) -> Row<'static> {
+    let (label, color) = match status {
+        Status::NotImplemented => ("not implemented", Color::DarkGray),
+        Status::NoInput => ("no input", Color::DarkGray),
+        Status::Running => ("running", Color::Yellow),
+        Status::Solved(_) => ("solved", Color::Green),
+        Status::Failed(_) => ("failed", Color::Red),
+    };
+    let mut cells = vec![day.to_string(), label.to_string()];
+    match status {
+        Status::Solved(run) => {
+            let part = |i: usize| run.parts.get(i);
+            cells
+                .extend((0..2).map(|i| part(i).map_or_else(String::new, |p| p.answer.to_string())));
+            cells.push(format!("{:.2?}", run.parse.elapsed));
+            cells.push(format!("{:.2?}", run.solve.elapsed));
+        }
+        Status::Failed(error) => cells.push(error.clone()),
+        _ => {}
+    }
+    Row::new(cells).style(Style::new().fg(color))
+}
+
+struct App {
+    statuses: Vec<Status>,
+    table: TableState,
+    /// The picture of a day, while it is shown.
+    picture: Option<(u8, String)>,
+    backend: Backend,
+    jobs: Sender<u8>,
+    results: Receiver<(u8, Status)>,
+}
+
+impl App {
+    /// Starts the worker thread. Days are solved one at a time, so the timings are not skewed by
+    /// other days running at the same time.
+    fn new() -> Self {
+        let (jobs, queue) = mpsc::channel();
+        let (done, results) = mpsc::channel();
+        thread::spawn(move || {
+            for day in queue {
+                if done.send((day, solve_day(day))).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            statuses: (1..=DAYS).map(|_| Status::Running).collect(),
+            table: TableState::default().with_selected(Some(0)),
+            picture: None,
+            backend: Backend::default(),
+            jobs,
+            results,
+        }
+    }
+
+    fn selected_day(&self) -> u8 {
+        self.table
+            .selected()
+            .and_then(|index| u8::try_from(index + 1).ok())
+            .map_or(1, |day| day.min(DAYS))
+    }
+
+    fn rerun(&mut self, day: u8) {
+        self.statuses[usize::from(day - 1)] = Status::Running;
+        // The worker only stops when the app does.
+        let _ = self.jobs.send(day);
+    }
+
+    fn select(&mut self, down: bool) {
+        if down {
+            self.table.select_next();
+        } else {
+            self.table.select_previous();
+        }
+        self.picture = None;
+    }
+
+    fn toggle_picture(&mut self) {
+        let day = self.selected_day();
+        self.picture = match self.picture.take() {
+            Some(_) => None,
+            None => Some((day, picture(day, self.backend))),
+        };
+    }
+
+    /// Switches between half blocks and Braille, redrawing the picture if it is shown.
+    fn toggle_backend(&mut self) {
+        self.backend = match self.backend {
+            Backend::HalfBlocks => Backend::Braille,
+            Backend::Braille | Backend::Plain => Backend::HalfBlocks,
+        };
+        if let Some((day, _)) = self.picture {
+            self.picture = Some((day, picture(day, self.backend)));
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, bottom_area] =
+            Layout::vertical([Constraint::Length(u16::from(DAYS) + 3), Constraint::Min(0)])
+                .areas(frame.area());
+        let header = Row::new(["Day", "Status", "Part 1", "Part 2", "Parse", "Solve"])
+            .style(Style::new().add_modifier(Modifier::BOLD));
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(15),
+            Constraint::Min(10),
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(
+            (1..)
+                .zip(&self.statuses)
+                .map(|(day, status)| row(day, status)),
+            widths,
+        )
+        .header(header)
+        .block(Block::bordered().title(" Advent of Code 2021 "))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+        let (title, text) = self.picture.as_ref().map_or_else(
+            || (" Keys ".to_string(), HELP),
+            |(day, picture)| (format!(" Day {day} "), picture.as_str()),
+        );
+        frame.render_widget(
+            Paragraph::new(text).block(Block::bordered().title(title)),
+            bottom_area,
+        );
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        for day in 1..=DAYS {
+            self.rerun(day);
+        }
+        loop {
+            while let Ok((day, status)) = self.results.try_recv() {
+                self.statuses[usize::from(day - 1)] = status;
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => self.select(true),
+                    KeyCode::Up | KeyCode::Char('k') => self.select(false),
+                    KeyCode::Char('r') => self.rerun(self.selected_day()),
+                    KeyCode::Char('a') => (1..=DAYS).for_each(|day| self.rerun(day)),
+                    KeyCode::Char('v') => self.toggle_picture(),
+                    KeyCode::Char('b') => self.toggle_backend(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Runs the dashboard until the user quits.
+///
+/// # Errors
+///
+/// Returns an error if the terminal can not be drawn to or read from.
+pub fn run() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = App::new().run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::instrument::Measurement;
+    use crate::solver::TimedPart;
+
+    #[test]
+    fn test_draw() {
+        let mut app = App::new();
+        let measurement = Measurement {
+            elapsed: Duration::from_micros(15),
+            peak_bytes: None,
+            allocations: None,
+        };
+        let part = |part, answer: &str| TimedPart {
+            part,
+            answer: answer.into(),
+            solve: None,
+        };
+        app.statuses[0] = Status::Solved(TimedRun {
+            parse: measurement,
+            solve: measurement,
+            parts: vec![part(1, "1766"), part(2, "1797")],
+        });
+        app.statuses[1] = Status::Failed("Syntax error".to_string());
+        app.statuses[2] = Status::NoInput;
+        let mut terminal = Terminal::new(TestBackend::new(100, 32)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let text = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect::<String>();
+        assert!(text.contains("solved"));
+        assert!(text.contains("1766"));
+        assert!(text.contains("15.00µs"));
+        assert!(text.contains("Syntax error"));
+        assert!(text.contains("no input"));
+        assert!(text.contains("q quit"));
+    }
+}
@@ -0,0 +1,31 @@
+//! Pacing for the animations that `aoc viz` draws in the terminal.
+
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+/// How an animation moves on to its next frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pace {
+    /// Plays by itself, with this long between frames.
+    Auto(Duration),
+    /// Waits for Enter before each frame.
+    Step,
+}
+
+impl Pace {
+    /// Waits until the next frame is due.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if standard input can not be read while stepping.
+    pub fn wait(self) -> io::Result<()> {
+        match self {
+            Self::Auto(delay) => thread::sleep(delay),
+            Self::Step => {
+                io::stdin().read_line(&mut String::new())?;
+            }
+        }
+        Ok(())
+    }
+}
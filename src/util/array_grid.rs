@@ -0,0 +1,88 @@
+use std::ops::{Index, IndexMut};
+
+/// A `W`×`H` grid stored inline, for puzzles whose dimensions are known up front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayGrid<T, const W: usize, const H: usize> {
+    rows: [[T; W]; H],
+}
+
+impl<T: Copy + Default, const W: usize, const H: usize> ArrayGrid<T, W, H> {
+    #[must_use]
+    pub const fn from_rows(rows: [[T; W]; H]) -> Self {
+        Self { rows }
+    }
+
+    /// Builds a grid from row-major cells, or `None` if there aren't exactly `W * H` of them.
+    #[must_use]
+    pub fn try_from_slice(cells: &[T]) -> Option<Self> {
+        if cells.len() != W * H {
+            return None;
+        }
+        let mut rows = [[T::default(); W]; H];
+        for (row, chunk) in rows.iter_mut().zip(cells.chunks_exact(W)) {
+            row.copy_from_slice(chunk);
+        }
+        Some(Self { rows })
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T; W]> {
+        self.rows.iter()
+    }
+
+    /// Positions of the up to 8 cells surrounding `[row, col]`.
+    pub fn neighbors8(
+        &self,
+        [row, col]: [usize; 2],
+    ) -> impl Iterator<Item = [usize; 2]> + use<T, W, H> {
+        (row.saturating_sub(1)..(row + 2).min(H))
+            .flat_map(move |r| (col.saturating_sub(1)..(col + 2).min(W)).map(move |c| [r, c]))
+            .filter(move |&pos| pos != [row, col])
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<[usize; 2]> for ArrayGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, [row, col]: [usize; 2]) -> &Self::Output {
+        &self.rows[row][col]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<[usize; 2]> for ArrayGrid<T, W, H> {
+    fn index_mut(&mut self, [row, col]: [usize; 2]) -> &mut Self::Output {
+        &mut self.rows[row][col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_slice() {
+        let grid = ArrayGrid::<u8, 3, 2>::try_from_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(grid, ArrayGrid::from_rows([[1, 2, 3], [4, 5, 6]]));
+        assert_eq!(grid[[1, 0]], 4);
+        assert!(ArrayGrid::<u8, 3, 2>::try_from_slice(&[1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let grid = ArrayGrid::<u8, 3, 3>::from_rows([[0; 3]; 3]);
+        assert_eq!(grid.neighbors8([1, 1]).count(), 8);
+        assert_eq!(
+            grid.neighbors8([0, 2]).collect::<Vec<_>>(),
+            [[0, 1], [1, 1], [1, 2]]
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// A set of cells on a `width`×`height` grid of at most 128 cells, one bit per cell in row-major
+/// order. The size is set when the board is made, so it can come from the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitBoard {
+    bits: u128,
+    width: usize,
+    height: usize,
+}
+
+impl BitBoard {
+    /// The most cells a board can have.
+    pub const MAX_CELLS: usize = 128;
+
+    /// An empty `width`×`height` board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board would have more than [`Self::MAX_CELLS`] cells.
+    #[must_use]
+    pub const fn empty(width: usize, height: usize) -> Self {
+        assert!(
+            width * height <= Self::MAX_CELLS,
+            "BitBoard is limited to 128 cells"
+        );
+        Self {
+            bits: 0,
+            width,
+            height,
+        }
+    }
+
+    /// A `width`×`height` board with every cell in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board would have more than [`Self::MAX_CELLS`] cells.
+    #[must_use]
+    pub const fn full(width: usize, height: usize) -> Self {
+        Self::empty(width, height).with_bits(u128::MAX)
+    }
+
+    /// A `width`×`height` board with the cells of `bits`, leaving out any past the last cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board would have more than [`Self::MAX_CELLS`] cells.
+    #[must_use]
+    pub const fn from_bits(width: usize, height: usize, bits: u128) -> Self {
+        Self::empty(width, height).with_bits(bits)
+    }
+
+    /// A board the size of this one, with the cells of `bits` that fit on it.
+    const fn with_bits(self, bits: u128) -> Self {
+        let cells = self.cells();
+        let mask = if cells == Self::MAX_CELLS {
+            u128::MAX
+        } else {
+            (1 << cells) - 1
+        };
+        Self {
+            bits: bits & mask,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn bits(self) -> u128 {
+        self.bits
+    }
+
+    #[must_use]
+    pub const fn width(self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(self) -> usize {
+        self.height
+    }
+
+    /// The number of cells on the board, in the set or not.
+    #[must_use]
+    pub const fn cells(self) -> usize {
+        self.width * self.height
+    }
+
+    /// Row `y` of a board the size of this one.
+    #[must_use]
+    pub const fn row(self, y: usize) -> Self {
+        self.with_bits(shl(!shl(u128::MAX, self.width), y * self.width))
+    }
+
+    /// Column `x` of a board the size of this one.
+    #[must_use]
+    pub const fn column(self, x: usize) -> Self {
+        let mut bits = 0;
+        let mut y = 0;
+        while y < self.height {
+            bits |= 1 << (y * self.width + x);
+            y += 1;
+        }
+        self.with_bits(bits)
+    }
+
+    /// The cells `(i, i)` for `i` in `0..min(width, height)`, of a board the size of this one.
+    #[must_use]
+    pub const fn diagonal(self) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < self.width && i < self.height {
+            bits |= 1 << (i * self.width + i);
+            i += 1;
+        }
+        self.with_bits(bits)
+    }
+
+    /// The cells `(width - 1 - i, i)` for `i` in `0..min(width, height)`, of a board the size of
+    /// this one.
+    #[must_use]
+    pub const fn anti_diagonal(self) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < self.width && i < self.height {
+            bits |= 1 << (i * self.width + self.width - 1 - i);
+            i += 1;
+        }
+        self.with_bits(bits)
+    }
+
+    #[must_use]
+    pub const fn contains(self, x: usize, y: usize) -> bool {
+        self.contains_index(y * self.width + x)
+    }
+
+    #[must_use]
+    pub const fn contains_index(self, index: usize) -> bool {
+        self.bits & (1 << index) != 0
+    }
+
+    pub const fn insert(&mut self, x: usize, y: usize) {
+        self.insert_index(y * self.width + x);
+    }
+
+    pub const fn insert_index(&mut self, index: usize) {
+        self.bits |= 1 << index;
+    }
+
+    pub const fn remove_index(&mut self, index: usize) {
+        self.bits &= !(1 << index);
+    }
+
+    #[must_use]
+    pub const fn count(self) -> u32 {
+        self.bits.count_ones()
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    #[must_use]
+    pub const fn is_superset(self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    #[must_use]
+    pub const fn is_row_full(self, y: usize) -> bool {
+        self.is_superset(self.row(y))
+    }
+
+    #[must_use]
+    pub const fn is_column_full(self, x: usize) -> bool {
+        self.is_superset(self.column(x))
+    }
+
+    /// Whether any row is full. The first row's mask is shifted down, rather than each row's
+    /// made anew.
+    #[must_use]
+    pub fn has_full_row(self) -> bool {
+        let first = self.row(0).bits;
+        (0..self.height).any(|y| {
+            let row = first << (y * self.width);
+            self.bits & row == row
+        })
+    }
+
+    /// Whether any column is full. The first column's mask is shifted across, rather than each
+    /// column's made anew.
+    #[must_use]
+    pub fn has_full_column(self) -> bool {
+        let first = self.column(0).bits;
+        (0..self.width).any(|x| {
+            let column = first << x;
+            self.bits & column == column
+        })
+    }
+
+    /// Every cell moved one step towards `x = 0`; cells in the first column fall off.
+    #[must_use]
+    pub const fn shift_west(self) -> Self {
+        self.with_bits((self.bits >> 1) & !self.column(self.width - 1).bits)
+    }
+
+    /// Every cell moved one step towards `x = width - 1`; cells in the last column fall off.
+    #[must_use]
+    pub const fn shift_east(self) -> Self {
+        self.with_bits((self.bits << 1) & !self.column(0).bits)
+    }
+
+    /// Every cell moved one step towards `y = 0`; cells in the first row fall off.
+    #[must_use]
+    pub const fn shift_north(self) -> Self {
+        self.with_bits(shr(self.bits, self.width))
+    }
+
+    /// Every cell moved one step towards `y = height - 1`; cells in the last row fall off.
+    #[must_use]
+    pub const fn shift_south(self) -> Self {
+        self.with_bits(shl(self.bits, self.width))
+    }
+
+    /// Cells orthogonally adjacent to any cell in the set.
+    #[must_use]
+    pub const fn neighbors4(self) -> Self {
+        self.with_bits(
+            self.shift_west().bits
+                | self.shift_east().bits
+                | self.shift_north().bits
+                | self.shift_south().bits,
+        )
+    }
+
+    /// Cells orthogonally or diagonally adjacent to any cell in the set.
+    #[must_use]
+    pub const fn neighbors8(self) -> Self {
+        let sideways = self.shift_west().bits | self.shift_east().bits;
+        let row = self.with_bits(self.bits | sideways);
+        self.with_bits(sideways | row.shift_north().bits | row.shift_south().bits)
+    }
+
+    /// Indices of the cells in the set, in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+}
+
+/// `bits << n`, or no bits if they are all shifted out.
+const fn shl(bits: u128, n: usize) -> u128 {
+    if n >= BitBoard::MAX_CELLS {
+        0
+    } else {
+        bits << n
+    }
+}
+
+/// `bits >> n`, or no bits if they are all shifted out.
+const fn shr(bits: u128, n: usize) -> u128 {
+    if n >= BitBoard::MAX_CELLS {
+        0
+    } else {
+        bits >> n
+    }
+}
+
+/// The operators keep the size of the left-hand board, which should match the right.
+impl BitAnd for BitBoard {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        debug_assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        self.with_bits(self.bits & rhs.bits)
+    }
+}
+
+impl BitOr for BitBoard {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        debug_assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        self.with_bits(self.bits | rhs.bits)
+    }
+}
+
+impl BitXor for BitBoard {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        debug_assert_eq!((self.width, self.height), (rhs.width, rhs.height));
+        self.with_bits(self.bits ^ rhs.bits)
+    }
+}
+
+impl Not for BitBoard {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.with_bits(!self.bits)
+    }
+}
+
+#[cfg(all(kani, feature = "verification"))]
+mod verification {
+    use super::*;
+
+    /// A board of any size that day 4 plays on, from 1×1 to 11×11, with any cells marked.
+    fn any_bingo_board() -> BitBoard {
+        let size: usize = kani::any();
+        kani::assume((1..=11).contains(&size));
+        BitBoard::from_bits(size, size, kani::any())
+    }
+
+    #[kani::proof]
+    fn insert_then_contains() {
+        let mut board = any_bingo_board();
+        let index: usize = kani::any();
+        kani::assume(index < board.cells());
+        board.insert_index(index);
+        assert!(board.contains_index(index));
+        board.remove_index(index);
+        assert!(!board.contains_index(index));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn bingo_matches_cells() {
+        let board = any_bingo_board();
+        let size = board.width();
+        let full_row = (0..size).any(|y| (0..size).all(|x| board.contains(x, y)));
+        let full_column = (0..size).any(|x| (0..size).all(|y| board.contains(x, y)));
+        assert_eq!(board.has_full_row(), full_row);
+        assert_eq!(board.has_full_column(), full_column);
+    }
+
+    /// Day 4 checks only the row and column of the number it marks. A board that had no bingo
+    /// has one after the mark exactly when that row or column is full.
+    #[kani::proof]
+    #[kani::unwind(12)]
+    fn marked_line_finds_new_bingo() {
+        let mut board = any_bingo_board();
+        kani::assume(!board.has_full_row() && !board.has_full_column());
+        let index: usize = kani::any();
+        kani::assume(index < board.cells());
+        board.insert_index(index);
+        let size = board.width();
+        let completes = board.is_row_full(index / size) || board.is_column_full(index % size);
+        assert_eq!(completes, board.has_full_row() || board.has_full_column());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: BitBoard = BitBoard::empty(5, 5);
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(BOARD.row(1).bits(), 0b11111_00000);
+        assert_eq!(BOARD.column(0).bits(), 0b00001_00001_00001_00001_00001);
+        assert_eq!(BOARD.diagonal().bits(), 0b10000_01000_00100_00010_00001);
+        assert_eq!(
+            BOARD.anti_diagonal().bits(),
+            0b00001_00010_00100_01000_10000
+        );
+        assert_eq!(BitBoard::full(5, 5).count(), 25);
+        assert_eq!(BitBoard::full(16, 8).bits(), u128::MAX);
+        assert_eq!(BitBoard::empty(3, 3).row(2).bits(), 0b111_000_000);
+        assert_eq!(BitBoard::empty(3, 3).column(2).bits(), 0b100_100_100);
+        assert_eq!(BitBoard::from_bits(2, 2, u128::MAX).bits(), 0b1111);
+        assert_eq!(BitBoard::empty(128, 1).row(0).bits(), u128::MAX);
+        assert!(BitBoard::full(128, 1).shift_south().is_empty());
+    }
+
+    #[test]
+    fn test_full_lines() {
+        let mut board = BOARD;
+        for x in 0..4 {
+            board.insert(x, 2);
+        }
+        assert!(!board.has_full_row());
+        board.insert(4, 2);
+        assert!(board.has_full_row());
+        assert!(board.is_row_full(2));
+        assert!(!board.has_full_column());
+        assert!((board | board.column(3)).has_full_column());
+        assert!((board | board.column(3)).is_column_full(3));
+        let mut board = BitBoard::empty(7, 7);
+        for y in 0..7 {
+            board.insert(6, y);
+        }
+        assert!(board.is_column_full(6));
+        assert!(!board.has_full_row());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut corner = BOARD;
+        corner.insert(4, 0);
+        assert_eq!(corner.neighbors4().iter().collect::<Vec<_>>(), [3, 9]);
+        assert_eq!(corner.neighbors8().iter().collect::<Vec<_>>(), [3, 8, 9]);
+        let mut center = BOARD;
+        center.insert(2, 2);
+        assert_eq!(center.neighbors8().count(), 8);
+        assert_eq!(center.neighbors4().count(), 4);
+        assert_eq!(!BOARD.row(0), BitBoard::full(5, 5) ^ BOARD.row(0));
+    }
+}
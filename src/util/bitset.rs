@@ -0,0 +1,126 @@
+//! A fixed-size set of small integers, with one bit for each.
+
+/// A set of the integers in `0..64 * N`, stored as `N` words of bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const N: usize>([u64; N]);
+
+impl<const N: usize> BitSet<N> {
+    /// One more than the largest integer the set can hold.
+    pub const CAPACITY: usize = 64 * N;
+
+    pub const EMPTY: Self = Self([0; N]);
+
+    /// Whether `index` is in the set. Integers past the capacity never are.
+    #[must_use]
+    pub const fn contains(&self, index: usize) -> bool {
+        index < Self::CAPACITY && self.0[index / 64] & 1 << (index % 64) != 0
+    }
+
+    /// Adds `index` to the set, and returns whether it was not already there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below `CAPACITY`.
+    pub const fn insert(&mut self, index: usize) -> bool {
+        let added = !self.contains(index);
+        self.0[index / 64] |= 1 << (index % 64);
+        added
+    }
+
+    /// Removes `index` from the set, and returns whether it was there.
+    pub const fn remove(&mut self, index: usize) -> bool {
+        let removed = self.contains(index);
+        if removed {
+            self.0[index / 64] &= !(1 << (index % 64));
+        }
+        removed
+    }
+
+    /// A copy of the set with `index` added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below `CAPACITY`.
+    #[must_use]
+    pub const fn with(mut self, index: usize) -> Self {
+        self.insert(index);
+        self
+    }
+
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        let mut count = 0;
+        let mut word = 0;
+        while word < N {
+            count += self.0[word].count_ones();
+            word += 1;
+        }
+        count
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// The integers in the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(word, &bits)| {
+            let mut bits = bits;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let index = word * 64 + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(index)
+            })
+        })
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, Self::with)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = BitSet::<2>::EMPTY;
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(100));
+        assert!(!set.insert(3));
+        assert!(set.contains(100));
+        assert!(!set.contains(99));
+        assert!(!set.contains(BitSet::<2>::CAPACITY));
+        assert_eq!(set.count(), 2);
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.count(), 1);
+    }
+
+    #[test]
+    fn test_iter() {
+        let set = [127, 0, 64, 63, 5].into_iter().collect::<BitSet<2>>();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 5, 63, 64, 127]);
+        assert_eq!(BitSet::<3>::EMPTY.iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_insert_out_of_range() {
+        let _ = BitSet::<1>::EMPTY.with(64);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::ops::{AddAssign, Index, IndexMut};
+
+pub trait Count: Copy + Ord + Default + Debug + AddAssign + Sum {
+    const ONE: Self;
+
+    #[must_use]
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    #[must_use]
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_count {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_count!(u8, u16, u32, u64, u128, usize);
+
+/// Counts occurrences of keys that map to a small, dense range of indices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DenseCounter<K, V> {
+    counts: Vec<V>,
+    key: PhantomData<fn(K) -> usize>,
+}
+
+impl<K: Into<usize>, V: Count> DenseCounter<K, V> {
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            counts: vec![V::default(); len],
+            key: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    #[must_use]
+    pub fn get(&self, key: K) -> V {
+        self.counts[key.into()]
+    }
+
+    pub fn increment(&mut self, key: K) {
+        self.add(key, V::ONE);
+    }
+
+    pub fn add(&mut self, key: K, amount: V) {
+        self.counts[key.into()] += amount;
+    }
+
+    pub fn saturating_increment(&mut self, key: K) {
+        self.saturating_add(key, V::ONE);
+    }
+
+    pub fn saturating_add(&mut self, key: K, amount: V) {
+        let count = &mut self.counts[key.into()];
+        *count = count.saturating_add(amount);
+    }
+
+    /// Returns the new count, or `None` (leaving the count unchanged) on overflow.
+    pub fn checked_increment(&mut self, key: K) -> Option<V> {
+        self.checked_add(key, V::ONE)
+    }
+
+    /// Returns the new count, or `None` (leaving the count unchanged) on overflow.
+    pub fn checked_add(&mut self, key: K, amount: V) -> Option<V> {
+        let count = &mut self.counts[key.into()];
+        *count = count.checked_add(amount)?;
+        Some(*count)
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.fill(V::default());
+    }
+
+    #[must_use]
+    pub fn total(&self) -> V {
+        self.counts.iter().copied().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, V)> + '_ {
+        self.counts.iter().copied().enumerate()
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[V] {
+        &self.counts
+    }
+
+    /// The `k` highest non-zero counts, highest first.
+    #[must_use]
+    pub fn most_common(&self, k: usize) -> Vec<(usize, V)> {
+        let mut entries = self.non_zero();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        entries.truncate(k);
+        entries
+    }
+
+    /// The `k` lowest non-zero counts, lowest first.
+    #[must_use]
+    pub fn least_common(&self, k: usize) -> Vec<(usize, V)> {
+        let mut entries = self.non_zero();
+        entries.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+        entries.truncate(k);
+        entries
+    }
+
+    fn non_zero(&self) -> Vec<(usize, V)> {
+        self.iter().filter(|&(_, v)| v != V::default()).collect()
+    }
+}
+
+impl<K: Into<usize>, V> Index<K> for DenseCounter<K, V> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &Self::Output {
+        &self.counts[key.into()]
+    }
+}
+
+impl<K: Into<usize>, V> IndexMut<K> for DenseCounter<K, V> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        &mut self.counts[key.into()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_increment() {
+        let mut counter = DenseCounter::<u8, u32>::new(4);
+        for key in [1, 3, 1, 0, 1] {
+            counter.increment(key);
+        }
+        assert_eq!(counter.as_slice(), [1, 3, 0, 1]);
+        assert_eq!(counter.total(), 5);
+        assert_eq!(counter[1], 3);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut counter = DenseCounter::<usize, u8>::new(1);
+        counter.add(0, 254);
+        assert_eq!(counter.checked_increment(0), Some(255));
+        assert_eq!(counter.checked_increment(0), None);
+        assert_eq!(counter.get(0), 255);
+        counter.saturating_add(0, 10);
+        assert_eq!(counter.get(0), 255);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let mut counter = DenseCounter::<usize, u64>::new(5);
+        counter.add(0, 4);
+        counter.add(2, 9);
+        counter.add(3, 1);
+        counter.add(4, 4);
+        assert_eq!(counter.most_common(2), [(2, 9), (0, 4)]);
+        assert_eq!(counter.least_common(2), [(3, 1), (0, 4)]);
+        assert_eq!(counter.most_common(10).len(), 4);
+    }
+}
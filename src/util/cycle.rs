@@ -0,0 +1,113 @@
+/// Brent's cycle detection over the states reached by repeatedly applying `step` to `start`,
+/// comparing states by `key`.
+///
+/// Returns `(prefix length, cycle length)`, or `None` if no state repeats within `limit`
+/// steps.
+pub fn find_cycle<S: Clone, K: PartialEq>(
+    start: &S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+) -> Option<(usize, usize)> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(start);
+    let mut hare = start.clone();
+    step(&mut hare);
+    let mut steps = 1;
+    while tortoise != key(&hare) {
+        if steps >= limit {
+            return None;
+        }
+        if power == length {
+            tortoise = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+        steps += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut prefix = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        prefix += 1;
+    }
+    Some((prefix, length))
+}
+
+/// Like [`find_cycle`], over the items of an iterator. Each item is taken to determine all the
+/// items after it.
+pub fn find_cycle_in<I>(iter: I, limit: usize) -> Option<(usize, usize)>
+where
+    I: Iterator + Clone,
+    I::Item: Clone + PartialEq,
+{
+    let mut iter = iter;
+    let first = iter.next();
+    find_cycle(
+        &(iter, first),
+        limit,
+        |(iter, item)| *item = iter.next(),
+        |(_, item)| item.clone(),
+    )
+}
+
+/// Returns the state after `steps` applications of `step`, skipping whole cycles if one is
+/// found within `limit` steps.
+pub fn fast_forward<S: Clone, K: PartialEq>(
+    start: S,
+    steps: usize,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl Fn(&S) -> K,
+) -> S {
+    let remaining = match find_cycle(&start, limit.min(steps), &mut step, key) {
+        Some((prefix, length)) if steps > prefix => prefix + (steps - prefix) % length,
+        _ => steps,
+    };
+    let mut state = start;
+    for _ in 0..remaining {
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &mut u64) {
+        *x = (*x * *x + 1) % 255;
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+        assert_eq!(find_cycle(&3, 100, next, |&x| x), Some((2, 6)));
+        assert_eq!(find_cycle(&3, 5, next, |&x| x), None);
+    }
+
+    #[test]
+    fn test_find_cycle_in() {
+        let sequence = [5, 6, 1, 2, 1, 2, 1, 2, 1, 2];
+        assert_eq!(find_cycle_in(sequence.into_iter(), 100), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let mut expected = 3;
+        for _ in 0..1_000_003 {
+            next(&mut expected);
+        }
+        assert_eq!(fast_forward(3, 1_000_003, 1_000, next, |&x| x), expected);
+        assert_eq!(fast_forward(3, 1, 1_000, next, |&x| x), 10);
+    }
+}
@@ -0,0 +1,145 @@
+//! Saves grids as PNG images and GIF animations, with every cell drawn as a square of pixels.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageResult, Rgb, RgbImage, Rgba, RgbaImage};
+
+use crate::util::grid::Grid;
+
+/// The side of the square drawn for each cell, unless a grid is too large for it.
+pub const CELL_SIZE: u32 = 8;
+
+/// The colors of unlit and lit cells in `bool` grids.
+pub const DARK: Rgb<u8> = Rgb([0x0f, 0x0f, 0x23]);
+pub const LIGHT: Rgb<u8> = Rgb([0xff, 0xff, 0x66]);
+
+/// Draws the grid with the color of each cell, as a square of `cell_size` pixels.
+///
+/// # Panics
+///
+/// Panics if the grid is too large for an image.
+pub fn draw<T>(grid: &Grid<T>, cell_size: u32, mut color: impl FnMut(&T) -> Rgb<u8>) -> RgbImage {
+    let size = |cells: usize| u32::try_from(cells).expect("grid is too large") * cell_size;
+    let mut image = RgbImage::new(size(grid.width()), size(grid.height()));
+    let width = grid.width().max(1);
+    for (index, cell) in grid.as_slice().iter().enumerate() {
+        let (row, col) = (index / width, index % width);
+        let color = color(cell);
+        for y in 0..cell_size {
+            for x in 0..cell_size {
+                image.put_pixel(size(col) + x, size(row) + y, color);
+            }
+        }
+    }
+    image
+}
+
+/// Saves a `bool` grid as a PNG file, with lit cells in `LIGHT` and unlit ones in `DARK`.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+pub fn save_bool_png(path: &Path, grid: &Grid<bool>) -> ImageResult<()> {
+    save_png(path, grid, CELL_SIZE, |&lit| if lit { LIGHT } else { DARK })
+}
+
+/// Saves the grid as a PNG file, with each cell as a square of `cell_size` pixels.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+pub fn save_png<T>(
+    path: &Path,
+    grid: &Grid<T>,
+    cell_size: u32,
+    color: impl FnMut(&T) -> Rgb<u8>,
+) -> ImageResult<()> {
+    draw(grid, cell_size, color).save_with_format(path, image::ImageFormat::Png)
+}
+
+/// Saves the grids as the frames of a looping GIF animation, `delay_ms` milliseconds apart.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+pub fn save_gif<T>(
+    path: &Path,
+    frames: impl IntoIterator<Item = Grid<T>>,
+    delay_ms: u32,
+    mut color: impl FnMut(&T) -> Rgb<u8>,
+) -> ImageResult<()> {
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    let delay = Delay::from_numer_denom_ms(delay_ms, 1);
+    for grid in frames {
+        let image = draw(&grid, CELL_SIZE, &mut color);
+        let rgba = RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+            let Rgb([r, g, b]) = *image.get_pixel(x, y);
+            Rgba([r, g, b, 0xff])
+        });
+        encoder.encode_frame(Frame::from_parts(rgba, 0, 0, delay))?;
+    }
+    Ok(())
+}
+
+/// A color on a gradient from `from` to `to`, at `num / den` of the way.
+#[must_use]
+pub fn blend(from: Rgb<u8>, to: Rgb<u8>, num: u8, den: u8) -> Rgb<u8> {
+    let mix = |a: u8, b: u8| {
+        let (a, b, num, den) = (
+            u16::from(a),
+            u16::from(b),
+            u16::from(num),
+            u16::from(den.max(1)),
+        );
+        u8::try_from((a * (den - num.min(den)) + b * num.min(den)) / den).unwrap_or(u8::MAX)
+    };
+    Rgb([
+        mix(from[0], to[0]),
+        mix(from[1], to[1]),
+        mix(from[2], to[2]),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw() {
+        let grid = Grid::new(vec![true, false], 2, 1);
+        let image = draw(&grid, CELL_SIZE, |&lit| if lit { LIGHT } else { DARK });
+        assert_eq!(image.dimensions(), (2 * CELL_SIZE, CELL_SIZE));
+        assert_eq!(*image.get_pixel(0, 0), LIGHT);
+        assert_eq!(*image.get_pixel(CELL_SIZE, CELL_SIZE - 1), DARK);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir();
+        let grid = Grid::new(vec![true, false, false, true], 2, 2);
+        let png = dir.join(format!("aoc2021-export-{}.png", std::process::id()));
+        save_bool_png(&png, &grid).unwrap();
+        let gif = dir.join(format!("aoc2021-export-{}.gif", std::process::id()));
+        save_gif(&gif, [grid.clone(), grid], 100, |&lit| {
+            if lit { LIGHT } else { DARK }
+        })
+        .unwrap();
+        assert_eq!(&std::fs::read(&png).unwrap()[1..4], b"PNG");
+        assert_eq!(&std::fs::read(&gif).unwrap()[..6], b"GIF89a");
+        std::fs::remove_file(png).unwrap();
+        std::fs::remove_file(gif).unwrap();
+    }
+
+    #[test]
+    fn test_blend() {
+        let black = Rgb([0, 0, 0]);
+        let white = Rgb([200, 100, 0]);
+        assert_eq!(blend(black, white, 0, 4), black);
+        assert_eq!(blend(black, white, 1, 4), Rgb([50, 25, 0]));
+        assert_eq!(blend(black, white, 4, 4), white);
+    }
+}
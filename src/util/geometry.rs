@@ -0,0 +1,281 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+pub trait Coordinate:
+    Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+fn abs_diff<T: Coordinate>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+fn signum<T: Coordinate + Neg<Output = T>>(value: T) -> T {
+    match value.cmp(&T::ZERO) {
+        Ordering::Less => -T::ONE,
+        Ordering::Equal => T::ZERO,
+        Ordering::Greater => T::ONE,
+    }
+}
+
+/// One unit from `from` towards `to`, or no step if they are equal.
+fn step<T: Coordinate>(from: T, to: T) -> T {
+    match from.cmp(&to) {
+        Ordering::Less => from + T::ONE,
+        Ordering::Equal => from,
+        Ordering::Greater => from - T::ONE,
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+/// A point, or a displacement, in the plane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+pub type Vec2<T> = Point<T>;
+
+impl<T> Point<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Coordinate> Point<T> {
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The number of king moves between the points.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// Moves one unit along each axis where `self` and `target` differ.
+    #[must_use]
+    pub fn step_towards(self, target: Self) -> Self {
+        Self {
+            x: step(self.x, target.x),
+            y: step(self.y, target.y),
+        }
+    }
+
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        }
+    }
+
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Point<T> {
+    /// The direction of the vector, with each component reduced to -1, 0 or 1.
+    #[must_use]
+    pub fn signum(self) -> Self {
+        Self {
+            x: signum(self.x),
+            y: signum(self.y),
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Parses `x,y`.
+impl<T: FromStr<Err = ParseIntError>> FromStr for Point<T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(',').ok_or(ParsePointError::SyntaxError)?;
+        Ok(Self {
+            x: x.parse()?,
+            y: y.parse()?,
+        })
+    }
+}
+
+/// The smallest axis-aligned rectangle containing a set of points, with inclusive corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    /// Returns `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut result = Self {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            result.include(point);
+        }
+        Some(result)
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn include(&mut self, point: Point<T>) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    #[must_use]
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3_i32, -4);
+        let b = Point::new(-1, 2);
+        assert_eq!(a + b, Point::new(2, -2));
+        assert_eq!(a - b, Point::new(4, -6));
+        assert_eq!(a * 2, Point::new(6, -8));
+        assert_eq!(-a, Point::new(-3, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!((b - a).signum(), Point::new(-1, 1));
+        assert_eq!("12,-7".parse(), Ok(Point::new(12_i32, -7)));
+        assert_eq!(
+            "12".parse::<Point<i32>>(),
+            Err(ParsePointError::SyntaxError)
+        );
+    }
+
+    #[test]
+    fn test_step_towards() {
+        let target = Point::new(2_u16, 5);
+        let path = std::iter::successors(Some(Point::new(0, 3)), |&p| {
+            (p != target).then(|| p.step_towards(target))
+        });
+        assert_eq!(path.count(), 3);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [Point::new(3, 1), Point::new(-2, 4), Point::new(0, 0)];
+        let bounds = BoundingBox::from_points(points).unwrap();
+        assert_eq!(bounds.min, Point::new(-2, 0));
+        assert_eq!(bounds.max, Point::new(3, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 5));
+        assert!(bounds.contains(Point::new(0, 4)));
+        assert!(!bounds.contains(Point::new(4, 0)));
+        assert_eq!(BoundingBox::<i32>::from_points([]), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A directed graph over interned node names, keeping both adjacency lists and, while there
+/// are at most 64 nodes, adjacency bitmasks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graph {
+    names: Vec<String>,
+    lookup: HashMap<String, usize>,
+    adjacency: Vec<Vec<usize>>,
+    masks: Vec<u64>,
+}
+
+impl Graph {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty graph with room for `nodes` nodes before it has to grow.
+    #[must_use]
+    pub fn with_capacity(nodes: usize) -> Self {
+        Self {
+            names: Vec::with_capacity(nodes),
+            lookup: HashMap::with_capacity(nodes),
+            adjacency: Vec::with_capacity(nodes),
+            masks: Vec::with_capacity(nodes),
+        }
+    }
+
+    /// Returns the index of the node called `name`, adding it if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&node) = self.lookup.get(name) {
+            return node;
+        }
+        let node = self.names.len();
+        self.names.push(name.to_owned());
+        self.lookup.insert(name.to_owned(), node);
+        self.adjacency.push(Vec::new());
+        self.masks.push(0);
+        node
+    }
+
+    #[must_use]
+    pub fn node(&self, name: &str) -> Option<usize> {
+        self.lookup.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.names.iter().map(String::as_str).enumerate()
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Adds the edge `from -> to`, unless it already exists.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        if self.adjacency[from].contains(&to) {
+            return;
+        }
+        self.adjacency[from].push(to);
+        if to < 64 {
+            self.masks[from] |= 1 << to;
+        }
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize) {
+        self.add_edge(a, b);
+        self.add_edge(b, a);
+    }
+
+    #[must_use]
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.adjacency[from].contains(&to)
+    }
+
+    #[must_use]
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.adjacency[node]
+    }
+
+    #[must_use]
+    pub fn degree(&self, node: usize) -> usize {
+        self.adjacency[node].len()
+    }
+
+    /// The neighbors of `node` as a bitmask, or `None` if the graph has more than 64 nodes.
+    #[must_use]
+    pub fn neighbor_mask(&self, node: usize) -> Option<u64> {
+        (self.len() <= 64).then(|| self.masks[node])
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (from, name) in self.nodes() {
+            if self.adjacency[from].is_empty() {
+                writeln!(dot, "    {name:?};").unwrap();
+            }
+            for &to in &self.adjacency[from] {
+                writeln!(dot, "    {name:?} -> {:?};", self.names[to]).unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut graph = Graph::new();
+        let a = graph.intern("a");
+        let b = graph.intern("b");
+        assert_eq!(graph.intern("a"), a);
+        assert_eq!(graph.node("b"), Some(b));
+        assert_eq!(graph.node("c"), None);
+        assert_eq!(graph.name(b), "b");
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut graph = Graph::new();
+        let [a, b, c] = ["a", "b", "c"].map(|name| graph.intern(name));
+        graph.add_undirected_edge(a, b);
+        graph.add_edge(a, c);
+        graph.add_edge(a, c);
+        assert_eq!(graph.neighbors(a), [b, c]);
+        assert_eq!(graph.degree(b), 1);
+        assert!(graph.has_edge(b, a));
+        assert!(!graph.has_edge(c, a));
+        assert_eq!(graph.neighbor_mask(a), Some(0b110));
+        for i in 0..64 {
+            graph.intern(&i.to_string());
+        }
+        assert_eq!(graph.neighbor_mask(a), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::new();
+        let [a, b] = ["start", "end"].map(|name| graph.intern(name));
+        graph.add_edge(a, b);
+        graph.intern("lonely");
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    \"start\" -> \"end\";\n    \"end\";\n    \"lonely\";\n}"
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt::{self, Display, Write};
+use std::ops::{Index, IndexMut};
+
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    #[error("Rows have different lengths")]
+    RaggedRows,
+    #[error("The grid has no cells")]
+    Empty,
+}
+
+/// What a stencil sees of the cells past the edges of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border<T> {
+    /// Every cell outside the grid holds the same value.
+    Constant(T),
+    /// The nearest cell on the edge is repeated.
+    Clamp,
+    /// The grid repeats, so that the last row is above the first.
+    Wrap,
+}
+
+/// The cells around one cell of a grid, as seen by [`Grid::stencil`].
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'a, T> {
+    grid: &'a Grid<T>,
+    border: &'a Border<T>,
+    center: [isize; 2],
+}
+
+impl<T: Clone> Window<'_, T> {
+    /// The cell `rows` down and `cols` right of the center, which may lie past the edges.
+    #[must_use]
+    pub fn get(&self, rows: isize, cols: isize) -> T {
+        let [row, col] = self.center;
+        self.grid
+            .get_or_border([row + rows, col + cols], self.border)
+    }
+}
+
+/// A rectangular grid stored row by row, indexed by `[row, col]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// # Panics
+    ///
+    /// Panics if `data` does not hold exactly `width * height` cells.
+    #[must_use]
+    pub fn new(data: Vec<T>, width: usize, height: usize) -> Self {
+        assert_eq!(width * height, data.len());
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    #[must_use]
+    pub fn pos_to_index(&self, [row, col]: [usize; 2]) -> Option<usize> {
+        (col < self.width && row < self.height).then_some(self.width * row + col)
+    }
+
+    #[must_use]
+    pub fn index_to_pos(&self, index: usize) -> Option<[usize; 2]> {
+        (index < self.data.len()).then(|| [index / self.width, index % self.width])
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: [usize; 2]) -> Option<&T> {
+        self.pos_to_index(pos).map(|index| &self.data[index])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: [usize; 2]) -> Option<&mut T> {
+        self.pos_to_index(pos).map(|index| &mut self.data[index])
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `row` is out of range.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "Row out of range");
+        &self.data[row * self.width..(row + 1) * self.width]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `col` is out of range.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column out of range");
+        self.data.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // An empty grid has no rows, whatever its width.
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The `height` rows and `width` columns starting at `[row, col]`, cut short at the edges.
+    #[must_use]
+    pub fn crop(&self, [row, col]: [usize; 2], width: usize, height: usize) -> Self
+    where
+        T: Clone,
+    {
+        let rows = row.min(self.height)..row.saturating_add(height).min(self.height);
+        let cols = col.min(self.width)..col.saturating_add(width).min(self.width);
+        let data = rows
+            .clone()
+            .flat_map(|r| self.row(r)[cols.clone()].iter().cloned())
+            .collect();
+        Self::new(data, cols.len(), rows.len())
+    }
+
+    /// Positions of all cells, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = [usize; 2]> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| [row, col]))
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors4(&self, [row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> + use<T> {
+        let (width, height) = (self.width, self.height);
+        [
+            [row.wrapping_sub(1), col],
+            [row, col.wrapping_sub(1)],
+            [row, col + 1],
+            [row + 1, col],
+        ]
+        .into_iter()
+        .filter(move |&[r, c]| r < height && c < width)
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors8(&self, [row, col]: [usize; 2]) -> impl Iterator<Item = [usize; 2]> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (row.saturating_sub(1)..(row + 2).min(height))
+            .flat_map(move |r| (col.saturating_sub(1)..(col + 2).min(width)).map(move |c| [r, c]))
+            .filter(move |&pos| pos != [row, col])
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.data.iter().map(f).collect(), self.width, self.height)
+    }
+
+    /// # Errors
+    ///
+    /// Returns the first error produced by `f`.
+    pub fn try_map<U, E>(&self, f: impl FnMut(&T) -> Result<U, E>) -> Result<Grid<U>, E> {
+        let data = self.data.iter().map(f).collect::<Result<_, _>>()?;
+        Ok(Grid::new(data, self.width, self.height))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The cell at `[row, col]`, or what `border` makes of it if that lies outside the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty and `border` is not a constant.
+    #[must_use]
+    pub fn get_or_border(&self, [row, col]: [isize; 2], border: &Border<T>) -> T {
+        let (height, width) = (self.height.cast_signed(), self.width.cast_signed());
+        if (0..height).contains(&row) && (0..width).contains(&col) {
+            return self[[row.cast_unsigned(), col.cast_unsigned()]].clone();
+        }
+        let [row, col] = match border {
+            Border::Constant(value) => return value.clone(),
+            Border::Clamp => [row.clamp(0, height - 1), col.clamp(0, width - 1)],
+            Border::Wrap => [row.rem_euclid(height), col.rem_euclid(width)],
+        };
+        self[[row.cast_unsigned(), col.cast_unsigned()]].clone()
+    }
+
+    /// Applies `f` to the window around every cell, for a new grid of what it returns.
+    ///
+    /// The new grid is `grow` cells larger on each side, with windows centered that far
+    /// outside the grid. The cells past the edges are taken from `border`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty and `border` is not a constant.
+    #[must_use]
+    pub fn stencil<U>(
+        &self,
+        grow: usize,
+        border: &Border<T>,
+        mut f: impl FnMut(Window<'_, T>) -> U,
+    ) -> Grid<U> {
+        let width = self.width + 2 * grow;
+        let height = self.height + 2 * grow;
+        let grow = grow.cast_signed();
+        let data = (0..height.cast_signed())
+            .flat_map(|row| (0..width.cast_signed()).map(move |col| [row - grow, col - grow]))
+            .map(|center| {
+                f(Window {
+                    grid: self,
+                    border,
+                    center,
+                })
+            })
+            .collect();
+        Grid::new(data, width, height)
+    }
+
+    /// Like [`stencil`](Self::stencil), but hands `f` the 3×3 block of cells around each
+    /// cell, row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty and `border` is not a constant.
+    #[must_use]
+    pub fn convolve3x3<U>(
+        &self,
+        grow: usize,
+        border: &Border<T>,
+        mut f: impl FnMut([[T; 3]; 3]) -> U,
+    ) -> Grid<U> {
+        self.stencil(grow, border, |window| {
+            f([-1, 0, 1].map(|row| [-1, 0, 1].map(|col| window.get(row, col))))
+        })
+    }
+}
+
+impl Grid<u8> {
+    /// Parses newline-separated rows of bytes, one cell per byte. A newline after the last row is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows have different lengths, or there are no cells.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, GridError> {
+        let input = input.strip_suffix(b"\n").unwrap_or(input);
+        let mut data = Vec::new();
+        let mut height = 0;
+        let mut width = None;
+        for row in input.split(|&ch| ch == b'\n') {
+            let row = row.strip_suffix(b"\r").unwrap_or(row);
+            if *width.get_or_insert(row.len()) != row.len() {
+                return Err(GridError::RaggedRows);
+            }
+            data.extend_from_slice(row);
+            height += 1;
+        }
+        if data.is_empty() {
+            return Err(GridError::Empty);
+        }
+        Ok(Self::new(data, width.unwrap_or(0), height))
+    }
+}
+
+impl<T> Index<[usize; 2]> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: [usize; 2]) -> &Self::Output {
+        self.get(pos).expect("Index out of range")
+    }
+}
+
+impl<T> IndexMut<[usize; 2]> for Grid<T> {
+    fn index_mut(&mut self, pos: [usize; 2]) -> &mut Self::Output {
+        self.get_mut(pos).expect("Index out of range")
+    }
+}
+
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for &cell in row {
+                f.write_char(cell.into())?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() {
+        let grid = Grid::from_bytes(b"abc\ndef").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[[1, 0]], b'd');
+        assert_eq!(grid.get([2, 0]), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(Grid::from_bytes(b"abc\nde"), Err(GridError::RaggedRows));
+        assert_eq!(Grid::from_bytes(b"abc\r\ndef\r\n"), Ok(grid));
+        assert_eq!(
+            Grid::from_bytes(b"abc\ndef\n\n"),
+            Err(GridError::RaggedRows)
+        );
+        assert_eq!(Grid::from_bytes(b""), Err(GridError::Empty));
+        assert_eq!(Grid::from_bytes(b"\n"), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = Grid::from_bytes(b"abc\ndef").unwrap();
+        grid[[0, 1]] = b'x';
+        assert_eq!(grid.row(0), b"axc");
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"xe");
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.positions().nth(4), Some([1, 1]));
+    }
+
+    #[test]
+    fn test_crop() {
+        let grid = Grid::from_bytes(b"abc\ndef\nghi").unwrap();
+        assert_eq!(grid.crop([1, 1], 2, 2).to_string(), "ef\nhi\n");
+        assert_eq!(grid.crop([2, 0], 5, 5).to_string(), "ghi\n");
+        assert_eq!(grid.crop([3, 3], 1, 1).as_slice(), b"");
+    }
+
+    #[test]
+    fn test_get_or_border() {
+        let grid = Grid::from_bytes(b"abc\ndef").unwrap();
+        assert_eq!(grid.get_or_border([1, 2], &Border::Clamp), b'f');
+        assert_eq!(grid.get_or_border([-1, 3], &Border::Constant(b'.')), b'.');
+        assert_eq!(grid.get_or_border([-1, 3], &Border::Clamp), b'c');
+        assert_eq!(grid.get_or_border([5, -4], &Border::Clamp), b'd');
+        assert_eq!(grid.get_or_border([-1, 3], &Border::Wrap), b'd');
+        assert_eq!(grid.get_or_border([5, -4], &Border::Wrap), b'f');
+    }
+
+    #[test]
+    fn test_convolve3x3() {
+        let grid = Grid::new(vec![1_u32; 6], 3, 2);
+        let sum = |block: [[u32; 3]; 3]| block.iter().flatten().sum::<u32>();
+        let sums = grid.convolve3x3(0, &Border::Constant(0), sum);
+        assert_eq!(sums.as_slice(), [4, 6, 4, 4, 6, 4]);
+        assert_eq!(grid.convolve3x3(0, &Border::Wrap, sum).as_slice(), [9; 6]);
+        let grown = grid.convolve3x3(1, &Border::Constant(0), sum);
+        assert_eq!((grown.width(), grown.height()), (5, 4));
+        assert_eq!(grown.row(0), [1, 2, 3, 2, 1]);
+        assert_eq!(grown.row(1), [2, 4, 6, 4, 2]);
+        let top_left = grid.stencil(1, &Border::Clamp, |window| window.get(-1, -1));
+        assert_eq!(top_left.as_slice(), [1; 20]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(vec![0; 9], 3, 3);
+        assert_eq!(
+            grid.neighbors4([0, 0]).collect::<Vec<_>>(),
+            [[0, 1], [1, 0]]
+        );
+        assert_eq!(grid.neighbors4([1, 1]).count(), 4);
+        assert_eq!(
+            grid.neighbors8([0, 0]).collect::<Vec<_>>(),
+            [[0, 1], [1, 0], [1, 1]]
+        );
+        assert_eq!(grid.neighbors8([1, 1]).count(), 8);
+        assert_eq!(grid.neighbors8([2, 2]).count(), 3);
+    }
+}
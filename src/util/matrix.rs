@@ -0,0 +1,487 @@
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+pub trait Scalar: Copy + Default + PartialEq + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize);
+
+/// A fixed-size `R`×`C` matrix stored row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix<T, const R: usize, const C: usize>(pub [[T; C]; R]);
+
+impl<T: Scalar, const R: usize, const C: usize> Matrix<T, R, C> {
+    #[must_use]
+    pub const fn new(rows: [[T; C]; R]) -> Self {
+        Self(rows)
+    }
+
+    #[must_use]
+    pub const fn zero() -> Self {
+        Self([[T::ZERO; C]; R])
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        let mut result = Matrix::zero();
+        for (r, row) in self.0.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                result.0[c][r] = value;
+            }
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, mut f: impl FnMut(T) -> U) -> Matrix<U, R, C> {
+        Matrix(self.0.map(|row| row.map(&mut f)))
+    }
+}
+
+impl<T: Scalar, const N: usize> Matrix<T, N, N> {
+    #[must_use]
+    pub fn identity() -> Self {
+        let mut result = Self::zero();
+        for i in 0..N {
+            result.0[i][i] = T::ONE;
+        }
+        result
+    }
+
+    /// Raises the matrix to the power `exp` by repeated squaring.
+    #[must_use]
+    pub fn pow(&self, exp: u64) -> Self {
+        self.pow_by(exp, |a, b| a * b)
+    }
+
+    /// Like [`Matrix::pow`], but with a caller-supplied product, e.g. one that reduces
+    /// modulo some number after each multiplication.
+    #[must_use]
+    pub fn pow_by(&self, mut exp: u64, mut mul: impl FnMut(&Self, &Self) -> Self) -> Self {
+        let mut result = Self::identity();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(&result, &base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = mul(&base, &base);
+            }
+        }
+        result
+    }
+}
+
+impl<const N: usize> Matrix<u64, N, N> {
+    /// The product modulo `modulus`, reduced after every step so that nothing overflows.
+    #[must_use]
+    pub fn mul_mod(&self, rhs: &Self, modulus: u64) -> Self {
+        let modulus = u128::from(modulus);
+        let mut result = Self::zero();
+        for r in 0..N {
+            for k in 0..N {
+                let sum = (0..N).fold(0, |sum, c| {
+                    (sum + u128::from(self.0[r][c]) * u128::from(rhs.0[c][k])) % modulus
+                });
+                // The sum was reduced modulo a `u64`, so it fits in one.
+                result.0[r][k] = u64::try_from(sum).unwrap_or_default();
+            }
+        }
+        result
+    }
+
+    /// Raises the matrix to the power `exp` modulo `modulus`.
+    #[must_use]
+    pub fn pow_mod(&self, exp: u64, modulus: u64) -> Self {
+        self.pow_by(exp, |a, b| a.mul_mod(b, modulus))
+    }
+}
+
+impl<const N: usize> Matrix<u128, N, N> {
+    /// The product, or `None` if any entry overflows.
+    #[must_use]
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        let mut result = Self::zero();
+        for r in 0..N {
+            for k in 0..N {
+                result.0[r][k] = (0..N).try_fold(0_u128, |sum, c| {
+                    sum.checked_add(self.0[r][c].checked_mul(rhs.0[c][k])?)
+                })?;
+            }
+        }
+        Some(result)
+    }
+}
+
+impl<T: Scalar + Sub<Output = T>> Matrix<T, 3, 3> {
+    #[must_use]
+    pub fn determinant(&self) -> T {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>>
+    for Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize, const K: usize> Mul<&Matrix<T, C, K>>
+    for &Matrix<T, R, C>
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: &Matrix<T, C, K>) -> Self::Output {
+        let mut result = Matrix::zero();
+        for r in 0..R {
+            for k in 0..K {
+                result.0[r][k] = (0..C).fold(T::ZERO, |sum, c| sum + self.0[r][c] * rhs.0[c][k]);
+            }
+        }
+        result
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Mul<[T; C]> for &Matrix<T, R, C> {
+    type Output = [T; R];
+
+    fn mul(self, rhs: [T; C]) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl<T: Scalar, const R: usize, const C: usize> Mul<[T; C]> for Matrix<T, R, C> {
+    type Output = [T; R];
+
+    fn mul(self, rhs: [T; C]) -> Self::Output {
+        self.0.map(|row| {
+            row.iter()
+                .zip(&rhs)
+                .fold(T::ZERO, |sum, (&a, &b)| sum + a * b)
+        })
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.0[row][col]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.0[row][col]
+    }
+}
+
+/// An `N`×`N` matrix whose size is only known at run time, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SquareMatrix<T> {
+    size: usize,
+    data: Vec<T>,
+}
+
+impl<T: Scalar> SquareMatrix<T> {
+    #[must_use]
+    pub fn zero(size: usize) -> Self {
+        Self {
+            size,
+            data: vec![T::ZERO; size * size],
+        }
+    }
+
+    #[must_use]
+    pub fn identity(size: usize) -> Self {
+        let mut result = Self::zero(size);
+        for i in 0..size {
+            result[(i, i)] = T::ONE;
+        }
+        result
+    }
+
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> SquareMatrix<U> {
+        SquareMatrix {
+            size: self.size,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.size..(row + 1) * self.size]
+    }
+
+    /// Raises the matrix to the power `exp` by repeated squaring, with a caller-supplied
+    /// product.
+    #[must_use]
+    pub fn pow_by(&self, mut exp: u64, mut mul: impl FnMut(&Self, &Self) -> Self) -> Self {
+        let mut result = Self::identity(self.size);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(&result, &base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = mul(&base, &base);
+            }
+        }
+        result
+    }
+}
+
+impl SquareMatrix<u64> {
+    /// The product modulo `modulus`, reduced after every step so that nothing overflows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices have different sizes, or `modulus` is zero.
+    #[must_use]
+    pub fn mul_mod(&self, rhs: &Self, modulus: u64) -> Self {
+        assert_eq!(self.size, rhs.size, "matrices of different sizes");
+        let modulus = u128::from(modulus);
+        let mut result = Self::zero(self.size);
+        for r in 0..self.size {
+            for k in 0..self.size {
+                let sum = self.row(r).iter().enumerate().fold(0, |sum, (c, &a)| {
+                    (sum + u128::from(a) * u128::from(rhs[(c, k)])) % modulus
+                });
+                // The sum was reduced modulo a `u64`, so it fits in one.
+                result[(r, k)] = u64::try_from(sum).unwrap_or_default();
+            }
+        }
+        result
+    }
+
+    /// Raises the matrix to the power `exp` modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn pow_mod(&self, exp: u64, modulus: u64) -> Self {
+        self.pow_by(exp, |a, b| a.mul_mod(b, modulus))
+    }
+
+    /// The product with the column vector `rhs`, modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    #[must_use]
+    pub fn mul_vec_mod(&self, rhs: &[u64], modulus: u64) -> Vec<u64> {
+        let modulus = u128::from(modulus);
+        (0..self.size)
+            .map(|r| {
+                let sum = self.row(r).iter().zip(rhs).fold(0, |sum, (&a, &b)| {
+                    (sum + u128::from(a) * (u128::from(b) % modulus)) % modulus
+                });
+                u64::try_from(sum).unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+impl SquareMatrix<u128> {
+    /// The product, or `None` if any entry overflows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices have different sizes.
+    #[must_use]
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        assert_eq!(self.size, rhs.size, "matrices of different sizes");
+        let mut result = Self::zero(self.size);
+        for r in 0..self.size {
+            for k in 0..self.size {
+                result[(r, k)] = self
+                    .row(r)
+                    .iter()
+                    .enumerate()
+                    .try_fold(0_u128, |sum, (c, &a)| {
+                        sum.checked_add(a.checked_mul(rhs[(c, k)])?)
+                    })?;
+            }
+        }
+        Some(result)
+    }
+
+    /// The product with the column vector `rhs`, or `None` if any entry overflows.
+    #[must_use]
+    pub fn checked_mul_vec(&self, rhs: &[u128]) -> Option<Vec<u128>> {
+        (0..self.size)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .zip(rhs)
+                    .try_fold(0_u128, |sum, (&a, &b)| sum.checked_add(a.checked_mul(b)?))
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for SquareMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.size + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for SquareMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.size + col]
+    }
+}
+
+/// The 24 proper rotations of the cube, as signed permutation matrices with determinant 1.
+/// The identity comes first.
+#[must_use]
+pub fn rotations<T: Scalar + Neg<Output = T> + Sub<Output = T>>() -> [Matrix<T, 3, 3>; 24] {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut result = [Matrix::zero(); 24];
+    let mut count = 0;
+    for perm in PERMUTATIONS {
+        for signs in 0..8 {
+            let mut m = Matrix::zero();
+            for (row, &col) in perm.iter().enumerate() {
+                m.0[row][col] = if signs & (1 << row) == 0 {
+                    T::ONE
+                } else {
+                    -T::ONE
+                };
+            }
+            if m.determinant() == T::ONE {
+                result[count] = m;
+                count += 1;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(a * b, Matrix::new([[58, 64], [139, 154]]));
+        assert_eq!(a * [1, 0, -1], [-2, -2]);
+        assert_eq!(a.transpose().transpose(), a);
+    }
+
+    #[test]
+    fn test_pow() {
+        let fib = Matrix::<u64, 2, 2>::new([[1, 1], [1, 0]]);
+        assert_eq!(fib.pow(0), Matrix::identity());
+        assert_eq!(fib.pow(10)[(0, 1)], 55);
+        assert_eq!(fib.pow(90)[(0, 1)], 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn test_pow_by_modulus() {
+        let fib = Matrix::<u64, 2, 2>::new([[1, 1], [1, 0]]);
+        let result = fib.pow_by(90, |a, b| (a * b).map(|x| x % 1_000_007));
+        assert_eq!(result, fib.pow(90).map(|x| x % 1_000_007));
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let fib = Matrix::<u64, 2, 2>::new([[1, 1], [1, 0]]);
+        assert_eq!(
+            fib.pow_mod(90, 1_000_007),
+            fib.pow(90).map(|x| x % 1_000_007)
+        );
+        let modulus = u64::MAX - 58;
+        let expected = fib
+            .map(u128::from)
+            .pow(180)
+            .map(|x| x % u128::from(modulus));
+        assert_eq!(fib.pow_mod(180, modulus).map(u128::from), expected);
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        let fib = Matrix::<u128, 2, 2>::new([[1, 1], [1, 0]]);
+        let big = fib.pow(180);
+        assert_eq!(fib.checked_mul(&big), Some(fib.pow(181)));
+        assert_eq!(big.checked_mul(&big), None);
+    }
+
+    #[test]
+    fn test_square_matrix() {
+        let mut fib = SquareMatrix::<u64>::zero(2);
+        fib[(0, 0)] = 1;
+        fib[(0, 1)] = 1;
+        fib[(1, 0)] = 1;
+        let fixed = Matrix::<u64, 2, 2>::new([[1, 1], [1, 0]]);
+        let modulus = u64::MAX - 58;
+        let result = fib.pow_mod(180, modulus);
+        let expected = fixed.pow_mod(180, modulus);
+        assert_eq!(result.size(), 2);
+        assert_eq!(result[(0, 1)], expected[(0, 1)]);
+        assert_eq!(result[(1, 1)], expected[(1, 1)]);
+        assert_eq!(fib.pow_mod(0, 7), SquareMatrix::identity(2));
+        assert_eq!(fib.mul_vec_mod(&[1, 0], 7), [1, 1]);
+        let fib = fib.map(|&x| u128::from(x));
+        let big = fib.pow_by(180, |a, b| a.checked_mul(b).unwrap());
+        assert_eq!(big[(0, 1)], fixed.map(u128::from).pow(180)[(0, 1)]);
+        assert_eq!(big.checked_mul(&big), None);
+        assert_eq!(fib.checked_mul_vec(&[2, 1]), Some(vec![3, 2]));
+        assert_eq!(big.checked_mul_vec(&[u128::MAX, 0]), None);
+    }
+
+    #[test]
+    fn test_rotations() {
+        let rotations = rotations::<i32>();
+        assert_eq!(rotations[0], Matrix::identity());
+        let unique = rotations.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), 24);
+        for a in &rotations {
+            assert_eq!(a.determinant(), 1);
+            assert_eq!(a * &a.transpose(), Matrix::identity());
+            for b in &rotations {
+                assert!(unique.contains(&(a * b)));
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+pub mod animation;
+pub mod array_grid;
+pub mod bitboard;
+pub mod bitset;
+pub mod counter;
+pub mod cycle;
+#[cfg(feature = "image")]
+pub mod export;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod matrix;
+pub mod ocr;
+pub mod par_search;
+pub mod parse;
+pub mod pathfinding;
+pub mod pq;
+pub mod render;
+#[cfg(feature = "serde")]
+pub mod serde_array;
+pub mod simulation;
+pub mod style;
+pub mod symmetry;
+pub mod union_find;
@@ -0,0 +1,149 @@
+use crate::util::geometry::{BoundingBox, Coordinate, Point};
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+/// Glyph width plus the blank column between letters.
+const SPACING: usize = GLYPH_WIDTH + 1;
+
+/// Packs a glyph given row by row as `#` and `.` into a bit mask.
+const fn glyph(art: &[u8; GLYPH_WIDTH * GLYPH_HEIGHT]) -> u32 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < art.len() {
+        if art[i] == b'#' {
+            mask |= 1 << i;
+        }
+        i += 1;
+    }
+    mask
+}
+
+/// The capital letters that appear in puzzle answers.
+const GLYPHS: [(char, u32); 17] = [
+    ('A', glyph(b".##.#..##..######..##..#")),
+    ('B', glyph(b"###.#..####.#..##..####.")),
+    ('C', glyph(b".##.#..##...#...#..#.##.")),
+    ('E', glyph(b"#####...###.#...#...####")),
+    ('F', glyph(b"#####...###.#...#...#...")),
+    ('G', glyph(b".##.#..##...#.###..#.###")),
+    ('H', glyph(b"#..##..######..##..##..#")),
+    ('I', glyph(b".###..#...#...#...#..###")),
+    ('J', glyph(b"..##...#...#...##..#.##.")),
+    ('K', glyph(b"#..##.#.##..#.#.#.#.#..#")),
+    ('L', glyph(b"#...#...#...#...#...####")),
+    ('O', glyph(b".##.#..##..##..##..#.##.")),
+    ('P', glyph(b"###.#..##..####.#...#...")),
+    ('R', glyph(b"###.#..##..####.#.#.#..#")),
+    ('S', glyph(b".####...#....##....####.")),
+    ('U', glyph(b"#..##..##..##..##..#.##.")),
+    ('Z', glyph(b"####...#..#..#..#...####")),
+];
+
+/// Reads the letters drawn by a set of lit points. The text starts at the leftmost lit column
+/// and the topmost lit row.
+///
+/// Returns `None` if the drawing is not six rows high, or contains anything but known letters.
+pub fn recognize<T>(points: impl IntoIterator<Item = Point<T>> + Clone) -> Option<String>
+where
+    T: Coordinate,
+    usize: TryFrom<T>,
+{
+    let bounds = BoundingBox::from_points(points.clone())?;
+    let width = usize::try_from(bounds.width()).ok()?;
+    let height = usize::try_from(bounds.height()).ok()?;
+    if height != GLYPH_HEIGHT {
+        return None;
+    }
+    let mut masks = vec![0_u32; width.div_ceil(SPACING)];
+    for point in points {
+        let x = usize::try_from(point.x - bounds.min.x).ok()?;
+        let y = usize::try_from(point.y - bounds.min.y).ok()?;
+        let (letter, column) = (x / SPACING, x % SPACING);
+        if column == GLYPH_WIDTH {
+            return None;
+        }
+        masks[letter] |= 1 << (y * GLYPH_WIDTH + column);
+    }
+    masks
+        .into_iter()
+        .map(|mask| {
+            GLYPHS
+                .iter()
+                .find_map(|&(letter, glyph)| (glyph == mask).then_some(letter))
+        })
+        .collect()
+}
+
+/// The letters [`recognize`] knows, and [`draw`] can draw.
+pub fn letters() -> impl Iterator<Item = char> {
+    GLYPHS.iter().map(|&(letter, _)| letter)
+}
+
+/// Draws `text` as lit points, the way [`recognize`] reads them, with the top left corner at
+/// the origin.
+///
+/// Returns `None` if the text contains anything but known letters.
+#[must_use]
+pub fn draw(text: &str) -> Option<Vec<Point<usize>>> {
+    let mut points = Vec::new();
+    for (index, ch) in text.chars().enumerate() {
+        let (_, mask) = GLYPHS.iter().find(|&&(letter, _)| letter == ch)?;
+        for bit in (0..GLYPH_WIDTH * GLYPH_HEIGHT).filter(|bit| mask & 1 << bit != 0) {
+            points.push(Point::new(
+                index * SPACING + bit % GLYPH_WIDTH,
+                bit / GLYPH_WIDTH,
+            ));
+        }
+    }
+    Some(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(art: &str) -> Vec<Point<i32>> {
+        let mut result = Vec::new();
+        for (y, line) in (0..).zip(art.lines()) {
+            for (x, ch) in (0..).zip(line.chars()) {
+                if ch == '#' {
+                    result.push(Point::new(x, y));
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_recognize() {
+        let art = "\
+            #..#.####.###.\n\
+            #..#.#....#..#\n\
+            ####.###..###.\n\
+            #..#.#....#..#\n\
+            #..#.#....#..#\n\
+            #..#.####.###.\
+        ";
+        assert_eq!(recognize(points(art)), Some("HEB".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let art = "\
+            #####\n\
+            #...#\n\
+            #...#\n\
+            #...#\n\
+            #####\
+        ";
+        assert_eq!(recognize(points(art)), None);
+        assert_eq!(recognize(Vec::<Point<i32>>::new()), None);
+    }
+
+    #[test]
+    fn test_draw() {
+        let text = letters().collect::<String>();
+        assert_eq!(recognize(draw(&text).unwrap()), Some(text));
+        assert_eq!(draw("HQ"), None);
+    }
+}
@@ -0,0 +1,115 @@
+use std::iter::Sum;
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rayon::prelude::*;
+
+/// Explores the tree rooted at `root` depth-first and sums the values returned by `expand`.
+///
+/// `expand` returns the value of a node and pushes its children. Nodes shallower than
+/// `spawn_depth` have their children explored in parallel; deeper subtrees are searched
+/// sequentially with an explicit stack.
+pub fn par_dfs_sum<S, V, F>(root: S, spawn_depth: usize, expand: &F) -> V
+where
+    S: Send,
+    V: Send + Sum + AddAssign,
+    F: Fn(S, &mut Vec<S>) -> V + Sync,
+{
+    let mut children = Vec::new();
+    let mut total = expand(root, &mut children);
+    if spawn_depth > 0 {
+        total += children
+            .into_par_iter()
+            .map(|child| par_dfs_sum(child, spawn_depth - 1, expand))
+            .sum();
+    } else {
+        let mut stack = children;
+        while let Some(node) = stack.pop() {
+            total += expand(node, &mut stack);
+        }
+    }
+    total
+}
+
+/// Searches for the minimum-cost solution in the tree rooted at `root`.
+///
+/// `expand` receives a node and the best cost found so far by any worker. It returns
+/// `Some(cost)` if the node is a complete solution, and pushes the children that could
+/// still beat the current best. Parallelism is controlled by `spawn_depth` as in
+/// [`par_dfs_sum`].
+pub fn par_branch_and_bound<S, F>(root: S, spawn_depth: usize, expand: &F) -> Option<u64>
+where
+    S: Send,
+    F: Fn(S, u64, &mut Vec<S>) -> Option<u64> + Sync,
+{
+    let best = AtomicU64::new(u64::MAX);
+    branch_and_bound(root, spawn_depth, expand, &best);
+    let best = best.into_inner();
+    (best != u64::MAX).then_some(best)
+}
+
+fn branch_and_bound<S, F>(node: S, spawn_depth: usize, expand: &F, best: &AtomicU64)
+where
+    S: Send,
+    F: Fn(S, u64, &mut Vec<S>) -> Option<u64> + Sync,
+{
+    let mut children = Vec::new();
+    if let Some(cost) = expand(node, best.load(Ordering::Relaxed), &mut children) {
+        best.fetch_min(cost, Ordering::Relaxed);
+    }
+    if spawn_depth > 0 {
+        children
+            .into_par_iter()
+            .for_each(|child| branch_and_bound(child, spawn_depth - 1, expand, best));
+    } else {
+        let mut stack = children;
+        while let Some(node) = stack.pop() {
+            if let Some(cost) = expand(node, best.load(Ordering::Relaxed), &mut stack) {
+                best.fetch_min(cost, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_dfs_sum() {
+        // Number of compositions of 20 into parts of size 1 and 2, i.e. fib(21).
+        let count = par_dfs_sum(20_u32, 4, &|remaining, children: &mut Vec<u32>| {
+            if remaining == 0 {
+                return 1_u64;
+            }
+            children.extend((1..=2).filter_map(|step| remaining.checked_sub(step)));
+            0
+        });
+        assert_eq!(count, 10_946);
+    }
+
+    #[test]
+    fn test_par_branch_and_bound() {
+        // Fewest coins summing to 63 using denominations 1, 5, 10, 21, 25.
+        let coins = [1, 5, 10, 21, 25];
+        let best = par_branch_and_bound(
+            (63_u32, 0_u64),
+            2,
+            &|(remaining, used), best, children: &mut Vec<(u32, u64)>| {
+                if remaining == 0 {
+                    return Some(used);
+                }
+                if used + 1 < best {
+                    children.extend(
+                        coins
+                            .iter()
+                            .filter(|&&c| c <= remaining)
+                            .map(|&c| (remaining - c, used + 1)),
+                    );
+                }
+                None
+            },
+        );
+        assert_eq!(best, Some(3));
+    }
+}
@@ -0,0 +1,308 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Write as _};
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[cfg(feature = "arena")]
+use crate::solver::Arena;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {error}")]
+pub struct LineError<E> {
+    pub line: usize,
+    pub error: E,
+}
+
+/// Parses every line of `input`, keeping the records that parse and collecting the errors
+/// (with 1-based line numbers) of those that don't.
+#[must_use]
+pub fn parse_lines_lenient<T: FromStr>(input: &str) -> (Vec<T>, Vec<LineError<T::Err>>) {
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (ix, line) in input.lines().enumerate() {
+        match line.parse() {
+            Ok(record) => records.push(record),
+            Err(error) => errors.push(LineError {
+                line: ix + 1,
+                error,
+            }),
+        }
+    }
+    (records, errors)
+}
+
+#[derive(Debug, Error)]
+pub enum StreamError<E> {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Parse(LineError<E>),
+}
+
+/// Parses the non-empty lines of `reader` one at a time, reusing a single line buffer.
+pub const fn parse_lines_from<R: BufRead, T: FromStr>(reader: R) -> ParsedLines<R, T> {
+    ParsedLines {
+        reader,
+        buffer: String::new(),
+        line: 0,
+        output: PhantomData,
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedLines<R, T> {
+    reader: R,
+    buffer: String,
+    line: usize,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<R: BufRead, T: FromStr> Iterator for ParsedLines<R, T> {
+    type Item = Result<T, StreamError<T::Err>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(err) => return Some(Err(err.into())),
+            }
+            let text = self.buffer.trim_end_matches(['\n', '\r']);
+            if !text.is_empty() {
+                return Some(text.parse().map_err(|error| {
+                    StreamError::Parse(LineError {
+                        line: self.line,
+                        error,
+                    })
+                }));
+            }
+        }
+    }
+}
+
+/// Why [`parse_unsigned`] could not read a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum NumberError {
+    #[error("Expected a number")]
+    Empty,
+    #[error("Invalid digit {0:?}")]
+    InvalidDigit(char),
+    #[error("Number too large")]
+    Overflow,
+}
+
+/// Reads an unsigned decimal number from ASCII digits, without going through `str` and
+/// `FromStr`. Unlike `str::parse`, a leading `+` is not accepted.
+///
+/// # Errors
+///
+/// Fails if there are no digits, a byte is not a digit, or the number does not fit in `T`.
+pub fn parse_unsigned<T: TryFrom<u64>>(digits: &[u8]) -> Result<T, NumberError> {
+    if digits.is_empty() {
+        return Err(NumberError::Empty);
+    }
+    let mut value = 0_u64;
+    for &digit in digits {
+        if !digit.is_ascii_digit() {
+            return Err(NumberError::InvalidDigit(char::from(digit)));
+        }
+        value = value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(u64::from(digit - b'0')))
+            .ok_or(NumberError::Overflow)?;
+    }
+    T::try_from(value).map_err(|_| NumberError::Overflow)
+}
+
+/// Splits `s` around the first occurrence of `separator`. A plain byte comparison, which for
+/// the short separators of puzzle inputs is quicker than `str::split_once` with a string
+/// pattern.
+///
+/// An empty separator splits nothing, and returns `None`.
+#[must_use]
+pub fn split_once_bytes<'a>(s: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    if separator.is_empty() {
+        return None;
+    }
+    let at = s
+        .windows(separator.len())
+        .position(|window| window == separator)?;
+    Some((&s[..at], &s[at + separator.len()..]))
+}
+
+/// The lines of `input`, without their `\n` or `\r\n` endings, as byte slices. Like
+/// `str::lines`, a final line ending does not start another line.
+pub fn byte_lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let empty = input.is_empty();
+    input
+        .strip_suffix(b"\n")
+        .unwrap_or(input)
+        .split(|&b| b == b'\n')
+        .filter(move |_| !empty)
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// `items` one per line, as [`byte_lines`] reads them back, without a line ending after the
+/// last.
+#[must_use]
+pub fn join_lines<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    let mut result = String::new();
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            result.push('\n');
+        }
+        // Writing to a `String` can not fail.
+        let _ = write!(result, "{item}");
+    }
+    result
+}
+
+/// The input with `\n` line endings, and no spaces or tabs at the ends of lines or empty lines
+/// at the end.
+///
+/// Inputs saved on Windows or pasted from a browser may have them. Borrows the input when there
+/// is nothing to change.
+#[must_use]
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.trim_end();
+    let untidy = |line: &str| line.ends_with([' ', '\t', '\r']);
+    if !input.contains('\r') && !input.lines().any(untidy) {
+        return Cow::Borrowed(input);
+    }
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines() {
+        normalized.push_str(line.trim_end_matches([' ', '\t', '\r']));
+        normalized.push('\n');
+    }
+    normalized.pop();
+    Cow::Owned(normalized)
+}
+
+/// `input` as saved on Windows by an editor that leaves spaces at the ends of lines.
+///
+/// Every line ends in a space and `\r\n`, and an empty line follows, for testing that parsing
+/// goes through [`normalize`].
+///
+/// # Panics
+///
+/// Panics if `input` is not UTF-8.
+#[cfg(test)]
+pub fn untidy(input: impl AsRef<[u8]>) -> String {
+    let input = std::str::from_utf8(input.as_ref()).unwrap();
+    input.replace('\n', " \r\n") + " \r\n\r\n"
+}
+
+/// Collects `items` in `arena` until they have all been read, then moves them into a `Vec` of
+/// exactly that length, which is allocated once instead of each time it would have grown.
+///
+/// # Errors
+///
+/// Returns the first error among `items`.
+#[cfg(feature = "arena")]
+pub fn collect_exact<T, E>(
+    items: impl IntoIterator<Item = Result<T, E>>,
+    arena: &Arena,
+) -> Result<Vec<T>, E> {
+    let mut scratch = bumpalo::collections::Vec::new_in(arena);
+    for item in items {
+        scratch.push(item?);
+    }
+    Ok(scratch.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines_lenient() {
+        let (records, errors) = parse_lines_lenient::<u32>("1\n2\nx\n4\n-5");
+        assert_eq!(records, [1, 2, 4]);
+        assert_eq!(errors.iter().map(|e| e.line).collect::<Vec<_>>(), [3, 5]);
+        assert_eq!(
+            errors[0].to_string(),
+            "line 3: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_from() {
+        let input = b"1\r\n2\n\nx\n4\n" as &[u8];
+        let results = parse_lines_from::<_, u32>(input).collect::<Vec<_>>();
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[1], Ok(2)));
+        assert!(matches!(
+            results[2],
+            Err(StreamError::Parse(LineError { line: 4, .. }))
+        ));
+        assert!(matches!(results[3], Ok(4)));
+    }
+
+    #[test]
+    fn test_parse_unsigned() {
+        assert_eq!(parse_unsigned::<u8>(b"0"), Ok(0));
+        assert_eq!(parse_unsigned::<u8>(b"255"), Ok(255));
+        assert_eq!(parse_unsigned::<u8>(b"256"), Err(NumberError::Overflow));
+        assert_eq!(parse_unsigned::<i16>(b"007"), Ok(7));
+        assert_eq!(parse_unsigned::<u64>(b""), Err(NumberError::Empty));
+        assert_eq!(
+            parse_unsigned::<u64>(b"-1"),
+            Err(NumberError::InvalidDigit('-'))
+        );
+        assert_eq!(
+            parse_unsigned::<u64>(b"99999999999999999999"),
+            Err(NumberError::Overflow)
+        );
+        assert_eq!(parse_unsigned::<u64>(b"18446744073709551615"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_split_once_bytes() {
+        assert_eq!(
+            split_once_bytes(b"0,9 -> 5,9", b" -> "),
+            Some((&b"0,9"[..], &b"5,9"[..]))
+        );
+        assert_eq!(
+            split_once_bytes(b"a-b-c", b"-"),
+            Some((&b"a"[..], &b"b-c"[..]))
+        );
+        assert_eq!(split_once_bytes(b"abc", b"->"), None);
+        assert_eq!(split_once_bytes(b"abc", b""), None);
+        assert_eq!(split_once_bytes(b"", b""), None);
+    }
+
+    #[test]
+    fn test_byte_lines() {
+        fn lines(input: &str) -> Vec<&[u8]> {
+            byte_lines(input.as_bytes()).collect()
+        }
+        assert_eq!(lines("a\r\nb\n\nc\n"), [&b"a"[..], b"b", b"", b"c"]);
+        assert_eq!(lines("a\nb"), [&b"a"[..], b"b"]);
+        for input in ["", "\n", "a\n\n", "\r\n"] {
+            let expected = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+            assert_eq!(lines(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\n\nb\n\n"), Cow::Borrowed("a\n\nb")));
+        assert_eq!(normalize("a\r\n\r\nb\r\n"), "a\n\nb");
+        assert_eq!(normalize("a \t\n  \nb c\r\n \r\n"), "a\n\nb c");
+        assert_eq!(normalize("  1\r\n 2"), "  1\n 2");
+        assert_eq!(normalize("\r\n"), "");
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_collect_exact() {
+        let arena = Arena::new();
+        let numbers = collect_exact("1 2 3".split(' ').map(str::parse::<u8>), &arena).unwrap();
+        assert_eq!(numbers, [1, 2, 3]);
+        assert!(collect_exact("1 x 3".split(' ').map(str::parse::<u8>), &arena).is_err());
+    }
+}
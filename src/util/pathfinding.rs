@@ -0,0 +1,231 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use tracing::debug;
+
+use crate::util::pq::{BucketQueue, IndexedHeap};
+
+/// The fewest steps from `start` to a node that `is_goal` accepts, where `neighbors` lists the
+/// nodes one step away from a node.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(steps);
+        }
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// The least total cost from `start` to a node that `is_goal` accepts, where `neighbors` lists
+/// the nodes one step away from a node with the cost of each step.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but nodes that `heuristic` puts closer to a goal are looked at first.
+///
+/// `heuristic` must never be more than the least cost from a node to a goal. A node is looked at
+/// again if a cheaper path to it turns up, so it does not have to be consistent as well.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // The nodes are numbered in the order they are found, for the queue.
+    let mut numbers = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![(start, C::default())];
+    let mut queue = IndexedHeap::new(1);
+    queue.push_or_decrease(0, heuristic(&nodes[0].0));
+    let mut expanded = 0_usize;
+    while let Some((number, _)) = queue.pop() {
+        let (ref node, cost) = nodes[number];
+        if is_goal(node) {
+            debug!(
+                expanded,
+                seen = nodes.len(),
+                queued = queue.len(),
+                "Reached the goal"
+            );
+            return Some(cost);
+        }
+        expanded += 1;
+        for (next, step) in neighbors(node) {
+            let next_cost = cost + step;
+            let next_number = match numbers.entry(next) {
+                Entry::Occupied(entry) => {
+                    let number = *entry.get();
+                    if nodes[number].1 <= next_cost {
+                        continue;
+                    }
+                    nodes[number].1 = next_cost;
+                    number
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push((entry.key().clone(), next_cost));
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[next_number].0);
+            queue.push_or_decrease(next_number, estimate);
+        }
+    }
+    None
+}
+
+/// Like [`dijkstra`], for nodes numbered `0..len` and steps that cost small whole numbers.
+///
+/// This suits the cells of a grid. It saves hashing the nodes, and queues them in one bucket per
+/// total cost rather than in a heap.
+pub fn dijkstra_buckets<I>(
+    len: usize,
+    start: usize,
+    mut neighbors: impl FnMut(usize) -> I,
+    mut is_goal: impl FnMut(usize) -> bool,
+) -> Option<usize>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut best = vec![usize::MAX; len];
+    let mut queue = BucketQueue::new();
+    best[start] = 0;
+    queue.push(start, 0);
+    while let Some((node, cost)) = queue.pop() {
+        if is_goal(node) {
+            return Some(cost);
+        }
+        if cost > best[node] {
+            continue;
+        }
+        for (next, step) in neighbors(node) {
+            let next_cost = cost + step;
+            if next_cost < best[next] {
+                best[next] = next_cost;
+                queue.push(next, next_cost);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A weighted directed graph, as lists of `(to, cost)` edges.
+    const EDGES: [&[(usize, usize)]; 6] = [
+        &[(1, 7), (2, 9), (5, 14)],
+        &[(2, 10), (3, 15)],
+        &[(3, 11), (5, 2)],
+        &[(4, 6)],
+        &[],
+        &[(4, 9)],
+    ];
+
+    fn edges(node: usize) -> impl Iterator<Item = (usize, usize)> {
+        EDGES[node].iter().copied()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let steps = bfs(
+            0,
+            |&node| edges(node).map(|(next, _)| next),
+            |&node| node == 4,
+        );
+        assert_eq!(steps, Some(2));
+        assert_eq!(
+            bfs(
+                4,
+                |&node| edges(node).map(|(next, _)| next),
+                |&node| node == 0
+            ),
+            None
+        );
+        assert_eq!(bfs(3, |_: &usize| [], |&node| node == 3), Some(0));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra(0, |&node| edges(node), |&node| node == 4),
+            Some(20)
+        );
+        assert_eq!(
+            dijkstra(0, |&node| edges(node), |&node| node == 3),
+            Some(20)
+        );
+        assert_eq!(dijkstra(4, |&node| edges(node), |&node| node == 0), None);
+        assert_eq!(dijkstra_buckets(6, 0, edges, |node| node == 4), Some(20));
+        assert_eq!(dijkstra_buckets(6, 4, edges, |node| node == 0), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        // Manhattan distance to the far corner of an open 20 by 20 grid.
+        let neighbors = |&(x, y): &(u32, u32)| {
+            [
+                (x + 1, y),
+                (x, y + 1),
+                (x.wrapping_sub(1), y),
+                (x, y.wrapping_sub(1)),
+            ]
+            .into_iter()
+            .filter(|&(x, y)| x < 20 && y < 20)
+            .map(|next| (next, 1))
+        };
+        let heuristic = |&(x, y): &(u32, u32)| 38 - x - y;
+        let goal = |&node: &(u32, u32)| node == (19, 19);
+        assert_eq!(astar((0, 0), neighbors, heuristic, goal), Some(38));
+        assert_eq!(dijkstra((0, 0), neighbors, goal), Some(38));
+        assert_eq!(
+            bfs((0, 0), |node| neighbors(node).map(|(next, _)| next), goal),
+            Some(38)
+        );
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // The heuristic is admissible, but so uneven that node 1 is first reached the long way.
+        let edges: [&[(usize, u32)]; 4] = [&[(1, 4), (2, 1)], &[(3, 4)], &[(1, 1)], &[]];
+        let heuristic = |&node: &usize| [0, 0, 5, 0][node];
+        let cost = astar(
+            0,
+            |&node| edges[node].iter().copied(),
+            heuristic,
+            |&node| node == 3,
+        );
+        assert_eq!(cost, Some(6));
+    }
+}
@@ -0,0 +1,279 @@
+/// A binary min-heap over indices, each present at most once, supporting decrease-key. It
+/// grows to fit indices past the capacity it was made with.
+///
+/// Keeping positions costs more than it saves on a grid, where each cell is reached from only a
+/// few neighbours: the `queues` benchmark finds Dijkstra's algorithm through a day 15 sized cave
+/// over twice as slow as with a `BinaryHeap` that keeps stale entries. It suits searches like
+/// day 23's, which find nodes as they go and reach them many times.
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<P> {
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+    priorities: Vec<Option<P>>,
+}
+
+impl<P: Ord + Copy> IndexedHeap<P> {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: vec![None; capacity],
+            priorities: vec![None; capacity],
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        self.positions.get(index).is_some_and(Option::is_some)
+    }
+
+    /// The priority of `index`, if it is currently queued.
+    #[must_use]
+    pub fn priority(&self, index: usize) -> Option<P> {
+        self.positions.get(index)?.and(self.priorities[index])
+    }
+
+    /// Inserts `index`, or lowers its priority if it is already queued with a higher one.
+    /// Returns `false` if the queue was left unchanged.
+    pub fn push_or_decrease(&mut self, index: usize, priority: P) -> bool {
+        if index >= self.positions.len() {
+            self.positions.resize(index + 1, None);
+            self.priorities.resize(index + 1, None);
+        }
+        if let Some(pos) = self.positions[index] {
+            if self.priorities[index].is_some_and(|p| p <= priority) {
+                return false;
+            }
+            self.priorities[index] = Some(priority);
+            self.sift_up(pos);
+        } else {
+            self.priorities[index] = Some(priority);
+            self.positions[index] = Some(self.heap.len());
+            self.heap.push(index);
+            self.sift_up(self.heap.len() - 1);
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn peek(&self) -> Option<(usize, P)> {
+        let &index = self.heap.first()?;
+        Some((index, self.priorities[index]?))
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        let last = self.heap.len().checked_sub(1)?;
+        self.swap(0, last);
+        let index = self.heap.pop()?;
+        self.positions[index] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((index, self.priorities[index]?))
+    }
+
+    fn key(&self, pos: usize) -> Option<P> {
+        self.priorities[self.heap[pos]]
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = Some(a);
+        self.positions[self.heap[b]] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.key(parent) <= self.key(pos) {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let left = 2 * pos + 1;
+            let right = left + 1;
+            let mut smallest = pos;
+            if left < self.heap.len() && self.key(left) < self.key(smallest) {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.key(right) < self.key(smallest) {
+                smallest = right;
+            }
+            if smallest == pos {
+                break;
+            }
+            self.swap(pos, smallest);
+            pos = smallest;
+        }
+    }
+}
+
+/// A min-queue for small integer priorities, storing one bucket per priority.
+///
+/// Where the priorities are small, as with day 15's risks, it is the fastest of the three in the
+/// `queues` benchmark.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    buckets: Vec<Vec<T>>,
+    cursor: usize,
+    len: usize,
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BucketQueue<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buckets: Vec::new(),
+            cursor: 0,
+            len: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, item: T, priority: usize) {
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, Vec::new);
+        }
+        self.buckets[priority].push(item);
+        self.cursor = self.cursor.min(priority);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(T, usize)> {
+        while let Some(bucket) = self.buckets.get_mut(self.cursor) {
+            if let Some(item) = bucket.pop() {
+                self.len -= 1;
+                return Some((item, self.cursor));
+            }
+            self.cursor += 1;
+        }
+        None
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(Vec::clear);
+        self.cursor = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    use super::*;
+
+    fn pseudo_random(count: usize) -> Vec<usize> {
+        let mut state = 12_345_u64;
+        (0..count)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1);
+                usize::try_from(state >> 54).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_indexed_heap_matches_binary_heap() {
+        let priorities = pseudo_random(200);
+        let mut heap = IndexedHeap::new(priorities.len());
+        let mut reference = BinaryHeap::new();
+        for (index, &priority) in priorities.iter().enumerate() {
+            heap.push_or_decrease(index, priority);
+            reference.push(Reverse(priority));
+        }
+        while let Some(Reverse(expected)) = reference.pop() {
+            assert_eq!(heap.pop().map(|(_, p)| p), Some(expected));
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = IndexedHeap::new(4);
+        heap.push_or_decrease(0, 10);
+        heap.push_or_decrease(1, 20);
+        heap.push_or_decrease(2, 30);
+        assert!(!heap.push_or_decrease(1, 25));
+        assert!(heap.push_or_decrease(2, 5));
+        assert_eq!(heap.priority(2), Some(5));
+        assert_eq!(heap.pop(), Some((2, 5)));
+        assert_eq!(heap.pop(), Some((0, 10)));
+        assert_eq!(heap.pop(), Some((1, 20)));
+        assert_eq!(heap.pop(), None);
+        assert!(!heap.contains(1));
+    }
+
+    #[test]
+    fn test_indexed_heap_grows() {
+        let mut heap = IndexedHeap::new(0);
+        assert!(!heap.contains(7));
+        assert_eq!(heap.priority(7), None);
+        assert!(heap.push_or_decrease(7, 3));
+        assert!(heap.push_or_decrease(2, 4));
+        assert_eq!(heap.priority(7), Some(3));
+        assert_eq!(heap.pop(), Some((7, 3)));
+        assert_eq!(heap.pop(), Some((2, 4)));
+    }
+
+    #[test]
+    fn test_bucket_queue_matches_binary_heap() {
+        let priorities = pseudo_random(200);
+        let mut queue = BucketQueue::new();
+        let mut reference = BinaryHeap::new();
+        for &priority in &priorities {
+            queue.push(priority, priority);
+            reference.push(Reverse(priority));
+        }
+        assert_eq!(queue.len(), priorities.len());
+        while let Some(Reverse(expected)) = reference.pop() {
+            assert_eq!(queue.pop().map(|(_, p)| p), Some(expected));
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_bucket_queue_push_below_cursor() {
+        let mut queue = BucketQueue::new();
+        queue.push('a', 5);
+        assert_eq!(queue.pop(), Some(('a', 5)));
+        queue.push('b', 2);
+        queue.push('c', 7);
+        assert_eq!(queue.pop(), Some(('b', 2)));
+        assert_eq!(queue.pop(), Some(('c', 7)));
+        assert_eq!(queue.pop(), None);
+    }
+}
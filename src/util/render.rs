@@ -0,0 +1,132 @@
+//! Draws `bool` grids as text, packing several pixels into each character.
+
+use crate::util::geometry::{BoundingBox, Coordinate, Point};
+use crate::util::grid::Grid;
+
+/// How pixels are packed into characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// One pixel wide and two high per character, drawn with half blocks.
+    #[default]
+    HalfBlocks,
+    /// Two pixels wide and four high per character, drawn with Braille dots.
+    Braille,
+    /// One pixel per character, drawn as `#` when lit and `.` when not, for copying as plain
+    /// text.
+    Plain,
+}
+
+impl Backend {
+    const fn cell(self) -> (usize, usize) {
+        match self {
+            Self::HalfBlocks => (1, 2),
+            Self::Braille => (2, 4),
+            Self::Plain => (1, 1),
+        }
+    }
+
+    /// The character for a cell, where bit `row * width + col` is set for each lit pixel.
+    fn glyph(self, mask: u8) -> char {
+        match self {
+            Self::HalfBlocks => [' ', '▀', '▄', '█'][usize::from(mask & 3)],
+            Self::Plain => ['.', '#'][usize::from(mask & 1)],
+            Self::Braille if mask == 0 => ' ',
+            Self::Braille => {
+                // Braille numbers the dots down the left column, then down the right, with the
+                // bottom row added last.
+                const DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+                let dots = (0..8)
+                    .filter(|bit| mask & 1 << bit != 0)
+                    .fold(0, |dots, bit| dots | DOTS[bit]);
+                char::from_u32(0x2800 + u32::from(dots)).unwrap_or(' ')
+            }
+        }
+    }
+}
+
+/// Draws the grid with one line of text per row of cells, separated by newlines. Cells that hang
+/// over the right or bottom edge are padded with unlit pixels.
+#[must_use]
+pub fn render(grid: &Grid<bool>, backend: Backend) -> String {
+    let (width, height) = backend.cell();
+    let mut result = String::new();
+    for top in (0..grid.height()).step_by(height) {
+        if top > 0 {
+            result.push('\n');
+        }
+        for left in (0..grid.width()).step_by(width) {
+            let mut mask = 0;
+            for row in 0..height {
+                for col in 0..width {
+                    if grid.get([top + row, left + col]) == Some(&true) {
+                        mask |= 1 << (row * width + col);
+                    }
+                }
+            }
+            result.push(backend.glyph(mask));
+        }
+    }
+    result
+}
+
+/// The smallest grid holding every point, with those points lit. Returns `None` if there are no
+/// points.
+pub fn from_points<T>(points: impl IntoIterator<Item = Point<T>> + Clone) -> Option<Grid<bool>>
+where
+    T: Coordinate,
+    usize: TryFrom<T>,
+{
+    let bounds = BoundingBox::from_points(points.clone())?;
+    let width = usize::try_from(bounds.width()).ok()?;
+    let height = usize::try_from(bounds.height()).ok()?;
+    let mut grid = Grid::new(vec![false; width * height], width, height);
+    for point in points {
+        let col = usize::try_from(point.x - bounds.min.x).ok()?;
+        let row = usize::try_from(point.y - bounds.min.y).ok()?;
+        grid[[row, col]] = true;
+    }
+    Some(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(art: &str) -> Grid<bool> {
+        let width = art.lines().next().map_or(0, str::len);
+        let data = art
+            .lines()
+            .flat_map(str::bytes)
+            .map(|b| b == b'#')
+            .collect::<Vec<_>>();
+        let height = data.len() / width;
+        Grid::new(data, width, height)
+    }
+
+    #[test]
+    fn test_half_blocks() {
+        let grid = grid("#..#\n#.##\n###.");
+        assert_eq!(render(&grid, Backend::HalfBlocks), "█ ▄█\n▀▀▀ ");
+    }
+
+    #[test]
+    fn test_braille() {
+        let grid = grid("#..#\n#.##\n###.\n....\n##..");
+        assert_eq!(render(&grid, Backend::Braille), "⠧⠞\n⠉ ");
+    }
+
+    #[test]
+    fn test_plain() {
+        let grid = grid("#..#\n#.##");
+        assert_eq!(render(&grid, Backend::Plain), "#..#\n#.##");
+    }
+
+    #[test]
+    fn test_from_points() {
+        let points = [Point { x: 3_i16, y: -1 }, Point { x: 1, y: 0 }];
+        let grid = from_points(points).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.as_slice(), [false, false, true, true, false, false]);
+        assert!(from_points::<i16>([]).is_none());
+    }
+}
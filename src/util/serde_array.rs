@@ -0,0 +1,94 @@
+//! `#[serde(with = ...)]` helpers for arrays that serde has no impls for: those longer than 32
+//! and those with a generic length. They are written as sequences.
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+fn from_vec<'de, D: Deserializer<'de>, T, const N: usize>(
+    items: Vec<T>,
+) -> Result<[T; N], D::Error> {
+    let len = items.len();
+    items
+        .try_into()
+        .map_err(|_| D::Error::invalid_length(len, &format!("{N} items").as_str()))
+}
+
+/// # Errors
+///
+/// Returns the serializer's error.
+pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+    array: &[T; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(array)
+}
+
+/// # Errors
+///
+/// Returns an error if the sequence does not have exactly `N` items.
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    from_vec::<D, T, N>(Vec::deserialize(deserializer)?)
+}
+
+/// For `Box<[T; N]>`.
+pub mod boxed {
+    use super::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// # Errors
+    ///
+    /// Returns the serializer's error.
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(
+        array: &Box<[T; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize(array, serializer)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the sequence does not have exactly `N` items.
+    pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<Box<[T; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        super::deserialize(deserializer).map(Box::new)
+    }
+}
+
+/// For `[[T; N]; M]`, as a sequence of sequences.
+pub mod nested {
+    use super::{Deserialize, Deserializer, Serialize, Serializer, from_vec};
+
+    /// # Errors
+    ///
+    /// Returns the serializer's error.
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize, const M: usize>(
+        arrays: &[[T; N]; M],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(arrays.iter().map(<[T; N]>::as_slice))
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the sequences do not have exactly `M` items of `N` items each.
+    pub fn deserialize<'de, D, T, const N: usize, const M: usize>(
+        deserializer: D,
+    ) -> Result<[[T; N]; M], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        let arrays = Vec::<Vec<T>>::deserialize(deserializer)?
+            .into_iter()
+            .map(from_vec::<D, T, N>)
+            .collect::<Result<Vec<_>, _>>()?;
+        from_vec::<D, [T; N], M>(arrays)
+    }
+}
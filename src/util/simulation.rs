@@ -0,0 +1,137 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::cycle;
+
+pub trait Simulation {
+    type Output;
+
+    fn step(&mut self) -> Self::Output;
+
+    fn state_hash(&self) -> u64;
+
+    fn run_for(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Steps until `done` accepts the new state, returning the number of steps taken.
+    fn run_until(&mut self, mut done: impl FnMut(&Self, &Self::Output) -> bool) -> usize
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        loop {
+            let output = self.step();
+            steps += 1;
+            if done(self, &output) {
+                return steps;
+            }
+        }
+    }
+
+    fn steps(&mut self) -> impl Iterator<Item = Self::Output>
+    where
+        Self: Sized,
+    {
+        std::iter::repeat_with(move || self.step())
+    }
+
+    /// Brent's cycle detection over `state_hash`. Returns `(prefix length, cycle length)`, or
+    /// `None` if no state repeats within `limit` steps.
+    fn find_cycle(&self, limit: usize) -> Option<(usize, usize)>
+    where
+        Self: Clone,
+    {
+        cycle::find_cycle(
+            self,
+            limit,
+            |sim| {
+                sim.step();
+            },
+            Self::state_hash,
+        )
+    }
+
+    /// Advances the simulation by `steps`, skipping whole cycles if the state repeats within
+    /// `limit` steps.
+    fn fast_forward(&mut self, steps: usize, limit: usize)
+    where
+        Self: Clone,
+    {
+        *self = cycle::fast_forward(
+            self.clone(),
+            steps,
+            limit,
+            |sim| {
+                sim.step();
+            },
+            Self::state_hash,
+        );
+    }
+}
+
+#[must_use]
+pub fn hash_state<T: Hash + ?Sized>(state: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct Lcg(u32);
+
+    impl Simulation for Lcg {
+        type Output = u32;
+
+        fn step(&mut self) -> Self::Output {
+            self.0 = (self.0 * self.0 + 1) % 255;
+            self.0
+        }
+
+        fn state_hash(&self) -> u64 {
+            hash_state(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut sim = Lcg(3);
+        let steps = sim.run_until(|_, &x| x == 101);
+        assert_eq!(steps, 2);
+        assert_eq!(sim.0, 101);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let sim = Lcg(3);
+        let states = std::iter::once(3)
+            .chain(sim.clone().steps().take(100))
+            .collect::<Vec<_>>();
+        let (prefix, length) = sim.find_cycle(1_000).unwrap();
+        assert_eq!(states[prefix], states[prefix + length]);
+        assert!((1..length).all(|d| states[prefix] != states[prefix + d]));
+        assert!(prefix == 0 || states[prefix - 1] != states[prefix - 1 + length]);
+    }
+
+    #[test]
+    fn test_find_cycle_limit() {
+        #[derive(Clone)]
+        struct Counter(u64);
+        impl Simulation for Counter {
+            type Output = ();
+            fn step(&mut self) {
+                self.0 += 1;
+            }
+            fn state_hash(&self) -> u64 {
+                self.0
+            }
+        }
+        assert_eq!(Counter(0).find_cycle(100), None);
+    }
+}
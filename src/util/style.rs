@@ -0,0 +1,152 @@
+//! Terminal colors, written only when wanted: never when `NO_COLOR` is set or standard output is
+//! not a terminal, unless asked for with [`ColorChoice::Always`].
+
+use std::env;
+use std::fmt::{self, Display};
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Whether to write colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when standard output is a terminal and `NO_COLOR` is unset or empty.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Decides `Auto` from the value of `NO_COLOR` and whether output goes to a terminal.
+    #[must_use]
+    pub fn resolve(self, no_color: Option<&str>, is_terminal: bool) -> bool {
+        match self {
+            Self::Auto => no_color.is_none_or(str::is_empty) && is_terminal,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// The choice made by [`set_color_choice`], as `ColorChoice as u8`.
+static CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// Sets whether [`Style::current`] colors, such as from a `--color` flag.
+pub fn set_color_choice(choice: ColorChoice) {
+    CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Colors text with SGR codes such as `1;92`, or leaves it plain.
+///
+/// Look up [`Style::current`] once per picture rather than once per character, as it reads the
+/// environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    color: bool,
+}
+
+impl Style {
+    pub const COLOR: Self = Self { color: true };
+    pub const PLAIN: Self = Self { color: false };
+
+    /// The style that [`set_color_choice`] and the environment ask for.
+    #[must_use]
+    pub fn current() -> Self {
+        let choice = match CHOICE.load(Ordering::Relaxed) {
+            1 => ColorChoice::Always,
+            2 => ColorChoice::Never,
+            _ => ColorChoice::Auto,
+        };
+        let no_color = env::var("NO_COLOR").ok();
+        Self {
+            color: choice.resolve(no_color.as_deref(), io::stdout().is_terminal()),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_color(self) -> bool {
+        self.color
+    }
+
+    /// Switches to the color of `code` for everything written after it, until [`Style::reset`].
+    #[must_use]
+    pub const fn set(self, code: &str) -> Sgr<'_> {
+        Sgr(if self.color { Some(code) } else { None })
+    }
+
+    /// Switches back to the terminal's own color.
+    #[must_use]
+    pub const fn reset(self) -> Sgr<'static> {
+        self.set("0")
+    }
+
+    /// `text` in the color of `code`, followed by a reset.
+    #[must_use]
+    pub const fn paint<T: Display>(self, code: &str, text: T) -> Painted<'_, T> {
+        Painted {
+            sgr: self.set(code),
+            text,
+        }
+    }
+}
+
+/// An escape sequence that switches color, or nothing when the style is plain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sgr<'a>(Option<&'a str>);
+
+impl Display for Sgr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.map_or(Ok(()), |code| write!(f, "\x1b[{code}m"))
+    }
+}
+
+/// Text in a color, from [`Style::paint`]. Width and alignment apply to the text.
+#[derive(Debug, Clone, Copy)]
+pub struct Painted<'a, T> {
+    sgr: Sgr<'a>,
+    text: T,
+}
+
+impl<T: Display> Display for Painted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.sgr.0 {
+            Some(_) => {
+                write!(f, "{}", self.sgr)?;
+                self.text.fmt(f)?;
+                f.write_str("\x1b[0m")
+            }
+            None => self.text.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert!(ColorChoice::Auto.resolve(None, true));
+        assert!(ColorChoice::Auto.resolve(Some(""), true));
+        assert!(!ColorChoice::Auto.resolve(Some("1"), true));
+        assert!(!ColorChoice::Auto.resolve(None, false));
+        assert!(ColorChoice::Always.resolve(Some("1"), false));
+        assert!(!ColorChoice::Never.resolve(None, true));
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(
+            format!("{:>3}|", Style::COLOR.paint("97", 7)),
+            "\x1b[97m  7\x1b[0m|"
+        );
+        assert_eq!(format!("{:>3}|", Style::PLAIN.paint("97", 7)), "  7|");
+        let style = Style::COLOR;
+        assert_eq!(
+            format!("{}x{}", style.set("90"), style.reset()),
+            "\x1b[90mx\x1b[0m"
+        );
+        let style = Style::PLAIN;
+        assert_eq!(format!("{}x{}", style.set("90"), style.reset()), "x");
+    }
+}
@@ -0,0 +1,178 @@
+use std::ops::Neg;
+
+use super::matrix::{self, Matrix};
+
+/// One of the 8 symmetries of the square, acting on `[x, y]` coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SquareSymmetry(Matrix<i8, 2, 2>);
+
+/// One of the 24 rotations of the cube, acting on `[x, y, z]` coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubeRotation(Matrix<i8, 3, 3>);
+
+impl SquareSymmetry {
+    pub const IDENTITY: Self = Self(Matrix::new([[1, 0], [0, 1]]));
+    pub const ROTATE_90: Self = Self(Matrix::new([[0, -1], [1, 0]]));
+    pub const FLIP_X: Self = Self(Matrix::new([[-1, 0], [0, 1]]));
+
+    /// The four rotations followed by the four reflections.
+    #[must_use]
+    pub fn all() -> [Self; 8] {
+        let mut result = [Self::IDENTITY; 8];
+        for i in 1..4 {
+            result[i] = result[i - 1].then(Self::ROTATE_90);
+        }
+        for i in 4..8 {
+            result[i] = result[i - 4].then(Self::FLIP_X);
+        }
+        result
+    }
+
+    /// The transform that applies `self` first and `next` second.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        Self(next.0 * self.0)
+    }
+
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self(self.0.transpose())
+    }
+
+    #[must_use]
+    pub fn apply<T: Copy + Neg<Output = T>>(self, point: [T; 2]) -> [T; 2] {
+        apply_signed_permutation(&self.0, point)
+    }
+
+    /// Applies the transform to the contents of a row-major grid, returning the new cells and
+    /// the new `(width, height)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` does not hold exactly `width * height` cells.
+    #[must_use]
+    pub fn apply_grid<T: Clone>(
+        self,
+        cells: &[T],
+        width: usize,
+        height: usize,
+    ) -> (Vec<T>, usize, usize) {
+        assert_eq!(cells.len(), width * height);
+        let (new_width, new_height) = if self.0[(0, 0)] == 0 {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        let to_signed = |v: usize| i64::try_from(v).expect("grid too large");
+        let [max_x, max_y] = [to_signed(width) - 1, to_signed(height) - 1];
+        let corners = [[0, 0], [max_x, 0], [0, max_y], [max_x, max_y]].map(|c| self.apply(c));
+        let min_x = corners.iter().map(|c| c[0]).min().unwrap_or(0);
+        let min_y = corners.iter().map(|c| c[1]).min().unwrap_or(0);
+        let mut result = cells.to_vec();
+        for (index, cell) in cells.iter().enumerate() {
+            let [x, y] = self.apply([to_signed(index % width), to_signed(index / width)]);
+            let x = usize::try_from(x - min_x).expect("transform stays in bounds");
+            let y = usize::try_from(y - min_y).expect("transform stays in bounds");
+            result[y * new_width + x] = cell.clone();
+        }
+        (result, new_width, new_height)
+    }
+}
+
+impl CubeRotation {
+    pub const IDENTITY: Self = Self(Matrix::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]));
+
+    /// All 24 rotations, starting with the identity.
+    #[must_use]
+    pub fn all() -> [Self; 24] {
+        matrix::rotations().map(Self)
+    }
+
+    /// The transform that applies `self` first and `next` second.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        Self(next.0 * self.0)
+    }
+
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self(self.0.transpose())
+    }
+
+    #[must_use]
+    pub fn apply<T: Copy + Neg<Output = T>>(self, point: [T; 3]) -> [T; 3] {
+        apply_signed_permutation(&self.0, point)
+    }
+}
+
+fn apply_signed_permutation<T: Copy + Neg<Output = T>, const N: usize>(
+    matrix: &Matrix<i8, N, N>,
+    point: [T; N],
+) -> [T; N] {
+    matrix.0.map(|row| {
+        let (col, &sign) = row
+            .iter()
+            .enumerate()
+            .find(|&(_, &s)| s != 0)
+            .expect("each row has one non-zero entry");
+        if sign > 0 { point[col] } else { -point[col] }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_square_group_laws() {
+        let all = SquareSymmetry::all();
+        let set = all.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 8);
+        for a in all {
+            assert_eq!(a.then(a.inverse()), SquareSymmetry::IDENTITY);
+            assert_eq!(a.then(SquareSymmetry::IDENTITY), a);
+            for b in all {
+                assert!(set.contains(&a.then(b)));
+                assert_eq!(a.then(b).apply([2, 5]), b.apply(a.apply([2, 5])));
+                for c in all {
+                    assert_eq!(a.then(b).then(c), a.then(b.then(c)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_group_laws() {
+        let all = CubeRotation::all();
+        let set = all.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 24);
+        assert_eq!(all[0], CubeRotation::IDENTITY);
+        for a in all {
+            assert_eq!(a.then(a.inverse()), CubeRotation::IDENTITY);
+            for b in all {
+                assert!(set.contains(&a.then(b)));
+                assert_eq!(a.then(b).apply([1, 2, 3]), b.apply(a.apply([1, 2, 3])));
+            }
+        }
+        let images = all.map(|r| r.apply([1, 2, 3]));
+        assert_eq!(images.iter().collect::<HashSet<_>>().len(), 24);
+    }
+
+    #[test]
+    fn test_apply_grid() {
+        // 1 2 3
+        // 4 5 6
+        let cells = [1, 2, 3, 4, 5, 6];
+        let rotated = SquareSymmetry::ROTATE_90.apply_grid(&cells, 3, 2);
+        assert_eq!(rotated, (vec![4, 1, 5, 2, 6, 3], 2, 3));
+        let flipped = SquareSymmetry::FLIP_X.apply_grid(&cells, 3, 2);
+        assert_eq!(flipped, (vec![3, 2, 1, 6, 5, 4], 3, 2));
+        for symmetry in SquareSymmetry::all() {
+            let (once, w, h) = symmetry.apply_grid(&cells, 3, 2);
+            let back = symmetry.inverse().apply_grid(&once, w, h);
+            assert_eq!(back, (cells.to_vec(), 3, 2));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    parent: usize,
+    size: usize,
+}
+
+/// Disjoint sets over the indices `0..size`, with union by size and path halving.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    nodes: Vec<Node>,
+    components: usize,
+}
+
+impl UnionFind {
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self {
+            nodes: (0..size).map(|parent| Node { parent, size: 1 }).collect(),
+            components: size,
+        }
+    }
+
+    /// Puts every index back into a set of its own.
+    pub fn reset(&mut self) {
+        for (parent, node) in self.nodes.iter_mut().enumerate() {
+            *node = Node { parent, size: 1 };
+        }
+        self.components = self.nodes.len();
+    }
+
+    /// The representative of the set containing `index`.
+    pub fn find(&mut self, mut index: usize) -> usize {
+        let mut parent = self.nodes[index].parent;
+        while index != parent {
+            let grand_parent = self.nodes[parent].parent;
+            self.nodes[index].parent = grand_parent;
+            index = grand_parent;
+            parent = self.nodes[index].parent;
+        }
+        index
+    }
+
+    /// Like `find`, but without compressing the path.
+    fn root(&self, mut index: usize) -> usize {
+        while self.nodes[index].parent != index {
+            index = self.nodes[index].parent;
+        }
+        index
+    }
+
+    /// Merges the sets containing `index1` and `index2`. Returns `false` if they already were
+    /// the same set.
+    pub fn union(&mut self, mut index1: usize, mut index2: usize) -> bool {
+        index1 = self.find(index1);
+        index2 = self.find(index2);
+        if index1 == index2 {
+            return false;
+        }
+        if self.nodes[index1].size < self.nodes[index2].size {
+            (index1, index2) = (index2, index1);
+        }
+        self.nodes[index2].parent = index1;
+        self.nodes[index1].size += self.nodes[index2].size;
+        self.components -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, index1: usize, index2: usize) -> bool {
+        self.find(index1) == self.find(index2)
+    }
+
+    /// The number of indices in the set containing `index`.
+    pub fn size_of(&mut self, index: usize) -> usize {
+        let root = self.find(index);
+        self.nodes[root].size
+    }
+
+    #[must_use]
+    pub const fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The indices in the same set as `index`, in increasing order.
+    pub fn members(&self, index: usize) -> impl Iterator<Item = usize> {
+        let root = self.root(index);
+        (0..self.nodes.len()).filter(move |&other| self.root(other) == root)
+    }
+
+    /// The size of every set, in no particular order.
+    pub fn root_sizes(&self) -> impl Iterator<Item = usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, node)| (node.parent == ix).then_some(node.size))
+    }
+}
+
+#[cfg(all(kani, feature = "verification"))]
+mod verification {
+    use super::*;
+
+    const SIZE: usize = 4;
+
+    fn any_index() -> usize {
+        let index: usize = kani::any();
+        kani::assume(index < SIZE);
+        index
+    }
+
+    /// Any sets that a few unions can make, with paths of any shape those unions leave.
+    fn any_sets() -> UnionFind {
+        let mut uf = UnionFind::new(SIZE);
+        for _ in 0..SIZE {
+            if kani::any() {
+                uf.union(any_index(), any_index());
+            }
+        }
+        uf
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn find_stops_at_a_root() {
+        let mut uf = any_sets();
+        let index = any_index();
+        let root = uf.root(index);
+        assert_eq!(uf.find(index), root);
+        assert_eq!(uf.nodes[root].parent, root);
+        assert_eq!(uf.root(index), root);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(5)]
+    fn union_adds_sizes() {
+        let mut uf = any_sets();
+        let (index1, index2) = (any_index(), any_index());
+        let (size1, size2) = (uf.size_of(index1), uf.size_of(index2));
+        let components = uf.component_count();
+        if uf.union(index1, index2) {
+            assert_eq!(uf.size_of(index2), size1 + size2);
+            assert_eq!(uf.component_count(), components - 1);
+        } else {
+            assert_eq!(uf.size_of(index2), size2);
+            assert_eq!(uf.component_count(), components);
+        }
+        assert!(uf.same_set(index1, index2));
+        assert_eq!(uf.root_sizes().sum::<usize>(), SIZE);
+        assert_eq!(uf.root_sizes().count(), uf.component_count());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.component_count(), 6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 1));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(4, 5));
+        assert_eq!(uf.component_count(), 3);
+        assert!(uf.same_set(0, 2));
+        assert!(!uf.same_set(0, 3));
+        assert_eq!(uf.size_of(1), 3);
+        assert_eq!(uf.size_of(3), 1);
+        assert_eq!(uf.members(2).collect::<Vec<_>>(), [0, 1, 2]);
+        let mut sizes = uf.root_sizes().collect::<Vec<_>>();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut uf = UnionFind::new(3);
+        uf.union(0, 1);
+        uf.union(1, 2);
+        assert_eq!(uf.component_count(), 1);
+        uf.reset();
+        assert_eq!(uf.component_count(), 3);
+        assert!(!uf.same_set(0, 1));
+        assert_eq!(uf.members(1).collect::<Vec<_>>(), [1]);
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::fetcher::YEAR;
+use crate::input;
+use crate::solver;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AnswersError {
+    #[error("Syntax error on line {0}")]
+    SyntaxError(usize),
+    #[error("Unknown section on line {0}")]
+    UnknownSection(usize),
+    #[error("Unknown key on line {0}")]
+    UnknownKey(usize),
+}
+
+/// Recorded answers, read from a small subset of TOML:
+///
+/// ```toml
+/// [day13]
+/// part1 = 123
+/// part2 = "EFJKZLBL"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    days: BTreeMap<u8, [Option<String>; 2]>,
+}
+
+impl KnownAnswers {
+    #[must_use]
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        answers.get(usize::from(part).checked_sub(1)?)?.as_deref()
+    }
+
+    /// Days that have at least one recorded answer.
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.days.keys().copied()
+    }
+}
+
+impl FromStr for KnownAnswers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeMap::new();
+        let mut current = None;
+        for (line_number, line) in (1..).zip(s.lines()) {
+            let line = content(line);
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[') {
+                let day = section
+                    .strip_suffix(']')
+                    .ok_or(AnswersError::SyntaxError(line_number))?
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .ok_or(AnswersError::UnknownSection(line_number))?;
+                current = Some(days.entry(day).or_insert([None, None]));
+                continue;
+            }
+            let answers = current
+                .as_mut()
+                .ok_or(AnswersError::SyntaxError(line_number))?;
+            parse_entry(line, line_number, answers)?;
+        }
+        Ok(Self { days })
+    }
+}
+
+/// Strips a comment from `line`, and the whitespace around what is left.
+fn content(line: &str) -> &str {
+    line.split_once('#').map_or(line, |(line, _)| line).trim()
+}
+
+/// Reads a `part1 = ...` or `part2 = ...` line into `answers`.
+fn parse_entry(
+    line: &str,
+    line_number: usize,
+    answers: &mut [Option<String>; 2],
+) -> Result<(), AnswersError> {
+    let (key, value) = line
+        .split_once('=')
+        .ok_or(AnswersError::SyntaxError(line_number))?;
+    let slot = match key.trim() {
+        "part1" => &mut answers[0],
+        "part2" => &mut answers[1],
+        _ => return Err(AnswersError::UnknownKey(line_number)),
+    };
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+    *slot = Some(value.to_string());
+    Ok(())
+}
+
+/// Reads the answers of one case, as the `part1` and `part2` keys of a day without its section
+/// header.
+///
+/// # Errors
+///
+/// Returns an error on a line that is not one of the keys.
+pub fn parse_expected(s: &str) -> Result<[Option<String>; 2], AnswersError> {
+    let mut answers = [None, None];
+    for (line_number, line) in (1..).zip(s.lines()) {
+        let line = content(line);
+        if !line.is_empty() {
+            parse_entry(line, line_number, &mut answers)?;
+        }
+    }
+    Ok(answers)
+}
+
+/// Where the recorded answers are kept, next to the cached inputs.
+#[must_use]
+pub fn answers_path(root: &Path) -> PathBuf {
+    root.join(format!("input/{YEAR}/answers.toml"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// The input was missing, or the solver failed.
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+/// Runs every part that has a recorded answer on the cached input, and compares the results.
+#[must_use]
+pub fn verify_day(root: &Path, day: u8, answers: &KnownAnswers) -> Vec<Report> {
+    let parts = (1..=2)
+        .filter_map(|part| Some((part, answers.get(day, part)?)))
+        .collect::<Vec<_>>();
+    check_parts(day, &input::cached_path(root, YEAR, day), &parts)
+}
+
+/// Solves `parts` of `day` on the input at `path`, and compares each answer with the one paired
+/// with it.
+fn check_parts(day: u8, path: &Path, parts: &[(u8, &str)]) -> Vec<Report> {
+    let report = |part, outcome| Report { day, part, outcome };
+    let fail_all = |message: String| {
+        parts
+            .iter()
+            .map(|&(part, _)| report(part, Outcome::Error(message.clone())))
+            .collect()
+    };
+    let Some(solver) = solver::solver(day) else {
+        return fail_all("No solver".to_string());
+    };
+    let input = match input::read_file(path) {
+        Ok(input) => input,
+        Err(error) => return fail_all(format!("Can not read {}: {error}", path.display())),
+    };
+    parts
+        .iter()
+        .map(|&(part, expected)| {
+            let outcome = match solver
+                .solve_part(&input, part)
+                .map(|answer| answer.to_string())
+            {
+                Ok(actual) if actual == expected => Outcome::Pass,
+                Ok(actual) => Outcome::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+                Err(error) => Outcome::Error(error.to_string()),
+            };
+            report(part, outcome)
+        })
+        .collect()
+}
+
+/// Where the golden cases are kept, relative to the root of the repository.
+pub const CASES_DIR: &str = "tests/data";
+
+/// An input with the answers it should give, from `<dir>/dayNN/input/<name>.txt` and
+/// `<dir>/dayNN/expected/<name>.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub day: u8,
+    /// The file name of the input, without the extension.
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: PathBuf,
+}
+
+/// Finds every case under `dir`, ordered by day and name.
+///
+/// Directories that are not named after a day are ignored, as are days without an `input`
+/// directory. A case is found by its input, so that an input without answers is reported
+/// rather than skipped.
+///
+/// # Errors
+///
+/// Returns an error if a directory can not be listed.
+pub fn discover_cases(dir: &Path) -> io::Result<Vec<Case>> {
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let day_dir = entry?.path();
+        let Some(day) = day_dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse::<u8>().ok())
+        else {
+            continue;
+        };
+        let inputs = match fs::read_dir(day_dir.join("input")) {
+            Ok(inputs) => inputs,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        for input in inputs {
+            let input = input?.path();
+            let (Some(name), Some(file_name)) = (
+                input.file_stem().and_then(|name| name.to_str()),
+                input.file_name(),
+            ) else {
+                continue;
+            };
+            cases.push(Case {
+                day,
+                name: name.to_string(),
+                expected: day_dir.join("expected").join(file_name),
+                input: input.clone(),
+            });
+        }
+    }
+    cases.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(cases)
+}
+
+/// Runs a case through its day's solver, and compares the answers of each part it expects.
+#[must_use]
+pub fn check_case(case: &Case) -> Vec<Report> {
+    let expected = fs::read_to_string(&case.expected)
+        .map_err(|error| format!("Can not read {}: {error}", case.expected.display()))
+        .and_then(|expected| {
+            parse_expected(&expected)
+                .map_err(|error| format!("{}: {error}", case.expected.display()))
+        });
+    let expected = match expected {
+        Ok(expected) if expected.iter().any(Option::is_some) => expected,
+        Ok(_) => {
+            let message = format!("No answers in {}", case.expected.display());
+            return check_failed(case.day, &message);
+        }
+        Err(message) => return check_failed(case.day, &message),
+    };
+    let parts = (1..=2)
+        .zip(&expected)
+        .filter_map(|(part, answer)| Some((part, answer.as_deref()?)))
+        .collect::<Vec<_>>();
+    check_parts(case.day, &case.input, &parts)
+}
+
+/// The same error for both parts, when a case can not tell which parts to check.
+fn check_failed(day: u8, message: &str) -> Vec<Report> {
+    (1..=2)
+        .map(|part| Report {
+            day,
+            part,
+            outcome: Outcome::Error(message.to_string()),
+        })
+        .collect()
+}
+
+/// Reads the recorded answers under `root`.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be read or parsed.
+pub fn load_answers(root: &Path) -> io::Result<KnownAnswers> {
+    fs::read_to_string(answers_path(root))?
+        .parse()
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: KnownAnswers = "\
+            # Checked answers\n\
+            [day1]\n\
+            part1 = 7\n\
+            part2 = \"5\" # quoted\n\
+            \n\
+            [day13]\n\
+            part2 = \"EFJKZLBL\"\n\
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("7"));
+        assert_eq!(answers.get(1, 2), Some("5"));
+        assert_eq!(answers.get(13, 1), None);
+        assert_eq!(answers.get(13, 2), Some("EFJKZLBL"));
+        assert_eq!(answers.days().collect::<Vec<_>>(), [1, 13]);
+        assert_eq!(
+            "part1 = 3".parse::<KnownAnswers>(),
+            Err(AnswersError::SyntaxError(1))
+        );
+        assert_eq!(
+            "[day1]\npart3 = 3".parse::<KnownAnswers>(),
+            Err(AnswersError::UnknownKey(2))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "day01")]
+    fn test_verify_day() {
+        let root = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+        let path = crate::fetcher::input_path(&root, YEAR, 1);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+        let answers = "[day1]\npart1 = 7\npart2 = 6\n[day2]\npart1 = 1"
+            .parse()
+            .unwrap();
+        let day1 = verify_day(&root, 1, &answers);
+        let day2 = verify_day(&root, 2, &answers);
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(day1[0].outcome, Outcome::Pass);
+        assert_eq!(
+            day1[1].outcome,
+            Outcome::Fail {
+                expected: "6".to_string(),
+                actual: "5".to_string()
+            }
+        );
+        assert!(matches!(day2[0].outcome, Outcome::Error(_)));
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            parse_expected("# Only part 2\npart2 = \"EFJ\"\n"),
+            Ok([None, Some("EFJ".to_string())])
+        );
+        assert_eq!(parse_expected("[day1]"), Err(AnswersError::SyntaxError(1)));
+    }
+
+    #[test]
+    #[cfg(feature = "day01")]
+    fn test_check_cases() {
+        let dir = std::env::temp_dir().join(format!("aoc-cases-{}", std::process::id()));
+        for (name, input, expected) in [
+            ("pass", "1\n2\n", "part1 = 1"),
+            ("fail", "2\n1\n", "part1 = 1\npart2 = 0"),
+            ("missing", "1\n", ""),
+        ] {
+            fs::create_dir_all(dir.join("day01/input")).unwrap();
+            fs::create_dir_all(dir.join("day01/expected")).unwrap();
+            fs::write(dir.join(format!("day01/input/{name}.txt")), input).unwrap();
+            if !expected.is_empty() {
+                fs::write(dir.join(format!("day01/expected/{name}.txt")), expected).unwrap();
+            }
+        }
+        fs::create_dir_all(dir.join("notes")).unwrap();
+        let cases = discover_cases(&dir).unwrap();
+        let outcomes = cases
+            .iter()
+            .map(|case| {
+                let outcomes = check_case(case).into_iter().map(|report| report.outcome);
+                (case.name.as_str(), outcomes.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(outcomes[0].0, "fail");
+        assert_eq!(
+            outcomes[0].1,
+            [
+                Outcome::Fail {
+                    expected: "1".to_string(),
+                    actual: "0".to_string()
+                },
+                Outcome::Pass
+            ]
+        );
+        assert_eq!(outcomes[1].0, "missing");
+        assert!(matches!(
+            outcomes[1].1[..],
+            [Outcome::Error(_), Outcome::Error(_)]
+        ));
+        assert_eq!(outcomes[2], ("pass", vec![Outcome::Pass]));
+    }
+}
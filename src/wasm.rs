@@ -0,0 +1,50 @@
+//! Bindings for running the solvers from JavaScript, as packaged by `wasm-pack`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::solver;
+
+/// Solves one part of a day. Trailing line breaks in the input are ignored.
+///
+/// # Errors
+///
+/// Throws if the day is not compiled in, there is no such part, or the input is malformed or
+/// has no solution.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    solve_part(day, part, input).map_err(|message| JsError::new(&message))
+}
+
+/// The days that can be solved.
+#[wasm_bindgen]
+#[must_use]
+pub fn days() -> Vec<u8> {
+    solver::solvers().map(|(day, _)| day).collect()
+}
+
+fn solve_part(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solver = solver::solver(day).ok_or_else(|| format!("Day {day} is not available"))?;
+    solver
+        .solve_part(input.trim_end_matches(['\r', '\n']), part)
+        .map(|answer| answer.to_string())
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_part() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(solve_part(1, 1, input), Ok("7".to_string()));
+        assert_eq!(
+            solve_part(1, 3, input),
+            Err("There is no part 3".to_string())
+        );
+        assert_eq!(
+            solve_part(0, 1, input),
+            Err("Day 0 is not available".to_string())
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::io::BufRead;
+use std::num::ParseIntError;
+
+use crate::solver::Solver;
+use crate::util::parse::{
+    LineError, StreamError, normalize, parse_lines_from, parse_lines_lenient,
+};
+
+#[aoc_generator(day1)]
+fn parse(input: &str) -> Result<Vec<u32>, ParseIntError> {
+    parse_iter(input).collect()
+}
+
+/// The depths, each parsed as it is taken, for reading the input in a single pass without
+/// collecting it first.
+pub fn parse_iter(input: &str) -> impl Iterator<Item = Result<u32, ParseIntError>> {
+    input.lines().map(str::parse)
+}
+
+/// Reads the depths that parse, and the errors of the lines that don't, so that one bad line
+/// does not lose the rest.
+#[must_use]
+pub fn parse_lenient(input: &str) -> (Vec<u32>, Vec<LineError<ParseIntError>>) {
+    parse_lines_lenient(input)
+}
+
+#[aoc(day1, part1)]
+fn part_1(depths: &[u32]) -> usize {
+    count_increases(depths, 1)
+}
+
+#[aoc(day1, part2)]
+fn part_2(depths: &[u32]) -> usize {
+    count_increases(depths, 3)
+}
+
+#[aoc(day1, part1, wide)]
+fn part_1_wide(depths: &[u32]) -> usize {
+    count_increases_wide(depths, 1)
+}
+
+#[aoc(day1, part2, wide)]
+fn part_2_wide(depths: &[u32]) -> usize {
+    count_increases_wide(depths, 3)
+}
+
+/// How many sums of `window` consecutive depths are larger than the sum before them.
+///
+/// Two neighbouring windows share all but their first and last depths, so the sum increases
+/// exactly when the depth entering the window is larger than the one leaving it. That compares
+/// depths `window` apart, without adding anything up.
+#[must_use]
+pub fn count_increases(depths: &[u32], window: usize) -> usize {
+    depths
+        .iter()
+        .zip(depths.iter().skip(window))
+        .filter(|&(&leaving, &entering)| entering > leaving)
+        .count()
+}
+
+/// How many depths are compared at once by [`count_increases_wide`].
+const LANES: usize = 16;
+
+/// The same as [`count_increases`], but compares `LANES` pairs of depths at a time.
+///
+/// Each chunk of comparisons is packed into a bit mask and counted with `count_ones`. The loop
+/// over the lanes has a fixed length and no branches, so the compiler turns it into vector
+/// compares and a mask extraction, without `unsafe` or a nightly compiler.
+#[must_use]
+pub fn count_increases_wide(depths: &[u32], window: usize) -> usize {
+    if window == 0 || window >= depths.len() {
+        return 0;
+    }
+    let leaving = &depths[..depths.len() - window];
+    let entering = &depths[window..];
+    let mut leaving_chunks = leaving.chunks_exact(LANES);
+    let mut entering_chunks = entering.chunks_exact(LANES);
+    let mut count = 0;
+    for (leaving, entering) in (&mut leaving_chunks).zip(&mut entering_chunks) {
+        let mut mask = 0_u16;
+        for lane in 0..LANES {
+            mask |= u16::from(entering[lane] > leaving[lane]) << lane;
+        }
+        count += mask.count_ones() as usize;
+    }
+    let rest = leaving_chunks
+        .remainder()
+        .iter()
+        .zip(entering_chunks.remainder())
+        .filter(|&(leaving, entering)| entering > leaving)
+        .count();
+    count + rest
+}
+
+/// Solves both parts in a single pass over `depths`, keeping only the last three.
+///
+/// # Errors
+///
+/// Returns the first error among `depths`.
+pub fn solve_iter<E>(
+    depths: impl IntoIterator<Item = Result<u32, E>>,
+) -> Result<(usize, usize), E> {
+    let mut window = [0_u32; 3];
+    let mut part_1 = 0;
+    let mut part_2 = 0;
+    for (i, depth) in depths.into_iter().enumerate() {
+        let depth = depth?;
+        if i >= 1 && depth > window[(i - 1) % 3] {
+            part_1 += 1;
+        }
+        if i >= 3 && depth > window[i % 3] {
+            part_2 += 1;
+        }
+        window[i % 3] = depth;
+    }
+    Ok((part_1, part_2))
+}
+
+/// Solves both parts in a single pass, keeping only the last three depths.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or a line is not a valid depth.
+pub fn solve_stream(reader: impl BufRead) -> Result<(usize, usize), StreamError<ParseIntError>> {
+    solve_iter(parse_lines_from(reader))
+}
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Parsed = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day01::solve(input)
+}
+
+/// Parses the input and solves both parts like [`solve`], but in a single pass over the depths,
+/// without collecting them.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve_lazy(input: &str) -> Result<(usize, usize), crate::Error> {
+    solve_iter(parse_iter(&normalize(input))).map_err(crate::Error::invalid_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        199\n\
+        200\n\
+        208\n\
+        210\n\
+        200\n\
+        207\n\
+        240\n\
+        269\n\
+        260\n\
+        263\
+    ";
+
+    #[test]
+    fn test_part_1() {
+        let depths = parse(EXAMPLE).unwrap();
+        let result = part_1(&depths);
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let depths = parse(EXAMPLE).unwrap();
+        let result = part_2(&depths);
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_short_input() {
+        assert_eq!(part_1(&[]), 0);
+        assert_eq!(part_2(&[1, 2]), 0);
+    }
+
+    #[test]
+    fn test_count_increases() {
+        let depths = parse(EXAMPLE).unwrap();
+        let sums = |window| {
+            depths
+                .windows(window)
+                .map(|w| w.iter().sum::<u32>())
+                .collect::<Vec<_>>()
+        };
+        for window in 1..=depths.len() {
+            let expected = sums(window).windows(2).filter(|w| w[1] > w[0]).count();
+            assert_eq!(
+                count_increases(&depths, window),
+                expected,
+                "window {window}"
+            );
+        }
+        assert_eq!(count_increases(&depths, 0), 0);
+    }
+
+    #[test]
+    fn test_count_increases_wide() {
+        let depths = (0..100_u32).map(|i| i * 37 % 101).collect::<Vec<_>>();
+        for window in 0..=depths.len() {
+            for len in [0, 1, LANES, LANES + 1, 2 * LANES + 3, depths.len()] {
+                let depths = &depths[..len];
+                assert_eq!(
+                    count_increases_wide(depths, window),
+                    count_increases(depths, window),
+                    "window {window}, length {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        let result = solve_stream(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(result, (7, 5));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(1) {
+            let depths = parse(&input).unwrap();
+            let expected = (part_1(&depths), part_2(&depths));
+            assert_eq!(
+                (part_1_wide(&depths), part_2_wide(&depths)),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(
+                solve_stream(input.as_bytes()).unwrap(),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(solve_lazy(&input).unwrap(), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_lazy() {
+        assert_eq!(solve_lazy(EXAMPLE).unwrap(), (7, 5));
+        assert_eq!(solve_lazy(&untidy(EXAMPLE)).unwrap(), (7, 5));
+        assert!(solve_lazy("199\nx\n200").is_err());
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let (depths, errors) = parse_lenient("199\n200\nx\n210\n-1\n207");
+        assert_eq!(depths, [199, 200, 210, 207]);
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            [3, 5]
+        );
+        assert_eq!(
+            parse_lenient(EXAMPLE),
+            (parse(EXAMPLE).unwrap(), Vec::new())
+        );
+    }
+}
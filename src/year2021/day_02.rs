@@ -0,0 +1,420 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::parse::{
+    LineError, NumberError, StreamError, byte_lines, normalize, parse_lines_from,
+    parse_lines_lenient, parse_unsigned, split_once_bytes,
+};
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] NumberError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Command {
+    Forward(u32),
+    Up(u32),
+    Down(u32),
+}
+
+impl Command {
+    /// Reads a command such as `forward 5`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless `s` is a direction and a distance, separated by a space.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, ParseError> {
+        let (command, dist) = split_once_bytes(s, b" ").ok_or(ParseError::SyntaxError)?;
+        Ok(match command {
+            b"forward" => Self::Forward(parse_unsigned(dist)?),
+            b"up" => Self::Up(parse_unsigned(dist)?),
+            b"down" => Self::Down(parse_unsigned(dist)?),
+            _ => return Err(ParseError::SyntaxError),
+        })
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forward(dist) => write!(f, "forward {dist}"),
+            Self::Up(dist) => write!(f, "up {dist}"),
+            Self::Down(dist) => write!(f, "down {dist}"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+#[aoc_generator(day2)]
+fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_iter(input).collect()
+}
+
+/// The commands, each parsed as it is taken, for reading the input in a single pass without
+/// collecting it first.
+pub fn parse_iter(input: &str) -> impl Iterator<Item = Result<Command, ParseError>> {
+    byte_lines(input.as_bytes()).map(Command::from_bytes)
+}
+
+/// Reads the commands that parse, and the errors of the lines that don't, so that one bad line
+/// does not lose the rest.
+#[must_use]
+pub fn parse_lenient(input: &str) -> (Vec<Command>, Vec<LineError<ParseError>>) {
+    parse_lines_lenient(input)
+}
+
+/// Where the submarine is. Depths above the surface are negative.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Position {
+    horizontal: i64,
+    depth: i64,
+    aim: i64,
+}
+
+impl Position {
+    /// The position after a command that moves the depth directly, as in part 1. Returns `None`
+    /// if it overflows.
+    fn moved(self, command: Command) -> Option<Self> {
+        Some(match command {
+            Command::Forward(dist) => Self {
+                horizontal: self.horizontal.checked_add(dist.into())?,
+                ..self
+            },
+            Command::Up(dist) => Self {
+                depth: self.depth.checked_sub(dist.into())?,
+                ..self
+            },
+            Command::Down(dist) => Self {
+                depth: self.depth.checked_add(dist.into())?,
+                ..self
+            },
+        })
+    }
+
+    /// The position after a command that steers with the aim, as in part 2. Returns `None` if it
+    /// overflows.
+    fn steered(self, command: Command) -> Option<Self> {
+        Some(match command {
+            Command::Forward(dist) => Self {
+                horizontal: self.horizontal.checked_add(dist.into())?,
+                depth: self.depth.checked_add(self.aim.checked_mul(dist.into())?)?,
+                ..self
+            },
+            Command::Up(dist) => Self {
+                aim: self.aim.checked_sub(dist.into())?,
+                ..self
+            },
+            Command::Down(dist) => Self {
+                aim: self.aim.checked_add(dist.into())?,
+                ..self
+            },
+        })
+    }
+
+    const fn product(self) -> Option<i64> {
+        self.horizontal.checked_mul(self.depth)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "horizontal {}, depth {}, aim {}",
+            self.horizontal, self.depth, self.aim
+        )
+    }
+}
+
+/// Returns `None` if the position overflows.
+#[aoc(day2, part1)]
+fn part_1(commands: &[Command]) -> Option<i64> {
+    commands
+        .iter()
+        .try_fold(Position::default(), |position, &command| {
+            position.moved(command)
+        })?
+        .product()
+}
+
+/// Returns `None` if the position overflows.
+#[aoc(day2, part2)]
+fn part_2(commands: &[Command]) -> Option<i64> {
+    commands
+        .iter()
+        .try_fold(Position::default(), |position, &command| {
+            position.steered(command)
+        })?
+        .product()
+}
+
+/// Solves both parts in a single pass over `commands`. A part is `None` if its position
+/// overflows.
+///
+/// # Errors
+///
+/// Returns the first error among `commands`.
+pub fn solve_iter<E>(
+    commands: impl IntoIterator<Item = Result<Command, E>>,
+) -> Result<(Option<i64>, Option<i64>), E> {
+    let mut part_1 = Some(Position::default());
+    let mut part_2 = Some(Position::default());
+    for command in commands {
+        let command = command?;
+        part_1 = part_1.and_then(|position| position.moved(command));
+        part_2 = part_2.and_then(|position| position.steered(command));
+    }
+    Ok((
+        part_1.and_then(Position::product),
+        part_2.and_then(Position::product),
+    ))
+}
+
+/// Solves both parts in a single pass without collecting the commands. A part is `None` if its
+/// position overflows.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or a line is not a valid command.
+pub fn solve_stream(
+    reader: impl BufRead,
+) -> Result<(Option<i64>, Option<i64>), StreamError<ParseError>> {
+    solve_iter(parse_lines_from(reader))
+}
+
+/// Steers the submarine as in part 2, writing where it is after each command read from `reader`.
+///
+/// Lines that are not commands, and commands that would take the submarine out of range, are
+/// reported and skipped.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails.
+pub fn pilot(reader: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    let mut position = Position::default();
+    for command in parse_lines_from::<_, Command>(reader) {
+        match command {
+            Ok(command) => match position.steered(command) {
+                Some(next) => {
+                    position = next;
+                    writeln!(out, "{position}")?;
+                }
+                None => writeln!(out, "Out of range, skipped")?,
+            },
+            Err(StreamError::Io(error)) => return Err(error),
+            Err(StreamError::Parse(error)) => writeln!(out, "{error}, skipped")?,
+        }
+    }
+    Ok(())
+}
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Parsed = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or the submarine's position overflows.
+pub fn solve(input: &str) -> Result<(i64, i64), crate::Error> {
+    Day02::solve(input)
+}
+
+/// Parses the input and solves both parts like [`solve`], but in a single pass over the
+/// commands, without collecting them.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or the submarine's position overflows.
+pub fn solve_lazy(input: &str) -> Result<(i64, i64), crate::Error> {
+    match solve_iter(parse_iter(&normalize(input))) {
+        Ok((Some(part_1), Some(part_2))) => Ok((part_1, part_2)),
+        Ok(_) => Err(crate::Error::NoSolution),
+        Err(error) => Err(crate::Error::invalid_input(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::join_lines;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        forward 5\n\
+        down 5\n\
+        forward 8\n\
+        up 3\n\
+        down 8\n\
+        forward 2\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let result = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            result,
+            [
+                Command::Forward(5),
+                Command::Down(5),
+                Command::Forward(8),
+                Command::Up(3),
+                Command::Down(8),
+                Command::Forward(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let commands = parse(EXAMPLE).unwrap();
+        assert_eq!(join_lines(&commands), EXAMPLE);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let commands = parse(EXAMPLE).unwrap();
+        let result = part_1(&commands);
+        assert_eq!(result, Some(150));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let commands = parse(EXAMPLE).unwrap();
+        let result = part_2(&commands);
+        assert_eq!(result, Some(900));
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        let result = solve_stream(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(result, (Some(150), Some(900)));
+    }
+
+    #[test]
+    fn test_pilot() {
+        let mut out = Vec::new();
+        pilot(
+            "forward 5\ndown 5\nsideways 1\n\nforward 8\nforward 4294967295".as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "horizontal 5, depth 0, aim 0\n\
+            horizontal 5, depth 0, aim 5\n\
+            line 3: Syntax error, skipped\n\
+            horizontal 13, depth 40, aim 5\n\
+            horizontal 4294967308, depth 21474836515, aim 5\n"
+        );
+    }
+
+    #[test]
+    fn test_above_surface() {
+        let commands = parse("up 3\nforward 2\ndown 1\nforward 4").unwrap();
+        assert_eq!(part_1(&commands), Some(-12));
+        assert_eq!(part_2(&commands), Some(-84));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // The aim reaches 2^32 - 1, which takes the part 2 depth past `i64::MAX` after 2^31 + 1
+        // meters, while the part 1 depth ends back at the surface.
+        let input = "down 4294967295\nforward 2147483647\nforward 2\nup 4294967295";
+        let commands = parse(input).unwrap();
+        assert_eq!(part_1(&commands), Some(0));
+        assert_eq!(part_2(&commands), None);
+        assert_eq!(solve_stream(input.as_bytes()).unwrap(), (Some(0), None));
+        assert!(matches!(solve(input), Err(crate::Error::NoSolution)));
+        assert!(matches!(solve_lazy(input), Err(crate::Error::NoSolution)));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(2) {
+            let commands = parse(&input).unwrap();
+            let expected = (part_1(&commands), part_2(&commands));
+            assert_eq!(
+                solve_stream(input.as_bytes()).unwrap(),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(
+                solve_lazy(&input).ok(),
+                expected.0.zip(expected.1),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_lazy() {
+        assert_eq!(solve_lazy(EXAMPLE).unwrap(), (150, 900));
+        assert_eq!(solve_lazy(&untidy(EXAMPLE)).unwrap(), (150, 900));
+        assert!(matches!(
+            solve_lazy("forward 5\nbackward 2"),
+            Err(crate::Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let (commands, errors) = parse_lenient("forward 5\nbackward 2\ndown 5\nup\nup 3");
+        assert_eq!(
+            commands,
+            [Command::Forward(5), Command::Down(5), Command::Up(3)]
+        );
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                LineError {
+                    line: 2,
+                    error: ParseError::SyntaxError
+                },
+                LineError {
+                    line: 4,
+                    error: ParseError::SyntaxError
+                },
+            ]
+        ));
+    }
+}
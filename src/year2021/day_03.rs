@@ -0,0 +1,520 @@
+use std::fmt::{self, Display};
+
+use thiserror::Error;
+
+use crate::explain::Explain;
+#[cfg(feature = "arena")]
+use crate::solver::Arena;
+use crate::solver::Solver;
+#[cfg(feature = "arena")]
+use crate::util::parse::collect_exact;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Numbers must have 1 to 64 bits, all the same")]
+    InvalidWidth,
+}
+
+/// The diagnostic report, with each number packed into the low `width` bits of a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    numbers: Vec<u64>,
+    width: usize,
+}
+
+impl Report {
+    /// The numbers, each in the low `width` bits.
+    #[must_use]
+    pub fn numbers(&self) -> &[u64] {
+        &self.numbers
+    }
+
+    /// How many bits each number has.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Whether bit `index` of `number` is set, counting from the most significant of the `width`
+    /// bits, the way the puzzle counts them.
+    #[must_use]
+    pub const fn bit(&self, number: u64, index: usize) -> bool {
+        number >> (self.width - 1 - index) & 1 == 1
+    }
+
+    /// How many numbers have a 1 in each bit, most significant first.
+    ///
+    /// The numbers are taken 64 at a time and transposed as a 64 by 64 bit matrix, so that each
+    /// row holds one bit of all 64 numbers and is counted with a single `count_ones`.
+    #[must_use]
+    pub fn ones(&self) -> Vec<usize> {
+        let mut counts = [0; 64];
+        for chunk in self.numbers.chunks(64) {
+            let mut block = [0; 64];
+            block[..chunk.len()].copy_from_slice(chunk);
+            transpose(&mut block);
+            // After transposing, bit `b` of every number is in row `63 - b`.
+            for (count, row) in counts.iter_mut().zip(block.iter().rev()) {
+                *count += row.count_ones() as usize;
+            }
+        }
+        counts[..self.width].iter().rev().copied().collect()
+    }
+}
+
+/// Transposes a 64 by 64 bit matrix in place, with row `r` in `block[r]` and column `c` as bit
+/// `63 - c` of each row. Quarters are swapped, then the quarters of those, down to single bits.
+const fn transpose(block: &mut [u64; 64]) {
+    let mut size = 32;
+    let mut mask = 0x0000_0000_FFFF_FFFF_u64;
+    while size != 0 {
+        let mut row = 0;
+        while row < 64 {
+            let swap = (block[row] ^ (block[row + size] >> size)) & mask;
+            block[row] ^= swap;
+            block[row + size] ^= swap << size;
+            row = (row + size + 1) & !size;
+        }
+        size >>= 1;
+        mask ^= mask << size;
+    }
+}
+
+#[aoc_generator(day3)]
+fn parse(input: &str) -> Result<Report, ParseError> {
+    let (width, numbers) = numbers(input)?;
+    let numbers = numbers.collect::<Result<_, _>>()?;
+    Ok(Report { numbers, width })
+}
+
+/// Like [`parse`], with the numbers collected in `arena` until their count is known.
+#[cfg(feature = "arena")]
+fn parse_in(input: &str, arena: &Arena) -> Result<Report, ParseError> {
+    let (width, numbers) = numbers(input)?;
+    let numbers = collect_exact(numbers, arena)?;
+    Ok(Report { numbers, width })
+}
+
+/// The width of the numbers, taken from the first, and each number as it is read.
+fn numbers(
+    input: &str,
+) -> Result<(usize, impl Iterator<Item = Result<u64, ParseError>>), ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    if !(1..=64).contains(&width) {
+        return Err(ParseError::InvalidWidth);
+    }
+    let numbers = input.lines().map(move |s| {
+        if !s.bytes().all(|b| matches!(b, b'0' | b'1')) {
+            Err(ParseError::SyntaxError)
+        } else if s.len() != width {
+            Err(ParseError::InvalidWidth)
+        } else {
+            Ok(s.bytes()
+                .fold(0, |sum, bit| (sum << 1) | u64::from(bit == b'1')))
+        }
+    });
+    Ok((width, numbers))
+}
+
+/// A rate read from the report, in the low `width` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    pub value: u64,
+    pub width: usize,
+}
+
+impl Rate {
+    /// The bits as the report writes them, most significant first, such as `10110`.
+    #[must_use]
+    pub fn bits(self) -> String {
+        format!("{:0width$b}", self.value, width = self.width)
+    }
+}
+
+/// Written as the bits and the value, such as `10110 = 22`.
+impl Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.bits(), self.value)
+    }
+}
+
+/// Every rate the report gives, of which the answers are products.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// The most common bit in each position.
+    pub gamma: Rate,
+    /// The least common bit in each position.
+    pub epsilon: Rate,
+    /// The number left by keeping the most common bits, or `None` if none is left.
+    pub oxygen: Option<Rate>,
+    /// The number left by keeping the least common bits, or `None` if none is left.
+    pub co2: Option<Rate>,
+}
+
+impl Diagnostics {
+    /// The answer to part 1.
+    #[must_use]
+    pub fn power_consumption(&self) -> u128 {
+        u128::from(self.gamma.value) * u128::from(self.epsilon.value)
+    }
+
+    /// The answer to part 2, if both ratings were found.
+    #[must_use]
+    pub fn life_support(&self) -> Option<u128> {
+        Some(u128::from(self.oxygen?.value) * u128::from(self.co2?.value))
+    }
+}
+
+/// Reads every rate from the report.
+#[must_use]
+pub fn diagnostics(report: &Report) -> Diagnostics {
+    let (gamma, epsilon) = rates(report);
+    Diagnostics {
+        gamma,
+        epsilon,
+        oxygen: rating(report, true),
+        co2: rating(report, false),
+    }
+}
+
+/// The gamma and epsilon rates: the most and the least common bits.
+fn rates(report: &Report) -> (Rate, Rate) {
+    let total = report.numbers.len();
+    let gamma_rate = report
+        .ones()
+        .iter()
+        .fold(0, |sum, &count| (sum << 1) | u64::from(count * 2 >= total));
+    let epsilon_rate = (u64::MAX >> (64 - report.width)) ^ gamma_rate;
+    let rate = |value| Rate {
+        value,
+        width: report.width,
+    };
+    (rate(gamma_rate), rate(epsilon_rate))
+}
+
+#[aoc(day3, part1)]
+fn part_1(report: &Report) -> u128 {
+    let (gamma, epsilon) = rates(report);
+    u128::from(gamma.value) * u128::from(epsilon.value)
+}
+
+/// Counts the ones of each number bit by bit, the way the puzzle describes it.
+#[aoc(day3, part1, bitwise)]
+fn part_1_bitwise(report: &Report) -> u128 {
+    let total = report.numbers.len();
+    let gamma_rate = (0..report.width).fold(0, |sum, index| {
+        let ones = report
+            .numbers
+            .iter()
+            .filter(|&&number| report.bit(number, index))
+            .count();
+        (sum << 1) | u64::from(ones * 2 >= total)
+    });
+    let epsilon_rate = (u64::MAX >> (64 - report.width)) ^ gamma_rate;
+    u128::from(gamma_rate) * u128::from(epsilon_rate)
+}
+
+/// `None` if filtering leaves no number, which happens when a bit is the same in every number
+/// that is left.
+///
+/// Each rating partitions references to the numbers in place, bit by bit, and narrows down to
+/// the half it keeps. The halves shrink geometrically, so this takes linear time for random
+/// input.
+#[aoc(day3, part2)]
+fn part_2(report: &Report) -> Option<u128> {
+    Some(u128::from(rating(report, true)?.value) * u128::from(rating(report, false)?.value))
+}
+
+/// The oxygen generator rating when keeping the `most_common` bits, and the CO₂ scrubber rating
+/// otherwise.
+fn rating(report: &Report, most_common: bool) -> Option<Rate> {
+    let mut numbers = report.numbers.clone();
+    let mut numbers = numbers.as_mut_slice();
+    for index in 0..report.width {
+        if numbers.len() <= 1 {
+            break;
+        }
+        let split = partition(report, numbers, index);
+        let (zeros, ones) = numbers.split_at_mut(split);
+        numbers = if (zeros.len() <= ones.len()) == most_common {
+            ones
+        } else {
+            zeros
+        };
+    }
+    numbers.first().map(|&value| Rate {
+        value,
+        width: report.width,
+    })
+}
+
+/// Moves the numbers with a 0 at `index` to the front, and returns how many there are.
+fn partition(report: &Report, numbers: &mut [u64], index: usize) -> usize {
+    let mut zeros = 0;
+    for next in 0..numbers.len() {
+        if !report.bit(numbers[next], index) {
+            numbers.swap(zeros, next);
+            zeros += 1;
+        }
+    }
+    zeros
+}
+
+/// Sorts the numbers, so that each filtering step keeps a prefix or suffix of the numbers left.
+#[aoc(day3, part2, sorted)]
+fn part_2_sorted(report: &Report) -> Option<u128> {
+    let mut numbers = report.numbers.clone();
+    numbers.sort_unstable();
+    let oxygen_rating = get_rating(report, &numbers, true)?;
+    let co2_rating = get_rating(report, &numbers, false)?;
+    Some(u128::from(oxygen_rating) * u128::from(co2_rating))
+}
+
+fn get_rating(report: &Report, mut numbers: &[u64], upper: bool) -> Option<u64> {
+    for index in 0..report.width {
+        let zeros = numbers
+            .iter()
+            .take_while(|&&num| !report.bit(num, index))
+            .count();
+        let ones = numbers.len() - zeros;
+        if (zeros <= ones) ^ upper {
+            numbers = &numbers[..zeros];
+        } else {
+            numbers = &numbers[zeros..];
+        }
+        if numbers.len() <= 1 {
+            break;
+        }
+    }
+    numbers.first().copied()
+}
+
+/// Filters the numbers bit by bit, counting each time, without sorting them first.
+#[aoc(day3, part2, naive)]
+fn part_2_naive(report: &Report) -> Option<u128> {
+    let rating = |most_common: bool| {
+        let mut numbers = report.numbers.clone();
+        for index in 0..report.width {
+            if numbers.len() <= 1 {
+                break;
+            }
+            let ones = numbers
+                .iter()
+                .filter(|&&num| report.bit(num, index))
+                .count();
+            let keep = (2 * ones >= numbers.len()) == most_common;
+            numbers.retain(|&num| report.bit(num, index) == keep);
+        }
+        numbers.first().map(|&number| u128::from(number))
+    };
+    Some(rating(true)? * rating(false)?)
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Parsed = Report;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    #[cfg(feature = "arena")]
+    fn parse_in(input: &str, arena: &Arena) -> Result<Self::Parsed, crate::Error> {
+        parse_in(input, arena).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        let diagnostics = diagnostics(parsed);
+        Ok((
+            diagnostics.power_consumption(),
+            diagnostics.life_support().ok_or(crate::Error::NoSolution)?,
+        ))
+    }
+
+    fn explain(parsed: &Self::Parsed, explain: &mut Explain) {
+        let diagnostics = diagnostics(parsed);
+        explain.step("gamma rate", diagnostics.gamma);
+        explain.step("epsilon rate", diagnostics.epsilon);
+        for (title, rating) in [
+            ("oxygen generator rating", diagnostics.oxygen),
+            ("CO₂ scrubber rating", diagnostics.co2),
+        ] {
+            match rating {
+                Some(rating) => explain.step(title, rating),
+                None => explain.step(title, "no number left"),
+            }
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(u128, u128), crate::Error> {
+    Day03::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        00100\n\
+        11110\n\
+        10110\n\
+        10111\n\
+        10101\n\
+        01111\n\
+        00111\n\
+        11100\n\
+        10000\n\
+        11001\n\
+        00010\n\
+        01010\
+    ";
+
+    #[test]
+    fn test_part_1() {
+        let numbers = parse(EXAMPLE).unwrap();
+        let result = part_1(&numbers);
+        assert_eq!(result, 198);
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut explain = Explain::new();
+        Day03::explain(&parse(EXAMPLE).unwrap(), &mut explain);
+        assert_eq!(
+            explain.to_string(),
+            "gamma rate: 10110 = 22\n\
+             epsilon rate: 01001 = 9\n\
+             oxygen generator rating: 10111 = 23\n\
+             CO₂ scrubber rating: 01010 = 10\n"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let report = parse(EXAMPLE).unwrap();
+        let diagnostics = diagnostics(&report);
+        assert_eq!(
+            diagnostics.gamma,
+            Rate {
+                value: 22,
+                width: 5
+            }
+        );
+        assert_eq!(diagnostics.epsilon.bits(), "01001");
+        assert_eq!(diagnostics.oxygen.map(Rate::bits).as_deref(), Some("10111"));
+        assert_eq!(diagnostics.co2.map(|rate| rate.value), Some(10));
+        assert_eq!(diagnostics.power_consumption(), part_1(&report));
+        assert_eq!(diagnostics.life_support(), part_2(&report));
+        let same = super::diagnostics(&parse("101\n101").unwrap());
+        assert_eq!(same.co2, None);
+        assert_eq!(same.life_support(), None);
+    }
+
+    #[test]
+    fn test_ones() {
+        let report = parse(EXAMPLE).unwrap();
+        assert_eq!(report.width(), 5);
+        assert_eq!(report.numbers()[..3], [0b00100, 0b11110, 0b10110]);
+        assert_eq!(report.ones(), [7, 5, 8, 7, 5]);
+        assert!(report.bit(0b00100, 2));
+        assert!(!report.bit(0b00100, 4));
+        let report = parse(&geninput::generate(3, 3, 7).unwrap()).unwrap();
+        let naive = (0..report.width())
+            .map(|index| {
+                let ones = report.numbers().iter().filter(|&&n| report.bit(n, index));
+                ones.count()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(report.ones(), naive);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let mut block = [0_u64; 64];
+        block[0] = 1 << 63 | 1;
+        block[5] = 1 << 60;
+        transpose(&mut block);
+        let mut expected = [0_u64; 64];
+        expected[0] = 1 << 63;
+        expected[3] = 1 << 58;
+        expected[63] = 1 << 63;
+        assert_eq!(block, expected);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let numbers = parse(EXAMPLE).unwrap();
+        let result = part_2(&numbers);
+        assert_eq!(result, Some(230));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("").is_err());
+        assert!(parse("0101\n012").is_err());
+        assert!(parse("0101\n011").is_err());
+        assert!(parse("0101010101010").is_ok());
+        assert!(parse(&"1".repeat(64)).is_ok());
+        assert!(parse(&"1".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_wide_numbers() {
+        let ones = "1".repeat(64);
+        let input = format!("0{}\n1{}\n{ones}", &ones[1..], "0".repeat(63));
+        let numbers = parse(&input).unwrap();
+        let (gamma_rate, epsilon_rate) = (u64::MAX, 0);
+        assert_eq!(part_1(&numbers), u128::from(gamma_rate) * epsilon_rate);
+        assert_eq!(part_1_bitwise(&numbers), part_1(&numbers));
+        let (oxygen_rating, co2_rating) = (u64::MAX, u64::MAX >> 1);
+        let expected = u128::from(oxygen_rating) * u128::from(co2_rating);
+        assert_eq!(part_2(&numbers), Some(expected));
+        assert_eq!(part_2_sorted(&numbers), Some(expected));
+        assert_eq!(part_2_naive(&numbers), Some(expected));
+    }
+
+    #[test]
+    fn test_part_2_no_rating() {
+        let numbers = parse("10\n11").unwrap();
+        assert_eq!(part_2(&numbers), None);
+        assert_eq!(part_2_sorted(&numbers), None);
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(3) {
+            let numbers = parse(&input).unwrap();
+            assert_eq!(part_1_bitwise(&numbers), part_1(&numbers), "seed {seed}");
+            let expected = part_2(&numbers);
+            assert_eq!(part_2_sorted(&numbers), expected, "seed {seed}");
+            assert_eq!(part_2_naive(&numbers), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,727 @@
+use std::fmt::{Display, Write as _};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::explain::Explain;
+#[cfg(feature = "arena")]
+use crate::solver::Arena;
+use crate::solver::Solver;
+use crate::util::animation::Pace;
+use crate::util::bitboard::BitBoard;
+#[cfg(feature = "arena")]
+use crate::util::parse::collect_exact;
+use crate::util::parse::{NumberError, byte_lines, parse_unsigned};
+use crate::util::style::Style;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] NumberError),
+    #[error("Boards must be square, from 1×1 to 11×11, all the same size")]
+    InvalidSize,
+}
+
+/// The side of the largest board, whose marks just fit in a `BitBoard`.
+const MAX_SIZE: usize = 11;
+
+/// In `Board::cells`, for a number that is not on the board. Boards have at most 121 cells.
+const NOT_ON_BOARD: u8 = u8::MAX;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bingo {
+    numbers: Vec<u8>,
+    boards: Vec<Board>,
+}
+
+impl Bingo {
+    #[must_use]
+    pub const fn new(numbers: Vec<u8>, boards: Vec<Board>) -> Self {
+        Self { numbers, boards }
+    }
+
+    #[must_use]
+    pub fn numbers(&self) -> &[u8] {
+        &self.numbers
+    }
+
+    pub const fn numbers_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.numbers
+    }
+
+    #[must_use]
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    pub const fn boards_mut(&mut self) -> &mut Vec<Board> {
+        &mut self.boards
+    }
+}
+
+impl Display for Bingo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, number) in self.numbers.iter().enumerate() {
+            let separator = if index > 0 { "," } else { "" };
+            write!(f, "{separator}{number}")?;
+        }
+        for board in &self.boards {
+            write!(f, "\n\n{board}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Bingo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split("\n\n");
+        let numbers = parts
+            .next()
+            .ok_or(ParseError::SyntaxError)?
+            .as_bytes()
+            .split(|&b| b == b',')
+            .map(parse_unsigned)
+            .collect::<Result<_, _>>()?;
+        let boards = parts.map(str::parse).collect::<Result<Vec<Board>, _>>()?;
+        Self::checked(numbers, boards)
+    }
+}
+
+impl Bingo {
+    /// Fails unless the boards are all the same size.
+    fn checked(numbers: Vec<u8>, boards: Vec<Board>) -> Result<Self, ParseError> {
+        if boards.windows(2).any(|pair| pair[0].size != pair[1].size) {
+            return Err(ParseError::InvalidSize);
+        }
+        Ok(Self { numbers, boards })
+    }
+
+    /// Like `str::parse`, with the numbers and boards collected in `arena` until their counts
+    /// are known.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the numbers or a board are malformed, or the boards differ in size.
+    #[cfg(feature = "arena")]
+    pub fn parse_in(s: &str, arena: &Arena) -> Result<Self, ParseError> {
+        let mut parts = s.split("\n\n");
+        let numbers = parts.next().ok_or(ParseError::SyntaxError)?.as_bytes();
+        let numbers = collect_exact(numbers.split(|&b| b == b',').map(parse_unsigned), arena)?;
+        let boards = collect_exact(parts.map(|part| Board::parse_in(part, arena)), arena)?;
+        Self::checked(numbers, boards)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Board {
+    size: usize,
+    grid: Vec<u8>,
+    /// The cell of each number from 0 to 255, or `NOT_ON_BOARD`, so that marking a number does
+    /// not search the grid for it.
+    cells: Vec<u8>,
+    marks: BitBoard,
+}
+
+impl Board {
+    /// An unmarked board with the given numbers in row-major order. Returns `None` unless there
+    /// is a square number of them, from 1×1 to 11×11.
+    #[must_use]
+    pub fn new(grid: Vec<u8>) -> Option<Self> {
+        let size = grid.len().isqrt();
+        if size * size != grid.len() || !(1..=MAX_SIZE).contains(&size) {
+            return None;
+        }
+        let mut cells = vec![NOT_ON_BOARD; 256];
+        // A number that is on the board twice is found in its first cell.
+        for (cell, &number) in (0..).zip(&grid) {
+            if cells[usize::from(number)] == NOT_ON_BOARD {
+                cells[usize::from(number)] = cell;
+            }
+        }
+        Some(Self {
+            size,
+            grid,
+            cells,
+            marks: BitBoard::empty(size, size),
+        })
+    }
+
+    /// The number of rows, and of columns.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    #[must_use]
+    pub fn grid(&self) -> &[u8] {
+        &self.grid
+    }
+
+    /// The marked cells, with bit `row * size + col` set for each.
+    #[must_use]
+    pub const fn marks(&self) -> u128 {
+        self.marks.bits()
+    }
+
+    #[must_use]
+    pub const fn is_marked(&self, index: usize) -> bool {
+        self.marks.contains_index(index)
+    }
+
+    pub fn mark(&mut self, num: u8) {
+        self.mark_completes(num);
+    }
+
+    /// Marks `num`, if the board has it, and returns whether that completes its row or column.
+    /// Only that row and column are checked, so this tells whether the board has just won.
+    pub fn mark_completes(&mut self, num: u8) -> bool {
+        let ix = self.cells[usize::from(num)];
+        if ix == NOT_ON_BOARD {
+            return false;
+        }
+        let ix = usize::from(ix);
+        self.marks.insert_index(ix);
+        self.marks.is_row_full(ix / self.size) || self.marks.is_column_full(ix % self.size)
+    }
+
+    #[must_use]
+    pub fn has_bingo(&self) -> bool {
+        self.marks.has_full_row() || self.marks.has_full_column()
+    }
+
+    #[must_use]
+    pub fn sum_unmarked(&self) -> u32 {
+        self.grid
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, &val)| (!self.is_marked(ix)).then_some(u32::from(val)))
+            .sum()
+    }
+}
+
+impl FromStr for Board {
+    type Err = ParseError;
+
+    /// Reads a square of numbers, as many rows as there are numbers in each. The numbers go
+    /// straight into the board, without a list for each row.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid = Vec::new();
+        read_square(s, |number| grid.push(number))?;
+        Self::new(grid).ok_or(ParseError::InvalidSize)
+    }
+}
+
+/// Hands each number of a square of them to `push`, row by row.
+fn read_square(s: &str, mut push: impl FnMut(u8)) -> Result<(), ParseError> {
+    let mut rows = 0;
+    let mut width = None;
+    for line in byte_lines(s.as_bytes()) {
+        let mut count = 0;
+        for number in line.split(u8::is_ascii_whitespace) {
+            if !number.is_empty() {
+                push(parse_unsigned(number)?);
+                count += 1;
+            }
+        }
+        if *width.get_or_insert(count) != count {
+            return Err(ParseError::SyntaxError);
+        }
+        rows += 1;
+    }
+    if width.is_some_and(|width| width != rows) {
+        return Err(ParseError::SyntaxError);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "arena")]
+impl Board {
+    /// Like `str::parse`, with the numbers collected in `arena` until the size is known.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the numbers are malformed or do not make a square.
+    pub fn parse_in(s: &str, arena: &Arena) -> Result<Self, ParseError> {
+        let mut grid = bumpalo::collections::Vec::new_in(arena);
+        read_square(s, |number| grid.push(number))?;
+        Self::new(grid.to_vec()).ok_or(ParseError::InvalidSize)
+    }
+}
+
+impl Board {
+    /// The numbers, with the marked ones bright and the others grey. A plain `style` follows the
+    /// marked ones with `*` instead.
+    #[must_use]
+    pub fn render(&self, style: Style) -> String {
+        let mut result = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let ix = self.size * y + x;
+                let val = self.grid[ix];
+                let marked = self.is_marked(ix);
+                let (color, mark) = if marked { ("97", '*') } else { ("90", ' ') };
+                let mark = if style.is_color() { ' ' } else { mark };
+                // Writing to a `String` can not fail.
+                let _ = write!(result, "{:2}{mark}", style.paint(color, val));
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.grid.chunks(self.size).enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for (col, number) in row.iter().enumerate() {
+                let separator = if col > 0 { " " } else { "" };
+                write!(f, "{separator}{number:2}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[aoc_generator(day4)]
+fn parse(input: &str) -> Result<Bingo, ParseError> {
+    input.parse()
+}
+
+/// A board completing a row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinEvent {
+    /// The board's index in the input.
+    pub board: usize,
+    /// The number that completed the row or column.
+    pub number: u8,
+    /// The index of that number in the draw order.
+    pub draw: usize,
+    pub score: u32,
+}
+
+/// Marks `number` on every board that has not won yet, and records those that win with it.
+fn draw(boards: &mut [Board], draw: usize, number: u8, wins: &mut Vec<WinEvent>) {
+    for (index, board) in boards.iter_mut().enumerate() {
+        if board.has_bingo() {
+            continue;
+        }
+        if board.mark_completes(number) {
+            wins.push(WinEvent {
+                board: index,
+                number,
+                draw,
+                score: board.sum_unmarked() * u32::from(number),
+            });
+        }
+    }
+}
+
+/// Plays the game until every board has won, or the numbers run out, and returns the wins in
+/// order. Boards that win on the same number are in input order.
+#[must_use]
+pub fn play(bingo: &Bingo) -> Vec<WinEvent> {
+    let mut boards = bingo.boards.clone();
+    let mut wins = Vec::new();
+    for (index, &number) in bingo.numbers.iter().enumerate() {
+        draw(&mut boards, index, number, &mut wins);
+        if wins.len() == boards.len() {
+            break;
+        }
+    }
+    wins
+}
+
+#[aoc(day4, part1)]
+fn part_1(bingo: &Bingo) -> u32 {
+    play(bingo).first().map_or(0, |win| win.score)
+}
+
+#[aoc(day4, part2)]
+fn part_2(bingo: &Bingo) -> u32 {
+    play(bingo).last().map_or(0, |win| win.score)
+}
+
+/// Both answers from a single game: the score of the first board to win, and of the last.
+#[must_use]
+pub fn solve_both(bingo: &Bingo) -> (u32, u32) {
+    let wins = play(bingo);
+    let score = |win: Option<&WinEvent>| win.map_or(0, |win| win.score);
+    (score(wins.first()), score(wins.last()))
+}
+
+/// The board that won with `win`, marked as it was when it won.
+fn winning_board(bingo: &Bingo, win: &WinEvent) -> Board {
+    let mut board = bingo.boards[win.board].clone();
+    for &number in &bingo.numbers[..=win.draw] {
+        board.mark(number);
+    }
+    board
+}
+
+/// Boards side by side in each row of the visualization.
+const BOARDS_PER_ROW: usize = 6;
+
+/// One frame of the game: a status line, then every board with its marks. The first board to win
+/// is labelled in green, and once every board has won, the last one in red. Other boards that
+/// have won are dimmed.
+fn frame(status: &str, boards: &[Board], wins: &[WinEvent], style: Style) -> String {
+    let mut result = format!("{status}\x1b[K\n");
+    if let Some(first) = wins.first() {
+        let _ = write!(
+            result,
+            "First win: board {}, score {}",
+            first.board + 1,
+            first.score
+        );
+    }
+    if let Some(last) = wins.last().filter(|_| wins.len() == boards.len()) {
+        let _ = write!(
+            result,
+            ". Last win: board {}, score {}",
+            last.board + 1,
+            last.score
+        );
+    }
+    result.push_str("\x1b[K\n\n");
+    // Each number takes three columns, and the label at least ten.
+    let size = boards.first().map_or(0, Board::size);
+    let width = (3 * size + 2).max(10);
+    for start in (0..boards.len()).step_by(BOARDS_PER_ROW) {
+        let end = boards.len().min(start + BOARDS_PER_ROW);
+        for index in start..end {
+            let color = match wins.iter().position(|win| win.board == index) {
+                Some(0) => "1;92",
+                Some(order) if order + 1 == boards.len() => "1;91",
+                Some(_) => "2",
+                None => "0",
+            };
+            // Writing to a `String` can not fail.
+            let _ = write!(
+                result,
+                "{:<width$}",
+                style.paint(color, format!("Board {}", index + 1))
+            );
+        }
+        result.push('\n');
+        let pictures = boards[start..end]
+            .iter()
+            .map(|board| board.render(style))
+            .collect::<Vec<_>>();
+        for line in 0..size {
+            for picture in &pictures {
+                result.push_str(picture.lines().nth(line).unwrap_or_default());
+                result.push_str(&" ".repeat(width - 3 * size));
+            }
+            result.push('\n');
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Plays the numbers in the terminal, redrawing every board after each draw at the given pace,
+/// until the last board has won.
+///
+/// # Errors
+///
+/// Returns an error if `out` can not be written to.
+pub fn visualize(bingo: &Bingo, pace: Pace, style: Style, out: &mut impl Write) -> io::Result<()> {
+    let mut boards = bingo.boards.clone();
+    let mut wins = Vec::new();
+    let status = format!("{} numbers to draw", bingo.numbers.len());
+    write!(
+        out,
+        "\x1b[2J\x1b[H{}",
+        frame(&status, &boards, &wins, style)
+    )?;
+    for (turn, &num) in bingo.numbers.iter().enumerate() {
+        out.flush()?;
+        pace.wait()?;
+        draw(&mut boards, turn, num, &mut wins);
+        let status = format!("Draw {} of {}: {num}", turn + 1, bingo.numbers.len());
+        write!(out, "\x1b[H{}", frame(&status, &boards, &wins, style))?;
+        if wins.len() == boards.len() {
+            break;
+        }
+    }
+    out.flush()
+}
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Parsed = Bingo;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    #[cfg(feature = "arena")]
+    fn parse_in(input: &str, arena: &Arena) -> Result<Self::Parsed, crate::Error> {
+        Bingo::parse_in(input, arena).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+
+    /// The first and the last board to win, with the marked numbers followed by `*`.
+    fn explain(parsed: &Self::Parsed, explain: &mut Explain) {
+        let wins = play(parsed);
+        for (which, win) in [("first", wins.first()), ("last", wins.last())] {
+            let Some(win) = win else {
+                continue;
+            };
+            explain.step(
+                format!(
+                    "board {} wins {which}, on {}, scoring {}",
+                    win.board + 1,
+                    win.number,
+                    win.score
+                ),
+                winning_board(parsed, win).render(Style::PLAIN),
+            );
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u32, u32), crate::Error> {
+    Day04::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let result = parse(EXAMPLE).unwrap();
+        assert_eq!(&result.numbers[..5], [7, 4, 9, 5, 11]);
+        assert_eq!(&result.boards[1].grid[0..5], [3, 15, 0, 2, 22]);
+        assert_eq!(result.boards[1].size(), 5);
+    }
+
+    #[test]
+    fn test_display() {
+        let bingo = parse(EXAMPLE).unwrap();
+        assert_eq!(bingo.to_string(), EXAMPLE);
+        assert_eq!(
+            bingo.boards[1].to_string().lines().next(),
+            Some(" 3 15  0  2 22")
+        );
+    }
+
+    #[test]
+    fn test_other_sizes() {
+        let bingo = parse("1,2,4,7,6\n\n1 2 3\n4 5 6\n7 8 9\n\n2 9 4\n7 5 3\n6 1 8").unwrap();
+        assert_eq!((part_1(&bingo), part_2(&bingo)), (31 * 7, 25 * 6));
+        assert_eq!(solve_both(&bingo), (31 * 7, 25 * 6));
+        let rows = (0..7)
+            .map(|row| {
+                (0..7)
+                    .map(|col| (7 * row + col).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        let bingo = parse(&format!(
+            "0,8,16,24,32,40,48,0,7,14,21,28,35,42\n\n{}",
+            rows.join("\n")
+        ))
+        .unwrap();
+        assert_eq!(part_1(&bingo), (49 * 48 / 2 - 168 - 147) * 42);
+    }
+
+    #[test]
+    fn test_parse_invalid_size() {
+        assert!(matches!(
+            parse("1,2\n\n1 2\n3 4\n\n1"),
+            Err(ParseError::InvalidSize)
+        ));
+        let row = "1 ".repeat(12);
+        let board = [row.trim_end(); 12].join("\n");
+        assert!(matches!(
+            parse(&format!("1\n\n{board}")),
+            Err(ParseError::InvalidSize)
+        ));
+    }
+
+    #[test]
+    fn test_parse_ragged_board() {
+        assert!(parse("1,2\n\n1 2 3 4 5 6").is_err());
+        assert!(parse("1,2\n\n1 2 3 4 5\n1 2 3 4 5").is_err());
+        let six_rows = format!("1,2\n\n{}", "1 2 3 4 5\n".repeat(6));
+        assert!(parse(six_rows.trim_end()).is_err());
+    }
+
+    #[test]
+    fn test_mark_completes() {
+        let mut board = Board::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 2]).unwrap();
+        assert!(!board.mark_completes(200));
+        assert!(!board.mark_completes(2));
+        assert_eq!(board.marks(), 0b10);
+        assert!(!board.mark_completes(5));
+        assert!(board.mark_completes(8));
+        assert!(board.has_bingo());
+        for seed in 0..4 {
+            let bingo = parse(geninput::generate(4, 1, seed).unwrap().trim_end()).unwrap();
+            for mut board in bingo.boards.iter().cloned() {
+                for &number in &bingo.numbers {
+                    let had_bingo = board.has_bingo();
+                    let completes = board.mark_completes(number);
+                    assert_eq!(completes && !had_bingo, board.has_bingo() && !had_bingo);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let bingo = parse(EXAMPLE).unwrap();
+        let result = part_1(&bingo);
+        assert_eq!(result, 4512);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let bingo = parse(EXAMPLE).unwrap();
+        let result = part_2(&bingo);
+        assert_eq!(result, 1924);
+    }
+
+    #[test]
+    fn test_play() {
+        let bingo = parse(EXAMPLE).unwrap();
+        let wins = play(&bingo);
+        assert_eq!(
+            wins.iter().map(|win| win.board).collect::<Vec<_>>(),
+            [2, 0, 1]
+        );
+        assert_eq!(
+            wins[0],
+            WinEvent {
+                board: 2,
+                number: 24,
+                draw: 11,
+                score: 4512
+            }
+        );
+        assert_eq!(
+            wins[2],
+            WinEvent {
+                board: 1,
+                number: 13,
+                draw: 14,
+                score: 1924
+            }
+        );
+    }
+
+    #[test]
+    fn test_visualize() {
+        let bingo = parse(EXAMPLE).unwrap();
+        let mut out = Vec::new();
+        visualize(&bingo, Pace::Auto(Duration::ZERO), Style::COLOR, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let last_frame = out.rsplit("\x1b[H").next().unwrap();
+        assert!(last_frame.starts_with("Draw 15 of 27: 13"));
+        assert!(
+            last_frame.contains("First win: board 3, score 4512. Last win: board 2, score 1924")
+        );
+        assert!(last_frame.contains("\x1b[1;92mBoard 3"));
+        assert!(last_frame.contains("\x1b[1;91mBoard 2"));
+        assert_eq!(out.matches("\x1b[H").count(), 16);
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let mut bingo = parse(EXAMPLE).unwrap();
+        let board = &mut bingo.boards_mut()[0];
+        board.mark(22);
+        let picture = board.render(Style::PLAIN);
+        assert_eq!(picture.lines().next(), Some("22*13 17 11  0 "));
+        assert!(!picture.contains('\x1b'));
+        assert!(
+            board
+                .render(Style::COLOR)
+                .starts_with("\x1b[97m22\x1b[0m \x1b[90m13")
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut explain = Explain::new();
+        Day04::explain(&parse(EXAMPLE).unwrap(), &mut explain);
+        let steps = explain.steps();
+        assert_eq!(steps[0].title, "board 3 wins first, on 24, scoring 4512");
+        assert_eq!(steps[0].detail.lines().next(), Some("14*21*17*24* 4*"));
+        assert_eq!(steps[1].title, "board 2 wins last, on 13, scoring 1924");
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let bingo = parse(EXAMPLE).unwrap();
+        assert_eq!(solve_both(&bingo), (4512, 1924));
+        for (seed, input) in geninput::samples(4) {
+            let bingo = parse(&input).unwrap();
+            let expected = (part_1(&bingo), part_2(&bingo));
+            assert_eq!(solve_both(&bingo), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,753 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::counter::DenseCounter;
+use crate::util::geometry::Point;
+use crate::util::grid::Grid;
+use crate::util::parse::{
+    LineError, NumberError, byte_lines, normalize, parse_lines_lenient, parse_unsigned,
+    split_once_bytes,
+};
+use crate::util::style::Style;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] NumberError),
+    #[error("Coordinate {value} is out of bounds, the largest allowed is {max}")]
+    OutOfBounds { value: u32, max: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DrawError {
+    #[error("The lines span {width}x{height} points, too many to draw")]
+    TooLarge { width: usize, height: usize },
+}
+
+/// The largest coordinate [`parse`] accepts, far beyond real and generated inputs. Use
+/// [`parse_within`] for other bounds.
+pub const MAX_COORDINATE: u32 = 1 << 24;
+
+/// The largest grid, in points, that is counted densely. Lines spread over a larger area are
+/// counted in a hash map instead, which only grows with the points they cover.
+const DENSE_AREA_LIMIT: usize = 1 << 27;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    start: Point<u32>,
+    end: Point<u32>,
+}
+
+impl Line {
+    #[must_use]
+    pub const fn new(start: Point<u32>, end: Point<u32>) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub const fn start(self) -> Point<u32> {
+        self.start
+    }
+
+    #[must_use]
+    pub const fn end(self) -> Point<u32> {
+        self.end
+    }
+
+    #[must_use]
+    pub const fn is_axis_aligned(self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// Whether the line is a diagonal at exactly 45 degrees, the only slanted lines the puzzle has.
+    #[must_use]
+    pub const fn is_diagonal_45(self) -> bool {
+        self.start.x.abs_diff(self.end.x) == self.start.y.abs_diff(self.end.y)
+            && self.start.x != self.end.x
+    }
+
+    /// Whether part 2 counts the line: it is axis-aligned, or a 45 degree diagonal.
+    const fn is_puzzle_line(self) -> bool {
+        self.is_axis_aligned() || self.is_diagonal_45()
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { start, end } = self;
+        write!(f, "{},{} -> {},{}", start.x, start.y, end.x, end.y)
+    }
+}
+
+impl Line {
+    /// Reads a line such as `0,9 -> 5,9`, with coordinates up to [`MAX_COORDINATE`].
+    ///
+    /// # Errors
+    ///
+    /// Fails unless `s` is two points separated by ` -> `.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, ParseError> {
+        Self::from_bytes_within(s, MAX_COORDINATE)
+    }
+
+    /// Reads a line such as `0,9 -> 5,9`, with coordinates up to `max`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless `s` is two points separated by ` -> `, or if a coordinate is larger than
+    /// `max`.
+    pub fn from_bytes_within(s: &[u8], max: u32) -> Result<Self, ParseError> {
+        let coordinate = |s| {
+            let value = parse_unsigned(s)?;
+            if value > max {
+                return Err(ParseError::OutOfBounds { value, max });
+            }
+            Ok(value)
+        };
+        let point = |s| {
+            let (x, y) = split_once_bytes(s, b",").ok_or(ParseError::SyntaxError)?;
+            Ok::<_, ParseError>(Point {
+                x: coordinate(x)?,
+                y: coordinate(y)?,
+            })
+        };
+        let (start, end) = split_once_bytes(s, b" -> ").ok_or(ParseError::SyntaxError)?;
+        Ok(Self {
+            start: point(start)?,
+            end: point(end)?,
+        })
+    }
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl IntoIterator for Line {
+    type Item = Point<u32>;
+
+    type IntoIter = LineIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let dx = i64::from(self.start.x.abs_diff(self.end.x));
+        let dy = -i64::from(self.start.y.abs_diff(self.end.y));
+        LineIterator {
+            next: Some(self.start),
+            end: self.end,
+            dx,
+            dy,
+            error: dx + dy,
+        }
+    }
+}
+
+/// The points of a line from its start to its end, both included, by Bresenham's algorithm.
+///
+/// Axis-aligned and 45 degree lines cover every point they pass through. Other lines cover the
+/// points closest to them.
+pub struct LineIterator {
+    next: Option<Point<u32>>,
+    end: Point<u32>,
+    dx: i64,
+    /// Minus the height, so that `error` is zero on the line.
+    dy: i64,
+    /// Within `2 * dy..=2 * dx`, which fits as both are at most `u32::MAX` from zero.
+    error: i64,
+}
+
+impl Iterator for LineIterator {
+    type Item = Point<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.next?;
+        if pos == self.end {
+            self.next = None;
+            return Some(pos);
+        }
+        let target = pos.step_towards(self.end);
+        let mut next = pos;
+        let doubled = 2 * self.error;
+        if doubled >= self.dy {
+            self.error += self.dy;
+            next.x = target.x;
+        }
+        if doubled <= self.dx {
+            self.error += self.dx;
+            next.y = target.y;
+        }
+        self.next = Some(next);
+        Some(pos)
+    }
+}
+
+#[aoc_generator(day5)]
+fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    parse_within(input, MAX_COORDINATE)
+}
+
+/// Reads the lines, with coordinates up to `max`.
+///
+/// # Errors
+///
+/// Fails if a line is malformed, or has a coordinate larger than `max`.
+pub fn parse_within(input: &str, max: u32) -> Result<Vec<Line>, ParseError> {
+    byte_lines(input.as_bytes())
+        .map(|line| Line::from_bytes_within(line, max))
+        .collect()
+}
+
+/// The lines, with coordinates up to [`MAX_COORDINATE`], each parsed as it is taken, for reading
+/// the input in a single pass without collecting it first.
+pub fn parse_iter(input: &str) -> impl Iterator<Item = Result<Line, ParseError>> {
+    byte_lines(input.as_bytes()).map(|line| Line::from_bytes_within(line, MAX_COORDINATE))
+}
+
+/// Reads the lines that parse, with coordinates up to [`MAX_COORDINATE`], and the errors of
+/// those that don't, so that one bad line does not lose the rest.
+#[must_use]
+pub fn parse_lenient(input: &str) -> (Vec<Line>, Vec<LineError<ParseError>>) {
+    parse_lines_lenient(input)
+}
+
+/// Counts the points in a hash map, which only holds the points that lines cover.
+#[aoc(day5, part1, hashmap)]
+fn part_1_hashmap(lines: &[Line]) -> usize {
+    count_overlaps_hashmap(lines.iter().filter(|line| line.is_axis_aligned()))
+}
+
+/// Counts the points in a hash map, which only holds the points that lines cover.
+#[aoc(day5, part2, hashmap)]
+fn part_2_hashmap(lines: &[Line]) -> usize {
+    count_overlaps_hashmap(lines.iter().filter(|line| line.is_puzzle_line()))
+}
+
+fn count_overlaps_hashmap<'a>(lines: impl IntoIterator<Item = &'a Line>) -> usize {
+    let mut counts = HashMap::<Point<u32>, u16>::new();
+    for line in lines {
+        for point in line.into_iter() {
+            let count = counts.entry(point).or_default();
+            *count = count.saturating_add(1);
+        }
+    }
+    counts.values().filter(|&&c| c > 1).count()
+}
+
+/// Both answers from a single pass over `lines`, counting the points in hash maps, as the
+/// bounding box is not known until the last line.
+///
+/// # Errors
+///
+/// Returns the first error among `lines`.
+pub fn solve_iter<E>(
+    lines: impl IntoIterator<Item = Result<Line, E>>,
+) -> Result<(usize, usize), E> {
+    let mut straight = HashMap::<Point<u32>, u16>::new();
+    let mut all = HashMap::<Point<u32>, u16>::new();
+    let mark = |counts: &mut HashMap<_, u16>, point| {
+        let count = counts.entry(point).or_default();
+        *count = count.saturating_add(1);
+    };
+    for line in lines {
+        let line = line?;
+        if !line.is_puzzle_line() {
+            continue;
+        }
+        let is_axis_aligned = line.is_axis_aligned();
+        for point in line {
+            mark(&mut all, point);
+            if is_axis_aligned {
+                mark(&mut straight, point);
+            }
+        }
+    }
+    let overlaps = |counts: &HashMap<_, u16>| counts.values().filter(|&&c| c > 1).count();
+    Ok((overlaps(&straight), overlaps(&all)))
+}
+
+/// Both answers from a single rasterization of the lines.
+#[must_use]
+pub fn solve_both(lines: &[Line]) -> (usize, usize) {
+    let Some((width, area)) = dense_area(lines) else {
+        return (part_1_hashmap(lines), part_2_hashmap(lines));
+    };
+    let mut straight = DenseCounter::<usize, u8>::new(area);
+    let mut all = DenseCounter::<usize, u8>::new(area);
+    for line in lines.iter().filter(|line| line.is_puzzle_line()) {
+        let is_axis_aligned = line.is_axis_aligned();
+        for point in line.into_iter() {
+            let index = index(point, width);
+            all.saturating_increment(index);
+            if is_axis_aligned {
+                straight.saturating_increment(index);
+            }
+        }
+    }
+    let overlaps = |counts: &DenseCounter<usize, u8>| counts.iter().filter(|&(_, c)| c > 1).count();
+    (overlaps(&straight), overlaps(&all))
+}
+
+#[aoc(day5, part1)]
+fn part_1(lines: &[Line]) -> usize {
+    count_overlaps_dense(lines.iter().filter(|line| line.is_axis_aligned()))
+}
+
+#[aoc(day5, part2)]
+fn part_2(lines: &[Line]) -> usize {
+    count_overlaps_dense(lines.iter().filter(|line| line.is_puzzle_line()))
+}
+
+fn bounds<'a>(lines: impl IntoIterator<Item = &'a Line>) -> (usize, usize) {
+    lines.into_iter().fold((0, 0), |(w, h), line| {
+        (
+            w.max(line.start.x.max(line.end.x) as usize + 1),
+            h.max(line.start.y.max(line.end.y) as usize + 1),
+        )
+    })
+}
+
+/// The width and area of the grid that holds the lines, unless it is larger than
+/// [`DENSE_AREA_LIMIT`].
+fn dense_area<'a>(lines: impl IntoIterator<Item = &'a Line>) -> Option<(usize, usize)> {
+    let (width, height) = bounds(lines);
+    let area = width.checked_mul(height)?;
+    (area <= DENSE_AREA_LIMIT).then_some((width, area))
+}
+
+/// Where `point` is in a grid `width` points wide, stored row by row.
+const fn index(point: Point<u32>, width: usize) -> usize {
+    point.y as usize * width + point.x as usize
+}
+
+/// Counts the points in a grid as large as the lines' bounding box, which on real inputs is
+/// much faster than hashing each point. Lines spread too far apart are counted in a hash map.
+fn count_overlaps_dense<'a>(lines: impl IntoIterator<Item = &'a Line> + Clone) -> usize {
+    let Some((width, area)) = dense_area(lines.clone()) else {
+        return count_overlaps_hashmap(lines);
+    };
+    let mut counts = DenseCounter::<usize, u8>::new(area);
+    for line in lines {
+        for point in line.into_iter() {
+            counts.saturating_increment(index(point, width));
+        }
+    }
+    counts.iter().filter(|&(_, c)| c > 1).count()
+}
+
+#[aoc(day5, part1, parallel)]
+fn part_1_parallel(lines: &[Line]) -> usize {
+    let lines = lines
+        .iter()
+        .filter(|line| line.is_axis_aligned())
+        .collect::<Vec<_>>();
+    count_overlaps_parallel(&lines)
+}
+
+#[aoc(day5, part2, parallel)]
+fn part_2_parallel(lines: &[Line]) -> usize {
+    let lines = lines
+        .iter()
+        .filter(|line| line.is_puzzle_line())
+        .collect::<Vec<_>>();
+    count_overlaps_parallel(&lines)
+}
+
+/// Both answers, each counted with the lines split among the rayon threads.
+#[must_use]
+pub fn solve_both_parallel(lines: &[Line]) -> (usize, usize) {
+    rayon::join(|| part_1_parallel(lines), || part_2_parallel(lines))
+}
+
+/// The same count as [`count_overlaps_dense`], with the lines split among the rayon threads.
+///
+/// Each thread draws its share of the lines into a grid of its own, and the grids are added
+/// together in pairs. The grids cost a pass over the bounding box each, so this only pays off
+/// when there are many lines, as in large generated inputs.
+fn count_overlaps_parallel(lines: &[&Line]) -> usize {
+    let Some((width, area)) = dense_area(lines.iter().copied()) else {
+        return count_overlaps_hashmap(lines.iter().copied());
+    };
+    let counts = lines
+        .par_iter()
+        .fold(
+            || DenseCounter::<usize, u8>::new(area),
+            |mut counts, line| {
+                for point in line.into_iter() {
+                    counts.saturating_increment(index(point, width));
+                }
+                counts
+            },
+        )
+        .reduce_with(|mut total, counts| {
+            for (index, count) in counts.iter() {
+                total.saturating_add(index, count);
+            }
+            total
+        });
+    counts.map_or(0, |counts| {
+        counts.as_slice().par_iter().filter(|&&c| c > 1).count()
+    })
+}
+
+/// How many lines cover each point, up to 255, indexed by `[y, x]`.
+///
+/// # Errors
+///
+/// Fails if the lines span more than [`DENSE_AREA_LIMIT`] points.
+pub fn overlap_counts(lines: &[Line]) -> Result<Grid<u8>, DrawError> {
+    let (width, height) = bounds(lines);
+    let Some((width, area)) = dense_area(lines) else {
+        return Err(DrawError::TooLarge { width, height });
+    };
+    let mut counts = DenseCounter::<usize, u8>::new(area);
+    for line in lines {
+        for point in line.into_iter() {
+            counts.saturating_increment(index(point, width));
+        }
+    }
+    Ok(Grid::new(counts.as_slice().to_vec(), width, height))
+}
+
+/// The color of a point covered by `count` lines: dark where there are no vents, blue where one
+/// line passes, and hotter where they overlap.
+#[must_use]
+pub const fn heat(count: u8) -> [u8; 3] {
+    match count {
+        0 => [0x0f, 0x0f, 0x23],
+        1 => [0x1f, 0x4e, 0x79],
+        2 => [0xe0, 0x7a, 0x1f],
+        3 => [0xe0, 0x3a, 0x1f],
+        _ => [0xff, 0xf0, 0x80],
+    }
+}
+
+/// Shades for the plain heatmap, by how many lines cover a point, up to four or more.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Draws the counts as a heatmap in 24-bit terminal colors. Each character is split into an upper
+/// and a lower square of `zoom` by `zoom` points, colored by the most lines over any point in it.
+///
+/// A plain `style` shades each character by the hotter of its squares instead.
+#[must_use]
+pub fn heatmap(counts: &Grid<u8>, zoom: usize, style: Style) -> String {
+    let zoom = zoom.max(1);
+    let square = |top: usize, left: usize| {
+        let square = counts.crop([top, left], zoom, zoom);
+        square.as_slice().iter().copied().max().unwrap_or(0)
+    };
+    let mut result = String::new();
+    for top in (0..counts.height()).step_by(2 * zoom) {
+        for left in (0..counts.width()).step_by(zoom) {
+            let (upper, lower) = (square(top, left), square(top + zoom, left));
+            if style.is_color() {
+                let ([r1, g1, b1], [r2, g2, b2]) = (heat(upper), heat(lower));
+                let code = format!("38;2;{r1};{g1};{b1};48;2;{r2};{g2};{b2}");
+                // Writing to a `String` can not fail.
+                let _ = write!(result, "{}▀", style.set(&code));
+            } else {
+                result.push(SHADES[usize::from(upper.max(lower)).min(SHADES.len() - 1)]);
+            }
+        }
+        let _ = writeln!(result, "{}", style.reset());
+    }
+    result
+}
+
+/// Saves the counts as a PNG image, with one pixel per point.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+#[cfg(feature = "image")]
+pub fn export_png(counts: &Grid<u8>, path: &std::path::Path) -> image::ImageResult<()> {
+    crate::util::export::save_png(path, counts, 1, |&count| image::Rgb(heat(count)))
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Parsed = Vec<Line>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day05::solve(input)
+}
+
+/// Parses the input and solves both parts like [`solve`], but in a single pass over the lines,
+/// without collecting them.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve_lazy(input: &str) -> Result<(usize, usize), crate::Error> {
+    solve_iter(parse_iter(&normalize(input))).map_err(crate::Error::invalid_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::join_lines;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        0,9 -> 5,9\n\
+        8,0 -> 0,8\n\
+        9,4 -> 3,4\n\
+        2,2 -> 2,1\n\
+        7,0 -> 7,4\n\
+        6,4 -> 2,0\n\
+        0,9 -> 2,9\n\
+        3,4 -> 1,4\n\
+        0,0 -> 8,8\n\
+        5,5 -> 8,2\
+    ";
+
+    #[test]
+    fn test_parse() {
+        macro_rules! pt {
+            ($x:expr,$y:expr) => {
+                Point { x: $x, y: $y }
+            };
+        }
+        macro_rules! line {
+            ($start:expr, $end:expr) => {
+                Line {
+                    start: $start,
+                    end: $end,
+                }
+            };
+        }
+        let result = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            result,
+            [
+                line!(pt!(0, 9), pt!(5, 9)),
+                line!(pt!(8, 0), pt!(0, 8)),
+                line!(pt!(9, 4), pt!(3, 4)),
+                line!(pt!(2, 2), pt!(2, 1)),
+                line!(pt!(7, 0), pt!(7, 4)),
+                line!(pt!(6, 4), pt!(2, 0)),
+                line!(pt!(0, 9), pt!(2, 9)),
+                line!(pt!(3, 4), pt!(1, 4)),
+                line!(pt!(0, 0), pt!(8, 8)),
+                line!(pt!(5, 5), pt!(8, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let lines = parse(EXAMPLE).unwrap();
+        assert_eq!(join_lines(&lines), EXAMPLE);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let lines = parse(EXAMPLE).unwrap();
+        let result = part_1(&lines);
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_part_1_hashmap() {
+        let lines = parse(EXAMPLE).unwrap();
+        let result = part_1_hashmap(&lines);
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let lines = parse(EXAMPLE).unwrap();
+        let result = part_2(&lines);
+        assert_eq!(result, 12);
+        assert_eq!(part_2_parallel(&lines), 12);
+        assert_eq!(part_2_parallel(&[]), 0);
+    }
+
+    #[test]
+    fn test_part_2_hashmap() {
+        let lines = parse(EXAMPLE).unwrap();
+        let result = part_2_hashmap(&lines);
+        assert_eq!(result, 12);
+    }
+
+    #[test]
+    fn test_line_points() {
+        let points = |line: &str| {
+            line.parse::<Line>()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let pt = |x, y| Point { x, y };
+        assert_eq!(points("3,4 -> 1,4"), [pt(3, 4), pt(2, 4), pt(1, 4)]);
+        assert_eq!(points("1,3 -> 3,1"), [pt(1, 3), pt(2, 2), pt(3, 1)]);
+        assert_eq!(
+            points("0,0 -> 3,1"),
+            [pt(0, 0), pt(1, 0), pt(2, 1), pt(3, 1)]
+        );
+        assert_eq!(
+            points("1,5 -> 0,0"),
+            [pt(1, 5), pt(1, 4), pt(1, 3), pt(0, 2), pt(0, 1), pt(0, 0)]
+        );
+        assert_eq!(points("2,2 -> 2,2"), [pt(2, 2)]);
+    }
+
+    #[test]
+    fn test_part_2_skips_other_slopes() {
+        let line = |s: &str| s.parse::<Line>().unwrap();
+        assert!(line("1,3 -> 3,1").is_diagonal_45());
+        assert!(!line("0,0 -> 4,2").is_diagonal_45());
+        assert!(!line("2,2 -> 2,2").is_diagonal_45());
+        let lines = parse("0,0 -> 4,2\n0,1 -> 4,1\n0,2 -> 2,0").unwrap();
+        assert_eq!(part_2(&lines), 1);
+        assert_eq!(part_2_hashmap(&lines), 1);
+        assert_eq!(solve_both(&lines), (0, 1));
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let lines = parse(
+            "70000,5 -> 70000,0\n69998,3 -> 70003,3\n0,0 -> 400000,400000\n400000,0 -> 0,400000",
+        )
+        .unwrap();
+        assert_eq!(lines[0].start(), Point { x: 70_000, y: 5 });
+        assert_eq!(dense_area(&lines), None);
+        assert_eq!(solve_both(&lines), (1, 2));
+        assert_eq!(solve_both_parallel(&lines), (1, 2));
+        let far = Line::new(Point::new(u32::MAX, 0), Point::new(u32::MAX - 2, 2));
+        assert_eq!(far.into_iter().last(), Some(Point::new(u32::MAX - 2, 2)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert!(matches!(
+            parse_within("0,9 -> 5,10", 9),
+            Err(ParseError::OutOfBounds { value: 10, max: 9 })
+        ));
+        assert!(parse_within("0,9 -> 5,9", 9).is_ok());
+        assert!(matches!(
+            parse("0,0 -> 99999999999,0"),
+            Err(ParseError::InvalidNumber(NumberError::Overflow))
+        ));
+        assert!(parse("0,0 -> 16777217,0").is_err());
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let lines = parse(EXAMPLE).unwrap();
+        let result = solve_both(&lines);
+        assert_eq!(result, (5, 12));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let lines = parse(EXAMPLE).unwrap();
+        let counts = overlap_counts(&lines).unwrap();
+        assert_eq!((counts.width(), counts.height()), (10, 10));
+        assert_eq!(counts.as_slice().iter().filter(|&&c| c > 1).count(), 12);
+        let picture = heatmap(&counts, 1, Style::COLOR);
+        assert_eq!(picture.lines().count(), 5);
+        assert_eq!(picture.matches('▀').count(), 50);
+        let zoomed = heatmap(&counts, 5, Style::COLOR);
+        assert_eq!(zoomed.lines().count(), 1);
+        assert_eq!(zoomed.matches("38;2;224;").count(), 2);
+        let plain = heatmap(&counts, 5, Style::PLAIN);
+        assert_eq!(plain, "▓▓\n");
+        let far = parse("0,0 -> 0,0\n16000000,16000000 -> 16000000,16000000").unwrap();
+        assert_eq!(
+            overlap_counts(&far),
+            Err(DrawError::TooLarge {
+                width: 16_000_001,
+                height: 16_000_001
+            })
+        );
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(5) {
+            let lines = parse(&input).unwrap();
+            let expected = (part_1(&lines), part_2(&lines));
+            assert_eq!(solve_both(&lines), expected, "seed {seed}");
+            assert_eq!(
+                (part_1_hashmap(&lines), part_2_hashmap(&lines)),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(solve_both_parallel(&lines), expected, "seed {seed}");
+            assert_eq!(solve_lazy(&input).unwrap(), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_lazy() {
+        assert_eq!(solve_lazy(EXAMPLE).unwrap(), (5, 12));
+        assert_eq!(solve_lazy(&untidy(EXAMPLE)).unwrap(), (5, 12));
+        assert!(solve_lazy("0,9 -> 5,9\n8,0 -> 0").is_err());
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let (lines, errors) = parse_lenient("0,9 -> 5,9\n8,0 -> 0\n9,4 -> 3,4\n1,1 -> 99999999,1");
+        assert_eq!(
+            lines,
+            [
+                Line::new(Point { x: 0, y: 9 }, Point { x: 5, y: 9 }),
+                Line::new(Point { x: 9, y: 4 }, Point { x: 3, y: 4 }),
+            ]
+        );
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                LineError {
+                    line: 2,
+                    error: ParseError::SyntaxError
+                },
+                LineError {
+                    line: 4,
+                    error: ParseError::OutOfBounds { .. }
+                },
+            ]
+        ));
+    }
+}
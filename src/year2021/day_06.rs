@@ -0,0 +1,338 @@
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::counter::DenseCounter;
+use crate::util::matrix::Matrix;
+use crate::util::simulation::{Simulation, hash_state};
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+    #[error("Timer {0} is above 8")]
+    InvalidTimer(u8),
+}
+
+#[aoc_generator(day6)]
+fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    input
+        .split(',')
+        .map(|s| match s.parse()? {
+            timer @ 0..=8 => Ok(timer),
+            timer => Err(ParseError::InvalidTimer(timer)),
+        })
+        .collect()
+}
+
+#[aoc(day6, part1)]
+fn part_1(fishes: &[u8]) -> Option<u128> {
+    simulate(fishes, 80)
+}
+
+#[aoc(day6, part2)]
+fn part_2(fishes: &[u8]) -> Option<u128> {
+    simulate(fishes, 256)
+}
+
+#[aoc(day6, part1, naive)]
+fn part_1_naive(fishes: &[u8]) -> usize {
+    let mut fishes = fishes.to_vec();
+    for _ in 0..80 {
+        let mut born = 0;
+        for fish in &mut fishes {
+            if *fish == 0 {
+                *fish = 6;
+                born += 1;
+            } else {
+                *fish -= 1;
+            }
+        }
+        fishes.resize(fishes.len() + born, 8);
+    }
+    fishes.len()
+}
+
+#[derive(Debug, Clone)]
+struct School {
+    counts: DenseCounter<usize, u128>,
+    time: usize,
+}
+
+impl School {
+    fn new(fishes: &[u8]) -> Self {
+        let mut counts = DenseCounter::new(9);
+        for &f in fishes {
+            counts.increment(usize::from(f));
+        }
+        Self { counts, time: 0 }
+    }
+
+    /// Steps one day, or returns `None` if the count of some timer would overflow.
+    fn checked_step(&mut self) -> Option<()> {
+        let t = self.time % 9;
+        let reset = (t + 7) % 9;
+        self.counts[reset] = self.counts[reset].checked_add(self.counts[t])?;
+        self.time += 1;
+        Some(())
+    }
+
+    /// Steps `days` days, or returns `None` if a count would overflow on the way.
+    fn checked_run_for(&mut self, days: usize) -> Option<()> {
+        (0..days).try_for_each(|_| self.checked_step())
+    }
+
+    fn checked_population(&self) -> Option<u128> {
+        self.counts
+            .as_slice()
+            .iter()
+            .try_fold(0_u128, |sum, &count| sum.checked_add(count))
+    }
+}
+
+impl Simulation for School {
+    type Output = ();
+
+    fn step(&mut self) {
+        let t = self.time % 9;
+        self.counts[(t + 7) % 9] += self.counts[t];
+        self.time += 1;
+    }
+
+    fn state_hash(&self) -> u64 {
+        let (done, pending) = self.counts.as_slice().split_at(self.time % 9);
+        hash_state(&(pending, done))
+    }
+}
+
+/// The number of lanternfish after `days` days. Returns `None` if there would be too many to
+/// count in a `u128`, which takes about a thousand days.
+#[must_use]
+pub fn simulate(fishes: &[u8], days: usize) -> Option<u128> {
+    let mut school = School::new(fishes);
+    school.checked_run_for(days)?;
+    school.checked_population()
+}
+
+/// The number of lanternfish after each day, starting with the first, so that `.nth(79)` is the
+/// population after 80 days. Ends once there are too many to count in a `u128`.
+pub fn timeline(fishes: &[u8]) -> impl Iterator<Item = u128> + use<> {
+    let mut school = School::new(fishes);
+    std::iter::from_fn(move || {
+        school.checked_step()?;
+        school.checked_population()
+    })
+}
+
+/// How one day changes the number of fish with each timer, as a matrix that maps the counts by
+/// timer before the day to those after it. Every timer counts down, and the fish at 0 go back to
+/// 6 and each spawn a fish at 8.
+fn transition() -> Matrix<u64, 9, 9> {
+    let mut matrix = Matrix::zero();
+    for timer in 1..9 {
+        matrix[(timer - 1, timer)] = 1;
+    }
+    matrix[(6, 0)] = 1;
+    matrix[(8, 0)] = 1;
+    matrix
+}
+
+fn timer_counts(fishes: &[u8]) -> [u64; 9] {
+    let mut counts = [0; 9];
+    for &fish in fishes {
+        counts[usize::from(fish)] += 1;
+    }
+    counts
+}
+
+/// The number of lanternfish after `days` days, from the transition matrix raised to that power,
+/// in time logarithmic in `days`. Returns `None` if the counts overflow a `u128`.
+#[must_use]
+pub fn population_after(fishes: &[u8], days: u64) -> Option<u128> {
+    let mut overflow = false;
+    let matrix = transition().map(u128::from).pow_by(days, |a, b| {
+        a.checked_mul(b).unwrap_or_else(|| {
+            overflow = true;
+            Matrix::zero()
+        })
+    });
+    if overflow {
+        return None;
+    }
+    let counts = timer_counts(fishes);
+    matrix
+        .0
+        .iter()
+        .flatten()
+        .zip(counts.iter().cycle())
+        .try_fold(0_u128, |sum, (&entry, &count)| {
+            sum.checked_add(entry.checked_mul(u128::from(count))?)
+        })
+}
+
+/// The number of lanternfish after `days` days, modulo `modulus`, for when there are far too many
+/// to count.
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+#[must_use]
+pub fn population_after_mod(fishes: &[u8], days: u64, modulus: u64) -> u64 {
+    let matrix = transition().pow_mod(days, modulus);
+    let counts = timer_counts(fishes);
+    let modulus = u128::from(modulus);
+    let population = matrix
+        .0
+        .iter()
+        .flatten()
+        .zip(counts.iter().cycle())
+        .fold(0, |sum, (&entry, &count)| {
+            (sum + u128::from(entry) * (u128::from(count) % modulus)) % modulus
+        });
+    u64::try_from(population).unwrap_or_default()
+}
+
+/// Both answers from one simulation, which continues from day 80 to day 256. Returns `None` if
+/// there would be too many fish to count in a `u128`, as `simulate` does.
+#[must_use]
+pub fn solve_both(fishes: &[u8]) -> Option<(u128, u128)> {
+    let mut school = School::new(fishes);
+    school.checked_run_for(80)?;
+    let part_1 = school.checked_population()?;
+    school.checked_run_for(256 - 80)?;
+    Some((part_1, school.checked_population()?))
+}
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Parsed = Vec<u8>;
+    type Part1 = u128;
+    type Part2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        solve_both(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u128, u128), crate::Error> {
+    Day06::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("3,9,1").is_err());
+        assert!(parse("3,,1").is_err());
+    }
+
+    #[test]
+    fn test_part_1_naive() {
+        let fishes = parse(EXAMPLE).unwrap();
+        let result = part_1_naive(&fishes);
+        assert_eq!(result, 5_934);
+    }
+
+    #[test_case(EXAMPLE, 80 => Some(5_934))]
+    #[test_case(EXAMPLE, 256 => Some(26_984_457_539))]
+    #[test_case(EXAMPLE, 500 => Some(45_920_814_377_410_229_085))]
+    #[test_case(EXAMPLE, 2000 => None)]
+    fn test_simulate(input: &str, days: usize) -> Option<u128> {
+        let fishes = parse(input).unwrap();
+        simulate(&fishes, days)
+    }
+
+    #[test]
+    fn test_timeline() {
+        let fishes = parse(EXAMPLE).unwrap();
+        let timeline = timeline(&fishes).collect::<Vec<_>>();
+        assert_eq!(timeline[..4], [5, 6, 7, 9]);
+        assert_eq!(timeline[17], 26);
+        assert_eq!(timeline[79], 5_934);
+        assert_eq!(timeline[255], 26_984_457_539);
+        assert_eq!(timeline.last().copied(), simulate(&fishes, timeline.len()));
+        assert_eq!(simulate(&fishes, timeline.len() + 1), None);
+        let over = timeline
+            .iter()
+            .position(|&population| population > 1_000_000);
+        assert_eq!(over.map(|day| day + 1), Some(139));
+        assert!(simulate(&fishes, 138) <= Some(1_000_000));
+    }
+
+    #[test]
+    fn test_population_after() {
+        let fishes = parse(EXAMPLE).unwrap();
+        for days in [0, 1, 18, 80, 256, 500, 900] {
+            let expected = simulate(&fishes, days);
+            let days = u64::try_from(days).unwrap();
+            assert_eq!(population_after(&fishes, days), expected, "{days} days");
+        }
+        assert_eq!(population_after(&fishes, 1_000_000_000_000), None);
+    }
+
+    #[test]
+    fn test_population_after_mod() {
+        let fishes = parse(EXAMPLE).unwrap();
+        let modulus = 1_000_000_007;
+        let expected = simulate(&fishes, 900).unwrap() % u128::from(modulus);
+        assert_eq!(
+            u128::from(population_after_mod(&fishes, 900, modulus)),
+            expected
+        );
+        assert_eq!(
+            population_after_mod(&fishes, 1_000_000_000_000, modulus),
+            995_077_479
+        );
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(6) {
+            let fishes = parse(&input).unwrap();
+            let naive = u128::try_from(part_1_naive(&fishes)).unwrap();
+            assert_eq!(Some(naive), part_1(&fishes), "seed {seed}");
+            let expected = (part_1(&fishes).unwrap(), part_2(&fishes).unwrap());
+            assert_eq!(solve_both(&fishes), Some(expected), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_checked_run_for() {
+        // A school far larger than any input makes, whose counts pass `u128::MAX` within 256 days.
+        let mut school = School::new(&[0]);
+        school.counts.add(0, 1 << 126);
+        assert_eq!(school.clone().checked_run_for(9), Some(()));
+        assert_eq!(school.checked_run_for(256), None);
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
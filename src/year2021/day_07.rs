@@ -0,0 +1,456 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
+
+use crate::solver::Solver;
+use crate::util::parse::{LineError, StreamError, normalize};
+
+/// Reads the comma-separated positions, each of which may have a sign and whitespace around it.
+#[aoc_generator(day7)]
+fn parse(input: &str) -> Result<Vec<i32>, ParseIntError> {
+    let mut res = parse_iter(input).collect::<Result<Vec<_>, _>>()?;
+    res.sort_unstable();
+    Ok(res)
+}
+
+/// The positions in the order they are written, each parsed as it is taken, for reading the
+/// input in a single pass without collecting it first.
+pub fn parse_iter(input: &str) -> impl Iterator<Item = Result<i32, ParseIntError>> {
+    input.split(',').map(|position| position.trim().parse())
+}
+
+/// Reads the positions that parse, sorted, and the errors of those that don't, each with the
+/// line it is on, so that one bad position does not lose the rest.
+#[must_use]
+pub fn parse_lenient(input: &str) -> (Vec<i32>, Vec<LineError<ParseIntError>>) {
+    let mut positions = Vec::new();
+    let mut errors = Vec::new();
+    for (ix, line) in input.lines().enumerate() {
+        for position in line.split(',') {
+            match position.trim().parse() {
+                Ok(position) => positions.push(position),
+                Err(error) => errors.push(LineError {
+                    line: ix + 1,
+                    error,
+                }),
+            }
+        }
+    }
+    positions.sort_unstable();
+    (positions, errors)
+}
+
+/// The position that costs the crabs the least fuel to line up at, and that fuel, when moving a
+/// distance `d` costs `cost(d)`. Returns `None` if there are no crabs.
+///
+/// The cost must be convex, so that the total is convex in the target too. A ternary search
+/// between the outermost crabs then narrows down to the cheapest target, preferring the smallest
+/// of any ties. This works for any positions on a line, so it is not limited to crabs. The total
+/// is summed in a `u128`, which no number of crabs that fits in memory can overflow.
+#[must_use]
+pub fn minimize_fuel(positions: &[i32], cost: impl Fn(u32) -> u64) -> Option<(i32, u128)> {
+    minimize_fuel_weighted(positions.iter().map(|&x| (x, 1)), cost)
+}
+
+/// Like [`minimize_fuel`], for `(position, crabs)` pairs, such as those of a histogram. A total
+/// that would overflow a `u128` saturates instead.
+fn minimize_fuel_weighted(
+    crabs: impl Iterator<Item = (i32, u64)> + Clone,
+    cost: impl Fn(u32) -> u64,
+) -> Option<(i32, u128)> {
+    let mut low = crabs.clone().map(|(x, _)| x).min()?;
+    let mut high = crabs.clone().map(|(x, _)| x).max()?;
+    let fuel = move |target: i32| {
+        crabs
+            .clone()
+            .map(|(x, n)| u128::from(n) * u128::from(cost(x.abs_diff(target))))
+            .fold(0, u128::saturating_add)
+    };
+    while high.abs_diff(low) > 2 {
+        let third = high.abs_diff(low) / 3;
+        let left = low.saturating_add_unsigned(third);
+        let right = high.saturating_sub_unsigned(third);
+        let (left_fuel, right_fuel) = (fuel(left), fuel(right));
+        // On a tie the cheapest targets may include `left` and those below it, but none past
+        // `right`, as the total can not fall again after rising.
+        if left_fuel <= right_fuel {
+            high = if left_fuel < right_fuel {
+                right - 1
+            } else {
+                right
+            };
+        } else {
+            low = left + 1;
+        }
+    }
+    (low..=high)
+        .map(|target| (target, fuel(target)))
+        .min_by_key(|&(_, fuel)| fuel)
+}
+
+/// The least fuel for `crabs` to line up, which is zero for no crabs, or `None` if it does not
+/// fit in a `u64`.
+fn least_fuel(
+    crabs: impl Iterator<Item = (i32, u64)> + Clone,
+    cost: impl Fn(u32) -> u64,
+) -> Option<u64> {
+    minimize_fuel_weighted(crabs, cost).map_or(Some(0), |(_, fuel)| u64::try_from(fuel).ok())
+}
+
+/// The fuel to move `dx` when each step costs one more than the last. Crabs are at most
+/// `u32::MAX` apart, so this fits in a `u64`.
+fn triangular(dx: u32) -> u64 {
+    u64::from(dx) * (1 + u64::from(dx)) / 2
+}
+
+/// Returns `None` if the fuel does not fit in a `u64`.
+#[aoc(day7, part1)]
+fn part_1(positions: &[i32]) -> Option<u64> {
+    least_fuel(positions.iter().map(|&x| (x, 1)), u64::from)
+}
+
+/// Returns `None` if the fuel does not fit in a `u64`.
+#[aoc(day7, part2)]
+fn part_2(positions: &[i32]) -> Option<u64> {
+    least_fuel(positions.iter().map(|&x| (x, 1)), triangular)
+}
+
+/// Moves every crab to the median, which minimizes the sum of distances.
+#[aoc(day7, part1, median)]
+fn part_1_median(positions: &[i32]) -> u64 {
+    let n = positions.len();
+    let target = positions[n / 2];
+    positions
+        .iter()
+        .map(|&x| u64::from(x.abs_diff(target)))
+        .sum()
+}
+
+/// Only tries the two positions next to the mean, which is within half a step of the best target
+/// for the triangular cost.
+#[aoc(day7, part2, mean)]
+fn part_2_mean(positions: &[i32]) -> u64 {
+    let n = i64::try_from(positions.len()).unwrap();
+    let sum = positions.iter().copied().map(i64::from).sum::<i64>();
+    let target = sum.div_euclid(n);
+    (target..=target + 1)
+        .map(|target| {
+            positions
+                .iter()
+                .map(|&x| i64::from(x).abs_diff(target))
+                .map(|dx| dx * (1 + dx) / 2)
+                .sum()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Tries every position instead of only the two next to the mean.
+#[aoc(day7, part2, brute_force)]
+fn part_2_brute_force(positions: &[i32]) -> u64 {
+    let (Some(&min), Some(&max)) = (positions.first(), positions.last()) else {
+        return 0;
+    };
+    (min..=max)
+        .map(|target| {
+            positions
+                .iter()
+                .map(|&x| u64::from(x.abs_diff(target)))
+                .map(|dx| dx * (1 + dx) / 2)
+                .sum()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Works out the fuel for every target between the outermost crabs, each in constant time.
+///
+/// Moving `d` costs `(d² + d) / 2`, so the total for a target `t` needs the sums of `|x - t|` and
+/// of `(x - t)²` over the positions `x`. The squares expand to `Σx² - 2tΣx + nt²`. The distances
+/// split at `t` into `t·k - Σ(x ≤ t)` for the `k` crabs at or below it, and `Σ(x > t) - t·(n - k)`
+/// for the rest, whose sums come from prefix sums of the sorted positions.
+#[aoc(day7, part2, prefix_sums)]
+fn part_2_prefix_sums(positions: &[i32]) -> u64 {
+    let (Some(&min), Some(&max)) = (positions.first(), positions.last()) else {
+        return 0;
+    };
+    let n = i128::try_from(positions.len()).unwrap_or(i128::MAX);
+    let prefix_sums = std::iter::once(0)
+        .chain(positions.iter().scan(0, |sum, &x| {
+            *sum += i128::from(x);
+            Some(*sum)
+        }))
+        .collect::<Vec<_>>();
+    let sum = prefix_sums[positions.len()];
+    let sum_of_squares = positions
+        .iter()
+        .map(|&x| i128::from(x).pow(2))
+        .sum::<i128>();
+    let mut below = 0;
+    (min..=max)
+        .map(|target| {
+            while below < positions.len() && positions[below] <= target {
+                below += 1;
+            }
+            let (t, k) = (
+                i128::from(target),
+                i128::try_from(below).unwrap_or(i128::MAX),
+            );
+            let distances = t * k - prefix_sums[below] + (sum - prefix_sums[below]) - t * (n - k);
+            let squares = sum_of_squares - 2 * t * sum + n * t * t;
+            let doubled_fuel = squares + distances;
+            u64::try_from(doubled_fuel / 2).unwrap_or(u64::MAX)
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Solves part 1 from a histogram of positions, reading the comma-separated
+/// input one number at a time.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or an item is not a valid position. A position that does
+/// not parse is reported with the line it starts on.
+pub fn solve_stream_part_1(mut reader: impl BufRead) -> Result<u64, StreamError<ParseIntError>> {
+    let mut histogram = BTreeMap::<i32, u64>::new();
+    let mut item = Vec::new();
+    let mut count = 0_u64;
+    let mut line = 1;
+    loop {
+        item.clear();
+        if reader.read_until(b',', &mut item)? == 0 {
+            break;
+        }
+        let text = str::from_utf8(&item)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .trim_end_matches(',');
+        let item_line = line;
+        line += text.matches('\n').count();
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let position: i32 = text.parse().map_err(|error| {
+            StreamError::Parse(LineError {
+                line: item_line + leading.matches('\n').count(),
+                error,
+            })
+        })?;
+        *histogram.entry(position).or_default() += 1;
+        count += 1;
+    }
+    let mut seen = 0;
+    let Some(target) = histogram.iter().find_map(|(&x, &n)| {
+        seen += n;
+        (seen > count / 2).then_some(x)
+    }) else {
+        return Ok(0);
+    };
+    Ok(histogram
+        .iter()
+        .map(|(&x, &n)| n * u64::from(x.abs_diff(target)))
+        .sum())
+}
+
+/// Solves both parts in a single pass over `positions`, keeping only how many crabs are at each
+/// one. A part is `None` if its fuel does not fit in a `u64`.
+///
+/// # Errors
+///
+/// Returns the first error among `positions`.
+pub fn solve_iter<E>(
+    positions: impl IntoIterator<Item = Result<i32, E>>,
+) -> Result<(Option<u64>, Option<u64>), E> {
+    let mut histogram = BTreeMap::<i32, u64>::new();
+    for position in positions {
+        *histogram.entry(position?).or_default() += 1;
+    }
+    let crabs = histogram.iter().map(|(&x, &n)| (x, n));
+    Ok((
+        least_fuel(crabs.clone(), u64::from),
+        least_fuel(crabs, triangular),
+    ))
+}
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Parsed = Vec<i32>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or the fuel does not fit in a `u64`.
+pub fn solve(input: &str) -> Result<(u64, u64), crate::Error> {
+    Day07::solve(input)
+}
+
+/// Parses the input and solves both parts like [`solve`], but in a single pass over the
+/// positions, without collecting them.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or the fuel does not fit in a `u64`.
+pub fn solve_lazy(input: &str) -> Result<(u64, u64), crate::Error> {
+    match solve_iter(parse_iter(&normalize(input))) {
+        Ok((Some(part_1), Some(part_2))) => Ok((part_1, part_2)),
+        Ok(_) => Err(crate::Error::NoSolution),
+        Err(error) => Err(crate::Error::invalid_input(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_part_1() {
+        let positions = parse(EXAMPLE).unwrap();
+        let result = part_1(&positions);
+        assert_eq!(result, Some(37));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let positions = parse(EXAMPLE).unwrap();
+        let result = part_2(&positions);
+        assert_eq!(result, Some(168));
+        assert_eq!(part_2_mean(&positions), 168);
+        assert_eq!(part_2_prefix_sums(&positions), 168);
+        assert_eq!(part_2_prefix_sums(&[]), 0);
+    }
+
+    #[test]
+    fn test_minimize_fuel() {
+        let positions = parse(EXAMPLE).unwrap();
+        assert_eq!(minimize_fuel(&positions, u64::from), Some((2, 37)));
+        let triangular = |dx| u64::from(dx) * (1 + u64::from(dx)) / 2;
+        assert_eq!(minimize_fuel(&positions, triangular), Some((5, 168)));
+        let squared = |dx| u64::from(dx * dx);
+        assert_eq!(minimize_fuel(&positions, squared), Some((5, 291)));
+        assert_eq!(minimize_fuel(&[], u64::from), None);
+        assert_eq!(minimize_fuel(&[3, 3], u64::from), Some((3, 0)));
+    }
+
+    #[test]
+    fn test_flat_minimum() {
+        assert_eq!(minimize_fuel(&[0, 10], u64::from), Some((0, 10)));
+        assert_eq!(minimize_fuel(&[1, 2, 3, 4], u64::from), Some((2, 4)));
+        let positions = [-7, -7, 0, 3, 50, 50];
+        assert_eq!(minimize_fuel(&positions, u64::from), Some((0, 117)));
+        let far = [0, 1_000_000_000];
+        assert_eq!(minimize_fuel(&far, u64::from), Some((0, 1_000_000_000)));
+    }
+
+    #[test]
+    fn test_fuel_overflow() {
+        let extremes = [[i32::MIN; 8], [i32::MAX; 8]].concat();
+        let triangular = |dx| u64::from(dx) * (1 + u64::from(dx)) / 2;
+        let (target, fuel) = minimize_fuel(&extremes, triangular).unwrap();
+        assert_eq!(target, -1);
+        assert!(fuel > u128::from(u64::MAX));
+        assert_eq!(part_2(&extremes), None);
+        assert_eq!(part_1(&extremes), Some(8 * u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn test_signed() {
+        let positions = parse(" -3, +1 ,2\n").unwrap();
+        assert_eq!(positions, [-3, 1, 2]);
+        let mirrored = parse("-16,-1,-2,0,-4,-2,-7,-1,-2,-14").unwrap();
+        assert_eq!(minimize_fuel(&mirrored, u64::from), Some((-2, 37)));
+        assert_eq!(part_1_median(&mirrored), 37);
+        assert_eq!(part_2(&mirrored), Some(168));
+        assert_eq!(part_2_mean(&mirrored), 168);
+        assert_eq!(part_2_prefix_sums(&mirrored), 168);
+        assert_eq!(part_2_brute_force(&mirrored), 168);
+        let extremes = [i32::MIN, i32::MAX];
+        let fuel = minimize_fuel(&extremes, u64::from).map(|(_, fuel)| fuel);
+        assert_eq!(fuel, Some(u128::from(u32::MAX)));
+        assert!(parse("1,-").is_err());
+        assert!(parse("1,2 3").is_err());
+    }
+
+    #[test]
+    fn test_solve_stream_part_1() {
+        let result = solve_stream_part_1(b"-16, -1,-2,0,-4,-2,-7,-1,-2,-14" as &[u8]).unwrap();
+        assert_eq!(result, 37);
+        let result = solve_stream_part_1(b"16,1,2,0,4,2,7,1,2,14\n" as &[u8]).unwrap();
+        assert_eq!(result, 37);
+        let lines = |input: &[u8]| match solve_stream_part_1(input) {
+            Err(StreamError::Parse(error)) => Some(error.line),
+            _ => None,
+        };
+        assert_eq!(lines(b"16,x"), Some(1));
+        assert_eq!(lines(b"16,1\n2,4"), Some(1));
+        assert_eq!(lines(b"16,1,\n2,\n\nx,4"), Some(4));
+        assert_eq!(lines(b"16,1,\n2,3,\n\n4,5,\n6,-"), Some(5));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(7) {
+            let positions = parse(&input).unwrap();
+            let part_1 = part_1(&positions).unwrap();
+            assert_eq!(part_1_median(&positions), part_1, "seed {seed}");
+            let part_1_stream = solve_stream_part_1(input.as_bytes()).unwrap();
+            assert_eq!(part_1_stream, part_1, "seed {seed}");
+            let part_2 = part_2(&positions).unwrap();
+            assert_eq!(part_2_mean(&positions), part_2, "seed {seed}");
+            assert_eq!(part_2_prefix_sums(&positions), part_2, "seed {seed}");
+            assert_eq!(part_2_brute_force(&positions), part_2, "seed {seed}");
+            assert_eq!(solve_lazy(&input).unwrap(), (part_1, part_2), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_lazy() {
+        assert_eq!(solve_lazy(EXAMPLE).unwrap(), (37, 168));
+        assert_eq!(solve_lazy(&untidy(EXAMPLE)).unwrap(), (37, 168));
+        assert!(matches!(
+            solve_lazy("16,x,2"),
+            Err(crate::Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let (positions, errors) = parse_lenient("16,1,x,0\n4, -2,,7");
+        assert_eq!(positions, [-2, 0, 1, 4, 7, 16]);
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(
+            parse_lenient(EXAMPLE),
+            (parse(EXAMPLE).unwrap(), Vec::new())
+        );
+    }
+}
@@ -0,0 +1,777 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::explain::Explain;
+#[cfg(feature = "arena")]
+use crate::solver::Arena;
+use crate::solver::Solver;
+#[cfg(feature = "arena")]
+use crate::util::parse::collect_exact;
+use crate::util::parse::{LineError, byte_lines, split_once_bytes};
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    Undecodable(#[from] DecodeError),
+}
+
+/// Why a display's patterns could not be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DecodeError {
+    #[error("Pattern {0} has no digit with as many segments")]
+    NoSuchLength(usize),
+    #[error("No wiring shows the patterns as the ten digits")]
+    NoWiring,
+    #[error("Output {0} is not one of the digits")]
+    UnknownOutput(usize),
+}
+
+/// The segments `a`-`g` of each digit, as bits 0-6.
+const SEGMENTS: [u8; 10] = [
+    0b111_0111, 0b010_0100, 0b101_1101, 0b110_1101, 0b010_1110, 0b110_1011, 0b111_1011, 0b010_0101,
+    0b111_1111, 0b110_1111,
+];
+
+/// The segments of a digit, named as in the puzzle: `a` on top, `b` and `c` top left and right,
+/// `d` in the middle, `e` and `f` bottom left and right, and `g` at the bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Segment {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+impl Segment {
+    pub const ALL: [Self; 7] = [
+        Self::A,
+        Self::B,
+        Self::C,
+        Self::D,
+        Self::E,
+        Self::F,
+        Self::G,
+    ];
+
+    /// The segment's place in `a`-`g`, from 0 to 6.
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The segment's letter, `a`-`g`.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        (b'a' + self.index()) as char
+    }
+}
+
+/// A display worked out from its patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoded {
+    /// The segment that each wire `a`-`g` lights.
+    pub wiring: [Segment; 7],
+    /// The digit that each pattern shows.
+    pub digits: [u8; 10],
+    /// The digits of the output.
+    pub output: [u8; 4],
+}
+
+impl Decoded {
+    /// The output as a number.
+    #[must_use]
+    pub fn value(&self) -> u32 {
+        self.output
+            .iter()
+            .fold(0, |sum, &digit| sum * 10 + u32::from(digit))
+    }
+}
+
+/// The set of lit wires `a`-`g` of one pattern, as bits 0-6.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wires(u8);
+
+impl Wires {
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0x7f)
+    }
+
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn contains(self, wire: usize) -> bool {
+        self.0 & 1 << wire != 0
+    }
+
+    /// The digit these wires show when wired up by `wiring`, if any.
+    fn digit(self, wiring: [u8; 7]) -> Option<u8> {
+        let segments = (0..7)
+            .filter(|&wire| self.contains(wire))
+            .fold(0, |segments, wire| segments | 1 << wiring[wire]);
+        (0..10)
+            .zip(SEGMENTS)
+            .find_map(|(digit, s)| (s == segments).then_some(digit))
+    }
+}
+
+impl Wires {
+    /// Reads a pattern such as `cdfbe`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a byte is not a wire from `a` to `g`.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, ParseError> {
+        let mut bits = 0;
+        for &ch in s {
+            if !(b'a'..=b'g').contains(&ch) {
+                return Err(ParseError::SyntaxError);
+            }
+            bits |= 1 << (ch - b'a');
+        }
+        Ok(Self(bits))
+    }
+}
+
+impl Display for Wires {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (wire, letter) in ('a'..='g').enumerate() {
+            if self.contains(wire) {
+                write!(f, "{letter}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Wires {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegmentDisplay {
+    digits: [Wires; 10],
+    output: [Wires; 4],
+}
+
+impl SegmentDisplay {
+    #[must_use]
+    pub const fn new(digits: [Wires; 10], output: [Wires; 4]) -> Self {
+        Self { digits, output }
+    }
+
+    #[must_use]
+    pub const fn digits(&self) -> &[Wires; 10] {
+        &self.digits
+    }
+
+    #[must_use]
+    pub const fn output(&self) -> &[Wires; 4] {
+        &self.output
+    }
+
+    /// Works out which digit each pattern and output shows, from the wiring found by
+    /// [`Self::solve_wiring`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no wiring shows the patterns as the ten digits, or an output is not
+    /// one of them.
+    pub fn decode(&self) -> Result<Decoded, DecodeError> {
+        let wiring = self.solve_wiring()?;
+        let indices = wiring.map(Segment::index);
+        let digits = self
+            .digits
+            .map(|pattern| pattern.digit(indices).unwrap_or_default());
+        let mut output = [0; 4];
+        for (index, (digit, pattern)) in output.iter_mut().zip(self.output).enumerate() {
+            *digit = pattern
+                .digit(indices)
+                .ok_or(DecodeError::UnknownOutput(index))?;
+        }
+        Ok(Decoded {
+            wiring,
+            digits,
+            output,
+        })
+    }
+
+    /// Works out which segment each wire `a`-`g` lights.
+    ///
+    /// Each wire starts out able to light any segment. A pattern with as many wires as some
+    /// digits has segments rules out, for the wires in it, the segments that none of those digits
+    /// have, and for the other wires, those that all of them have. Each wire is also in as many
+    /// patterns as its segment is in digits, which only `b`, `e` and `f` share with no other. Then
+    /// wires that can only light one segment rule it out for the others, until nothing changes.
+    /// Whatever choices are left are tried in turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no wiring shows the patterns as the ten digits. The outputs are not
+    /// checked.
+    pub fn solve_wiring(&self) -> Result<[Segment; 7], DecodeError> {
+        let mut candidates = [0x7f_u8; 7];
+        for (index, pattern) in self.digits.iter().enumerate() {
+            let len = pattern.0.count_ones();
+            let (any, all) = SEGMENTS
+                .iter()
+                .filter(|s| s.count_ones() == len)
+                .fold((0, 0x7f), |(any, all), &s| (any | s, all & s));
+            if any == 0 {
+                return Err(DecodeError::NoSuchLength(index));
+            }
+            for (wire, candidates) in candidates.iter_mut().enumerate() {
+                *candidates &= if pattern.contains(wire) { any } else { !all };
+            }
+        }
+        for (wire, candidates) in candidates.iter_mut().enumerate() {
+            let frequency = self.digits.iter().filter(|p| p.contains(wire)).count();
+            *candidates &= (0..7)
+                .filter(|&segment| {
+                    SEGMENTS.iter().filter(|s| *s & 1 << segment != 0).count() == frequency
+                })
+                .fold(0, |mask, segment| mask | 1 << segment);
+        }
+        propagate(&mut candidates);
+        let wiring = self
+            .search(candidates, &mut [0; 7], 0, 0)
+            .ok_or(DecodeError::NoWiring)?;
+        Ok(wiring.map(|segment| Segment::ALL[usize::from(segment)]))
+    }
+
+    /// Tries every wiring that `candidates` allows for the wires from `wire` on, with the
+    /// segments in `used` already taken, and returns the first that shows the patterns as the
+    /// ten digits.
+    fn search(
+        &self,
+        candidates: [u8; 7],
+        wiring: &mut [u8; 7],
+        wire: usize,
+        used: u8,
+    ) -> Option<[u8; 7]> {
+        if wire == 7 {
+            let seen = self.digits.iter().try_fold(0_u16, |seen, pattern| {
+                Some(seen | 1 << pattern.digit(*wiring)?)
+            })?;
+            return (seen == 0x3ff).then_some(*wiring);
+        }
+        (0..7)
+            .filter(|&segment| candidates[wire] & !used & 1 << segment != 0)
+            .find_map(|segment| {
+                wiring[wire] = segment;
+                self.search(candidates, wiring, wire + 1, used | 1 << segment)
+            })
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the display can not be decoded. Parsing checks that it can.
+    fn decode_checked(&self) -> Decoded {
+        self.decode()
+            .expect("The display was checked when it was parsed")
+    }
+}
+
+impl SegmentDisplay {
+    /// Reads the ten patterns and the four outputs of a display, separated by ` | `, and checks
+    /// that the display can be decoded.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the line is malformed, or the patterns are not the ten digits on some wiring.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, ParseError> {
+        fn patterns<const N: usize>(s: &[u8]) -> Result<[Wires; N], ParseError> {
+            let mut patterns = [Wires(0); N];
+            let mut count = 0;
+            for pattern in s.split(u8::is_ascii_whitespace) {
+                if !pattern.is_empty() {
+                    *patterns.get_mut(count).ok_or(ParseError::SyntaxError)? =
+                        Wires::from_bytes(pattern)?;
+                    count += 1;
+                }
+            }
+            if count == N {
+                Ok(patterns)
+            } else {
+                Err(ParseError::SyntaxError)
+            }
+        }
+        let (digits, output) = split_once_bytes(s, b" | ").ok_or(ParseError::SyntaxError)?;
+        let display = Self {
+            digits: patterns(digits)?,
+            output: patterns(output)?,
+        };
+        display.decode()?;
+        Ok(display)
+    }
+}
+
+impl Display for SegmentDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, digits @ ..] = &self.digits;
+        write!(f, "{first}")?;
+        for pattern in digits {
+            write!(f, " {pattern}")?;
+        }
+        f.write_str(" |")?;
+        for pattern in &self.output {
+            write!(f, " {pattern}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SegmentDisplay {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+/// Wires that can only light one segment rule it out for every other wire, which may leave more
+/// wires with only one segment, until nothing changes.
+fn propagate(candidates: &mut [u8; 7]) {
+    let mut fixed = 0;
+    loop {
+        let singles = candidates
+            .iter()
+            .filter(|c| c.is_power_of_two())
+            .fold(0, |singles, &c| singles | c);
+        if singles == fixed {
+            break;
+        }
+        fixed = singles;
+        for candidates in candidates.iter_mut() {
+            if candidates.count_ones() > 1 {
+                *candidates &= !fixed;
+            }
+        }
+    }
+}
+
+#[aoc_generator(day8)]
+fn parse(input: &str) -> Result<Vec<SegmentDisplay>, LineError<ParseError>> {
+    displays(input).collect()
+}
+
+/// Like [`parse`], with the displays collected in `arena` until their count is known.
+#[cfg(feature = "arena")]
+fn parse_in(input: &str, arena: &Arena) -> Result<Vec<SegmentDisplay>, LineError<ParseError>> {
+    collect_exact(displays(input), arena)
+}
+
+/// Each line's display as it is read.
+fn displays(input: &str) -> impl Iterator<Item = Result<SegmentDisplay, LineError<ParseError>>> {
+    byte_lines(input.as_bytes()).enumerate().map(|(ix, line)| {
+        SegmentDisplay::from_bytes(line).map_err(|error| LineError {
+            line: ix + 1,
+            error,
+        })
+    })
+}
+
+/// Parses the lines on the rayon threads. If several lines are malformed, any one of their
+/// errors may be returned.
+#[aoc_generator(day8, part2, parallel)]
+fn parse_parallel(input: &str) -> Result<Vec<SegmentDisplay>, LineError<ParseError>> {
+    let lines = input.lines().collect::<Vec<_>>();
+    lines
+        .par_iter()
+        .enumerate()
+        .map(|(ix, line)| {
+            line.parse().map_err(|error| LineError {
+                line: ix + 1,
+                error,
+            })
+        })
+        .collect()
+}
+
+#[aoc(day8, part1)]
+fn part_1(displays: &[SegmentDisplay]) -> usize {
+    displays
+        .iter()
+        .flat_map(|d| &d.output)
+        .filter(|d| matches!(d.0.count_ones(), 2..=4 | 7))
+        .count()
+}
+
+#[aoc(day8, part2)]
+fn part_2(displays: &[SegmentDisplay]) -> u32 {
+    displays
+        .iter()
+        .map(|display| display.decode_checked().value())
+        .sum()
+}
+
+/// Decodes the displays on the rayon threads.
+#[aoc(day8, part2, parallel)]
+fn part_2_parallel(displays: &[SegmentDisplay]) -> u32 {
+    displays
+        .par_iter()
+        .map(|display| display.decode_checked().value())
+        .sum()
+}
+
+/// Tries all 5040 wirings on each display, and takes the one that shows the patterns as the ten
+/// digits.
+///
+/// # Panics
+///
+/// Panics if a display can not be decoded. Parsing checks that it can.
+#[aoc(day8, part2, permutations)]
+fn part_2_permutations(displays: &[SegmentDisplay]) -> u32 {
+    let wirings = wirings();
+    displays
+        .iter()
+        .map(|display| {
+            let &wiring = wirings
+                .iter()
+                .find(|&&wiring| {
+                    let seen = display.digits.iter().try_fold(0_u16, |seen, pattern| {
+                        Some(seen | 1 << pattern.digit(wiring)?)
+                    });
+                    seen == Some(0x3ff)
+                })
+                .expect("The display was checked when it was parsed");
+            display.output.iter().fold(0, |sum, pattern| {
+                let digit = pattern
+                    .digit(wiring)
+                    .expect("The display was checked when it was parsed");
+                sum * 10 + u32::from(digit)
+            })
+        })
+        .sum()
+}
+
+/// Every permutation of the seven segments, by Heap's algorithm.
+fn wirings() -> Vec<[u8; 7]> {
+    let mut wiring = [0, 1, 2, 3, 4, 5, 6];
+    let mut counters = [0; 7];
+    let mut wirings = vec![wiring];
+    let mut index = 1;
+    while index < 7 {
+        if counters[index] < index {
+            let other = if index % 2 == 0 { 0 } else { counters[index] };
+            wiring.swap(other, index);
+            wirings.push(wiring);
+            counters[index] += 1;
+            index = 1;
+        } else {
+            counters[index] = 0;
+            index += 1;
+        }
+    }
+    wirings
+}
+
+/// Both answers from a single decoding of each display.
+///
+/// # Panics
+///
+/// Panics if a display can not be decoded. Parsing checks that it can.
+#[must_use]
+pub fn solve_both(displays: &[SegmentDisplay]) -> (usize, u32) {
+    let mut easy_digits = 0;
+    let mut sum = 0;
+    for display in displays {
+        let decoded = display.decode_checked();
+        easy_digits += decoded
+            .output
+            .iter()
+            .filter(|d| matches!(d, 1 | 4 | 7 | 8))
+            .count();
+        sum += decoded.value();
+    }
+    (easy_digits, sum)
+}
+
+/// The same as [`solve_both`], with the displays decoded on the rayon threads.
+///
+/// # Panics
+///
+/// Panics if a display can not be decoded. Parsing checks that it can.
+#[must_use]
+pub fn solve_both_parallel(displays: &[SegmentDisplay]) -> (usize, u32) {
+    displays
+        .par_iter()
+        .map(|display| {
+            let decoded = display.decode_checked();
+            let easy_digits = decoded
+                .output
+                .iter()
+                .filter(|d| matches!(d, 1 | 4 | 7 | 8))
+                .count();
+            (easy_digits, decoded.value())
+        })
+        .reduce(|| (0, 0), |(a1, a2), (b1, b2)| (a1 + b1, a2 + b2))
+}
+
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Parsed = Vec<SegmentDisplay>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    #[cfg(feature = "arena")]
+    fn parse_in(input: &str, arena: &Arena) -> Result<Self::Parsed, crate::Error> {
+        parse_in(input, arena).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+
+    /// The segment that each wire `a`-`g` lights, for every display.
+    fn explain(parsed: &Self::Parsed, explain: &mut Explain) {
+        for (index, display) in parsed.iter().enumerate() {
+            let decoded = display.decode_checked();
+            let segments = decoded
+                .wiring
+                .iter()
+                .map(|segment| segment.letter())
+                .collect::<String>();
+            explain.step(
+                format!("display {}", index + 1),
+                format!("wires abcdefg light {segments}, output {}", decoded.value()),
+            );
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(usize, u32), crate::Error> {
+    Day08::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::join_lines;
+    use crate::util::parse::untidy;
+    use test_case::test_case;
+
+    const EXAMPLE1: &str = "\
+        acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\
+    ";
+
+    const EXAMPLE2: &str = "\
+        be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe\n\
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc\n\
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg\n\
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb\n\
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea\n\
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb\n\
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe\n\
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef\n\
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb\n\
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let result = parse(EXAMPLE1).unwrap();
+        assert_eq!(
+            result,
+            [SegmentDisplay {
+                digits: [
+                    Wires(0b111_1111),
+                    Wires(0b011_1110),
+                    Wires(0b110_1101),
+                    Wires(0b010_1111),
+                    Wires(0b000_1011),
+                    Wires(0b011_1111),
+                    Wires(0b111_1110),
+                    Wires(0b011_0011),
+                    Wires(0b101_1111),
+                    Wires(0b000_0011)
+                ],
+                output: [
+                    Wires(0b011_1110),
+                    Wires(0b010_1111),
+                    Wires(0b011_1110),
+                    Wires(0b010_1111)
+                ]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let displays = parse(EXAMPLE2).unwrap();
+        assert_eq!(
+            displays[0].to_string(),
+            "be abcdefg bcdefg acdefg bceg cdefg abdefg bcdef abcdf bde | abcdefg bcdef bcdefg bceg"
+        );
+        assert_eq!(parse(&join_lines(&displays)).unwrap(), displays);
+    }
+
+    #[test_case("ab | ab" ; "too few patterns")]
+    #[test_case("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ax | ab ab ab ab" ; "unknown wire")]
+    #[test_case("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | ab ab ab abc" ; "unknown output")]
+    #[test_case("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb cagedb | ab ab ab ab" ; "repeated pattern")]
+    fn test_parse_invalid(input: &str) {
+        assert!(parse(input).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut explain = Explain::new();
+        Day08::explain(&parse(EXAMPLE1).unwrap(), &mut explain);
+        assert_eq!(
+            explain.to_string(),
+            "display 1: wires abcdefg light cfgabde, output 5353\n"
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let displays = parse(EXAMPLE1).unwrap();
+        let decoded = displays[0].decode().unwrap();
+        // The puzzle's example wiring: `d` lights the top segment, `e` the top left, and so on.
+        assert_eq!(
+            decoded.wiring,
+            [
+                Segment::C,
+                Segment::F,
+                Segment::G,
+                Segment::A,
+                Segment::B,
+                Segment::D,
+                Segment::E
+            ]
+        );
+        assert_eq!(displays[0].solve_wiring(), Ok(decoded.wiring));
+        assert_eq!(decoded.digits, [8, 5, 2, 3, 7, 9, 6, 4, 0, 1]);
+        assert_eq!(decoded.output, [5, 3, 5, 3]);
+        assert_eq!(decoded.value(), 5_353);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let line =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb abc";
+        let display = line.parse::<SegmentDisplay>();
+        assert!(matches!(
+            display,
+            Err(ParseError::Undecodable(DecodeError::UnknownOutput(3)))
+        ));
+        let error = parse(&format!("{EXAMPLE1}\n{line}")).unwrap_err();
+        assert_eq!(error.line, 2);
+        let error = parse_parallel(&format!("{EXAMPLE1}\n{line}")).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.to_string(),
+            "line 2: Output 3 is not one of the digits"
+        );
+        let repeated =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb cagedb | ab ab ab ab";
+        let display = repeated.parse::<SegmentDisplay>();
+        let patterns = repeated.split_once(" | ").unwrap().0;
+        let patterns = patterns.split(' ').map(|p| p.parse().unwrap());
+        let unchecked = SegmentDisplay::new(
+            patterns.collect::<Vec<_>>().try_into().unwrap(),
+            [Wires(0); 4],
+        );
+        assert_eq!(unchecked.solve_wiring(), Err(DecodeError::NoWiring));
+        assert!(matches!(
+            display,
+            Err(ParseError::Undecodable(DecodeError::NoWiring))
+        ));
+        let empty = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb a | ab ab ab ab";
+        let display = empty.parse::<SegmentDisplay>();
+        assert!(matches!(
+            display,
+            Err(ParseError::Undecodable(DecodeError::NoSuchLength(9)))
+        ));
+    }
+
+    #[test_case(EXAMPLE1 => 0)]
+    #[test_case(EXAMPLE2 => 26)]
+    fn test_part_1(input: &str) -> usize {
+        let displays = parse(input).unwrap();
+        part_1(&displays)
+    }
+
+    #[test_case(EXAMPLE1 => 5_353)]
+    #[test_case(EXAMPLE2 => 61_229)]
+    fn test_part_2(input: &str) -> u32 {
+        let displays = parse(input).unwrap();
+        part_2(&displays)
+    }
+
+    #[test]
+    fn test_wirings() {
+        let wirings = wirings();
+        assert_eq!(wirings.len(), 5_040);
+        assert_eq!(wirings.iter().collect::<HashSet<_>>().len(), 5_040);
+    }
+
+    #[test_case(EXAMPLE1 => (0, 5_353))]
+    #[test_case(EXAMPLE2 => (26, 61_229))]
+    fn test_solve_both(input: &str) -> (usize, u32) {
+        let displays = parse(input).unwrap();
+        solve_both(&displays)
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(8) {
+            let displays = parse(&input).unwrap();
+            let expected = (part_1(&displays), part_2(&displays));
+            assert_eq!(solve_both(&displays), expected, "seed {seed}");
+            let part_2_permutations = part_2_permutations(&displays);
+            assert_eq!(part_2_permutations, expected.1, "seed {seed}");
+            let parallel = parse_parallel(&input).unwrap();
+            assert_eq!(parallel, displays, "seed {seed}");
+            assert_eq!(part_2_parallel(&parallel), expected.1, "seed {seed}");
+            assert_eq!(solve_both_parallel(&displays), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        for example in [EXAMPLE1, EXAMPLE2] {
+            assert_eq!(solve(&untidy(example)).unwrap(), solve(example).unwrap());
+        }
+    }
+}
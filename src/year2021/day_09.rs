@@ -0,0 +1,395 @@
+use std::cmp;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::Write;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::grid::{Border, Grid, GridError};
+use crate::util::style::Style;
+use crate::util::union_find::UnionFind;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidGrid(#[from] GridError),
+}
+
+#[aoc_generator(day9)]
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::from_bytes(input)?;
+    if !grid.as_slice().iter().all(u8::is_ascii_digit) {
+        return Err(ParseError::SyntaxError);
+    }
+    Ok(grid)
+}
+
+#[aoc(day9, part1)]
+fn part_1(grid: &Grid<u8>) -> u32 {
+    grid.positions()
+        .filter_map(|pos| {
+            let center = grid[pos];
+            grid.neighbors4(pos)
+                .all(|neighbor| grid[neighbor] > center)
+                .then(|| u32::from(center - b'0' + 1))
+        })
+        .sum()
+}
+
+/// Joins every cell that is not a 9 with its neighbours, so that each basin is one set.
+fn join_basins(grid: &Grid<u8>) -> UnionFind {
+    let mut uf = UnionFind::new(grid.width() * grid.height());
+    for (r, row) in grid.rows().enumerate() {
+        for (c, &cell) in row.iter().enumerate() {
+            let index = grid.width() * r + c;
+            if cell != b'9' {
+                if r > 0 && grid[[r - 1, c]] != b'9' {
+                    uf.union(index - grid.width(), index);
+                }
+                if c > 0 && row[c - 1] != b'9' {
+                    uf.union(index - 1, index);
+                }
+            }
+        }
+    }
+    uf
+}
+
+/// Like `part_1`, but compares every cell with its neighbours in one pass over the grid, where
+/// the cells past the edges are higher than any.
+#[aoc(day9, part1, stencil)]
+fn part_1_stencil(grid: &Grid<u8>) -> u32 {
+    grid.stencil(0, &Border::Constant(u8::MAX), |window| {
+        let center = window.get(0, 0);
+        [(-1, 0), (0, -1), (0, 1), (1, 0)]
+            .into_iter()
+            .all(|(rows, cols)| window.get(rows, cols) > center)
+            .then(|| u32::from(center - b'0' + 1))
+    })
+    .as_slice()
+    .iter()
+    .flatten()
+    .sum()
+}
+
+#[aoc(day9, part2)]
+fn part_2(grid: &Grid<u8>) -> usize {
+    product_of_largest(join_basins(grid).root_sizes())
+}
+
+/// Flood-fills each basin breadth first, starting from its low point. Cells are tried as starting
+/// points from the deepest up, so a basin whose bottom is flat, or that has several low points, is
+/// still only filled once.
+#[aoc(day9, part2, flood_fill)]
+fn part_2_flood_fill(grid: &Grid<u8>) -> usize {
+    let mut visited = grid.map(|&height| height == b'9');
+    let mut queue = VecDeque::new();
+    let mut sizes = Vec::new();
+    for depth in b'0'..=b'8' {
+        for start in grid.positions().filter(|&pos| grid[pos] == depth) {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            queue.push_back(start);
+            let mut size = 0;
+            while let Some(pos) = queue.pop_front() {
+                size += 1;
+                for neighbor in grid.neighbors4(pos) {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+    }
+    product_of_largest(sizes.into_iter())
+}
+
+/// The product of the three largest basins.
+fn product_of_largest(sizes: impl Iterator<Item = usize>) -> usize {
+    let mut biggest = BinaryHeap::new();
+    for size in sizes {
+        biggest.push(cmp::Reverse(size));
+        if biggest.len() > 3 {
+            biggest.pop();
+        }
+    }
+    biggest.iter().map(|&cmp::Reverse(sz)| sz).product()
+}
+
+/// One basin: the cells that flow down to the same low point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Basin {
+    /// The deepest cell, or the first in reading order if several are as deep.
+    pub low_point: [usize; 2],
+    pub size: usize,
+}
+
+/// The basin of every cell, numbered in the order that their first cells are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledGrid {
+    labels: Grid<Option<usize>>,
+    basins: Vec<Basin>,
+}
+
+impl LabeledGrid {
+    /// The basin of each cell, or `None` for the 9s between them.
+    #[must_use]
+    pub const fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+
+    #[must_use]
+    pub fn label(&self, pos: [usize; 2]) -> Option<usize> {
+        self.labels.get(pos).copied().flatten()
+    }
+
+    #[must_use]
+    pub fn basins(&self) -> &[Basin] {
+        &self.basins
+    }
+}
+
+/// Works out which basin each cell is in, and the size and low point of each basin.
+#[must_use]
+pub fn basins(grid: &Grid<u8>) -> LabeledGrid {
+    let mut uf = join_basins(grid);
+    let mut ids = vec![None; grid.width() * grid.height()];
+    let mut basins = Vec::<Basin>::new();
+    let labels = grid
+        .as_slice()
+        .iter()
+        .enumerate()
+        .map(|(index, &height)| {
+            if height == b'9' {
+                return None;
+            }
+            let root = uf.find(index);
+            let pos = grid.index_to_pos(index)?;
+            let id = *ids[root].get_or_insert_with(|| {
+                basins.push(Basin {
+                    low_point: pos,
+                    size: 0,
+                });
+                basins.len() - 1
+            });
+            let basin = &mut basins[id];
+            basin.size += 1;
+            if height < grid[basin.low_point] {
+                basin.low_point = pos;
+            }
+            Some(id)
+        })
+        .collect();
+    LabeledGrid {
+        labels: Grid::new(labels, grid.width(), grid.height()),
+        basins,
+    }
+}
+
+/// The colors that the basins take turns with.
+const PALETTE: [[u8; 3]; 6] = [
+    [0xe6, 0x4b, 0x3c],
+    [0x3c, 0xb4, 0x4b],
+    [0x43, 0x63, 0xd8],
+    [0xf5, 0x82, 0x31],
+    [0x91, 0x1e, 0xb4],
+    [0x42, 0xd4, 0xf4],
+];
+
+/// The color of a basin. Neighbouring basins get different colors most of the time.
+const fn basin_color(id: usize) -> [u8; 3] {
+    PALETTE[id.wrapping_mul(0x9e37_79b9) % PALETTE.len()]
+}
+
+/// Draws the heights in 24-bit terminal colors, with each basin in its own color, its low point
+/// in bold, and the 9s between them in grey. A plain `style` leaves only the heights.
+#[must_use]
+pub fn basin_map(grid: &Grid<u8>, basins: &LabeledGrid, style: Style) -> String {
+    let mut result = String::new();
+    for (row, cells) in grid.rows().enumerate() {
+        for (col, &height) in cells.iter().enumerate() {
+            let pos = [row, col];
+            // Writing to a `String` can not fail.
+            let _ = match basins.label(pos) {
+                Some(id) => {
+                    let [r, g, b] = basin_color(id);
+                    let weight = if basins.basins[id].low_point == pos {
+                        1
+                    } else {
+                        22
+                    };
+                    let code = format!("{weight};38;2;{r};{g};{b}");
+                    write!(result, "{}{}", style.set(&code), char::from(height))
+                }
+                None => write!(result, "{}{}", style.set("22;90"), char::from(height)),
+            };
+        }
+        let _ = writeln!(result, "{}", style.reset());
+    }
+    result
+}
+
+/// Saves a map of the basins as a PNG image. Each basin has its own color, lighter where it is
+/// deeper, and the walls of 9s are dark.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+#[cfg(feature = "image")]
+pub fn export_png(grid: &Grid<u8>, path: &std::path::Path) -> image::ImageResult<()> {
+    use image::Rgb;
+
+    use crate::util::export::{self, CELL_SIZE, DARK};
+
+    let basins = basins(grid);
+    let colors = grid
+        .as_slice()
+        .iter()
+        .zip(basins.labels.as_slice())
+        .map(|(&height, &label)| {
+            label.map_or(DARK, |id| {
+                export::blend(Rgb(basin_color(id)), DARK, height - b'0', 10)
+            })
+        })
+        .collect();
+    let colors = Grid::new(colors, grid.width(), grid.height());
+    export::save_png(path, &colors, CELL_SIZE, |&color| color)
+}
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Parsed = Grid<u8>;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input.as_bytes()).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u32, usize), crate::Error> {
+    Day09::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &[u8] = b"\
+        2199943210\n\
+        3987894921\n\
+        9856789892\n\
+        8767896789\n\
+        9899965678\
+    ";
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(b"123\n45").is_err());
+        assert!(parse(b"123\n4a6").is_err());
+        assert!(parse(b"123\n456\n\n").is_err());
+        assert!(parse(b"").is_err());
+    }
+
+    #[test]
+    fn test_part_1() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1(&grid);
+        assert_eq!(result, 15);
+        assert_eq!(part_1_stencil(&grid), 15);
+        let trailing_newline = parse(&[EXAMPLE, b"\n"].concat()).unwrap();
+        assert_eq!(trailing_newline, grid);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_2(&grid);
+        assert_eq!(result, 1134);
+    }
+
+    #[test]
+    fn test_part_2_flood_fill() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2_flood_fill(&grid), 1134);
+        let flat = parse(b"119\n911\n999\n121").unwrap();
+        assert_eq!(part_2_flood_fill(&flat), part_2(&flat));
+    }
+
+    #[test]
+    fn test_basins() {
+        let grid = parse(EXAMPLE).unwrap();
+        let labeled = basins(&grid);
+        assert_eq!(
+            labeled.basins(),
+            [
+                Basin {
+                    low_point: [0, 1],
+                    size: 3
+                },
+                Basin {
+                    low_point: [0, 9],
+                    size: 9
+                },
+                Basin {
+                    low_point: [2, 2],
+                    size: 14
+                },
+                Basin {
+                    low_point: [4, 6],
+                    size: 9
+                },
+            ]
+        );
+        assert_eq!(labeled.label([0, 0]), Some(0));
+        assert_eq!(labeled.label([0, 2]), None);
+        assert_eq!(labeled.label([4, 9]), Some(3));
+        assert_eq!(labeled.label([5, 0]), None);
+        let picture = basin_map(&grid, &labeled, Style::COLOR);
+        assert_eq!(picture.lines().count(), 5);
+        assert_eq!(picture.matches("\x1b[1;").count(), 4);
+        assert_eq!(picture.matches("\x1b[22;90m9").count(), 15);
+        let plain = basin_map(&grid, &labeled, Style::PLAIN);
+        assert_eq!(plain.lines().next(), Some("2199943210"));
+        assert!(!plain.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(9) {
+            let grid = parse(input.as_bytes()).unwrap();
+            assert_eq!(part_1_stencil(&grid), part_1(&grid), "seed {seed}");
+            assert_eq!(part_2_flood_fill(&grid), part_2(&grid), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy(EXAMPLE)).unwrap(),
+            solve(std::str::from_utf8(EXAMPLE).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use std::io::{self, BufRead};
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Unexpected character {0:?}")]
+    InvalidCharacter(char),
+}
+
+/// What is wrong with a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineStatus {
+    /// A chunk was closed with the wrong character.
+    Corrupted {
+        /// The index of the wrong character in the line.
+        position: usize,
+        expected: char,
+        found: char,
+        score: u64,
+    },
+    /// Some chunks are never closed. `completion` closes them, and is empty if the line is
+    /// complete.
+    Incomplete { completion: String, score: u64 },
+}
+
+/// The first wrong closing character of a line, as its index, the expected character and the
+/// found one. Otherwise leaves the characters that would close the open chunks in `stack`,
+/// innermost last.
+fn check_line(line: &[u8], stack: &mut Vec<u8>) -> Result<(), (usize, u8, u8)> {
+    stack.clear();
+    for (position, &ch) in line.iter().enumerate() {
+        match ch {
+            b'(' => stack.push(b')'),
+            b'[' => stack.push(b']'),
+            b'{' => stack.push(b'}'),
+            b'<' => stack.push(b'>'),
+            _ => {
+                if let Some(check) = stack.pop()
+                    && ch != check
+                {
+                    return Err((position, check, ch));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+const fn error_score(ch: u8) -> u64 {
+    match ch {
+        b')' => 3,
+        b']' => 57,
+        b'}' => 1197,
+        b'>' => 25137,
+        _ => unreachable!(),
+    }
+}
+
+fn completion_score(completion: impl Iterator<Item = u8>) -> u64 {
+    completion.fold(0, |sum, ch| {
+        sum * 5
+            + match ch {
+                b')' => 1,
+                b']' => 2,
+                b'}' => 3,
+                b'>' => 4,
+                _ => unreachable!(),
+            }
+    })
+}
+
+/// Scores a single line: `Ok` with the completion score if it is incomplete,
+/// `Err` with the syntax error score if it is corrupted.
+fn score_line(line: &[u8], stack: &mut Vec<u8>) -> Result<u64, u64> {
+    check_line(line, stack).map_err(|(_, _, found)| error_score(found))?;
+    Ok(completion_score(stack.iter().rev().copied()))
+}
+
+/// What is wrong with each line, with the characters that complete the incomplete ones.
+#[must_use]
+pub fn diagnose(input: &[u8]) -> Vec<LineStatus> {
+    let mut stack = Vec::new();
+    input
+        .split(|&ch| ch == b'\n')
+        .map(|line| match check_line(line, &mut stack) {
+            Err((position, expected, found)) => LineStatus::Corrupted {
+                position,
+                expected: char::from(expected),
+                found: char::from(found),
+                score: error_score(found),
+            },
+            Ok(()) => LineStatus::Incomplete {
+                completion: stack.iter().rev().copied().map(char::from).collect(),
+                score: completion_score(stack.iter().rev().copied()),
+            },
+        })
+        .collect()
+}
+
+/// Where a corrupted line goes wrong, as a compiler would report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("line {line}, column {column}: Expected {expected:?}, but found {found:?}")]
+pub struct Diagnostic {
+    /// The line number, counting from 1.
+    pub line: usize,
+    /// The column of the wrong character, counting from 1.
+    pub column: usize,
+    pub expected: char,
+    pub found: char,
+}
+
+/// A diagnostic for each corrupted line, in order.
+#[must_use]
+pub fn diagnostics(input: &[u8]) -> Vec<Diagnostic> {
+    diagnose(input)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(ix, status)| match status {
+            LineStatus::Corrupted {
+                position,
+                expected,
+                found,
+                ..
+            } => Some(Diagnostic {
+                line: ix + 1,
+                column: position + 1,
+                expected,
+                found,
+            }),
+            LineStatus::Incomplete { .. } => None,
+        })
+        .collect()
+}
+
+fn median(scores: &mut [u64]) -> u64 {
+    let n = scores.len();
+    *scores.select_nth_unstable(n / 2).1
+}
+
+#[aoc_generator(day10)]
+fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    if let Some(ch) = input.chars().find(|ch| !"()[]{}<>\n".contains(*ch)) {
+        return Err(ParseError::InvalidCharacter(ch));
+    }
+    Ok(input.as_bytes().to_vec())
+}
+
+#[aoc(day10, part1)]
+fn part_1(input: &[u8]) -> u64 {
+    let mut stack = Vec::new();
+    input
+        .split(|&ch| ch == b'\n')
+        .filter_map(|line| score_line(line, &mut stack).err())
+        .sum()
+}
+
+#[aoc(day10, part2)]
+fn part_2(input: &[u8]) -> u64 {
+    let mut stack = Vec::new();
+    let mut scores = input
+        .split(|&ch| ch == b'\n')
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| score_line(line, &mut stack).ok())
+        .collect::<Vec<_>>();
+    if scores.is_empty() {
+        return 0;
+    }
+    median(&mut scores)
+}
+
+/// Solves both parts reading one line at a time. Part 2 still keeps one
+/// score per incomplete line, since it needs their median.
+///
+/// # Errors
+///
+/// Returns an error if reading fails.
+pub fn solve_stream(mut reader: impl BufRead) -> io::Result<(u64, u64)> {
+    let mut line = Vec::new();
+    let mut stack = Vec::new();
+    let mut error_score = 0;
+    let mut scores = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        let trimmed = line.trim_ascii_end();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match score_line(trimmed, &mut stack) {
+            Ok(score) => scores.push(score),
+            Err(score) => error_score += score,
+        }
+    }
+    let completion_score = if scores.is_empty() {
+        0
+    } else {
+        median(&mut scores)
+    };
+    Ok((error_score, completion_score))
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Parsed = Vec<u8>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Solves both parts.
+///
+/// # Errors
+///
+/// Never fails; the signature matches the other days.
+pub fn solve(input: &str) -> Result<(u64, u64), crate::Error> {
+    Day10::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &[u8] = b"\
+        [({(<(())[]>[[{[]{<()<>>\n\
+        [(()[<>])]({[<{<<[]>>(\n\
+        {([(<{}[<>[]}>{[]{[(<()>\n\
+        (((({<>}<{<{<>}{[]{[]{}\n\
+        [[<[([]))<([[{}[[()]]]\n\
+        [{[{({}]{}}([{[{{{}}([]\n\
+        {<[[]]>}<{[{[{[]{()[[[]\n\
+        [<(<(<(<{}))><([]([]()\n\
+        <{([([[(<>()){}]>(<<{{\n\
+        <{([{{}}[<[[[<>{}]]]>[]]\
+    ";
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("(]\n[x]").is_err());
+        assert_eq!(part_2(&parse("(]").unwrap()), 0);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let result = part_1(EXAMPLE);
+        assert_eq!(result, 26_397);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let result = part_2(EXAMPLE);
+        assert_eq!(result, 288_957);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let statuses = diagnose(EXAMPLE);
+        assert_eq!(statuses.len(), 10);
+        assert_eq!(
+            statuses[0],
+            LineStatus::Incomplete {
+                completion: "}}]])})]".to_string(),
+                score: 288_957
+            }
+        );
+        assert_eq!(
+            statuses[2],
+            LineStatus::Corrupted {
+                position: 12,
+                expected: ']',
+                found: '}',
+                score: 1197
+            }
+        );
+        let completions = statuses
+            .iter()
+            .filter_map(|status| match status {
+                LineStatus::Incomplete { completion, .. } => Some(completion.as_str()),
+                LineStatus::Corrupted { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            completions,
+            ["}}]])})]", ")}>]})", "}}>}>))))", "]]}}]}]}>", "])}>"]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let diagnostics = diagnostics(EXAMPLE);
+        assert_eq!(
+            diagnostics.iter().map(|d| d.line).collect::<Vec<_>>(),
+            [3, 5, 6, 8, 9]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "line 3, column 13: Expected ']', but found '}'"
+        );
+        assert_eq!(
+            diagnostics[1],
+            Diagnostic {
+                line: 5,
+                column: 9,
+                expected: ']',
+                found: ')'
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_stream() {
+        let result = solve_stream(EXAMPLE).unwrap();
+        assert_eq!(result, (26_397, 288_957));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(10) {
+            let lines = parse(&input).unwrap();
+            let expected = (part_1(&lines), part_2(&lines));
+            assert_eq!(
+                solve_stream(input.as_bytes()).unwrap(),
+                expected,
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy(EXAMPLE)).unwrap(),
+            solve(std::str::from_utf8(EXAMPLE).unwrap()).unwrap()
+        );
+    }
+}
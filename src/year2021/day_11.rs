@@ -0,0 +1,618 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::animation::Pace;
+use crate::util::array_grid::ArrayGrid;
+use crate::util::grid::{Border, Grid, GridError};
+use crate::util::simulation::{Simulation, hash_state};
+use crate::util::style::Style;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidGrid(#[from] GridError),
+}
+
+#[aoc_generator(day11)]
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    Grid::from_bytes(input)?.try_map(|&ch| match ch {
+        b'0'..=b'9' => Ok(ch - b'0'),
+        _ => Err(ParseError::SyntaxError),
+    })
+}
+
+/// When an octopus flashes, and the energy it starts over from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The energy that makes an octopus flash. Octopuses gain no more energy in a step once they
+    /// reach it.
+    pub threshold: u8,
+    /// The energy of an octopus at the end of a step it flashed in. Octopuses reset to the
+    /// threshold or above never flash again.
+    pub reset: u8,
+}
+
+impl Rules {
+    /// The puzzle's rules: octopuses flash when their energy goes above 9, and drop to 0.
+    pub const PUZZLE: Self = Self {
+        threshold: 10,
+        reset: 0,
+    };
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::PUZZLE
+    }
+}
+
+/// Adds one to `energy`, unless it is at `threshold` already. Whether it just reached it.
+const fn charge(energy: &mut u8, threshold: u8) -> bool {
+    if *energy >= threshold {
+        return false;
+    }
+    *energy += 1;
+    *energy == threshold
+}
+
+/// The octopuses flashing in one step.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StepReport {
+    /// The octopuses that flashed, in waves. The first wave flashed from the step's own energy,
+    /// and each later wave from the flashes of the one before.
+    pub waves: Vec<Vec<[usize; 2]>>,
+}
+
+impl StepReport {
+    /// The number of octopuses that flashed.
+    #[must_use]
+    pub fn flashes(&self) -> usize {
+        self.waves.iter().map(Vec::len).sum()
+    }
+
+    /// The octopuses that flashed, in the order that they did.
+    pub fn flashed(&self) -> impl Iterator<Item = [usize; 2]> {
+        self.waves.iter().flatten().copied()
+    }
+}
+
+/// The octopuses of any size of grid, stepped by `Simulation::step`.
+#[derive(Debug, Clone)]
+pub struct Octopuses {
+    grid: Grid<u8>,
+    rules: Rules,
+}
+
+impl Octopuses {
+    /// The octopuses of `grid`, following the puzzle's rules.
+    #[must_use]
+    pub const fn new(grid: Grid<u8>) -> Self {
+        Self::with_rules(grid, Rules::PUZZLE)
+    }
+
+    #[must_use]
+    pub const fn with_rules(grid: Grid<u8>, rules: Rules) -> Self {
+        Self { grid, rules }
+    }
+
+    /// The energy of each octopus.
+    #[must_use]
+    pub const fn grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    /// Whether every octopus flashed in the step that `report` is from.
+    #[must_use]
+    pub fn synchronized(&self, report: &StepReport) -> bool {
+        report.flashes() == self.grid.width() * self.grid.height()
+    }
+}
+
+impl Simulation for Octopuses {
+    type Output = StepReport;
+
+    fn step(&mut self) -> StepReport {
+        let Rules { threshold, reset } = self.rules;
+        let grid = &mut self.grid;
+        let mut wave = Vec::new();
+        for pos in grid.positions() {
+            if charge(&mut grid[pos], threshold) {
+                wave.push(pos);
+            }
+        }
+        let mut report = StepReport::default();
+        while !wave.is_empty() {
+            let mut next = Vec::new();
+            for &pos in &wave {
+                for neighbor_pos in grid.neighbors8(pos) {
+                    if charge(&mut grid[neighbor_pos], threshold) {
+                        next.push(neighbor_pos);
+                    }
+                }
+            }
+            report.waves.push(std::mem::replace(&mut wave, next));
+        }
+        for pos in report.flashed() {
+            grid[pos] = reset;
+        }
+        report
+    }
+
+    fn state_hash(&self) -> u64 {
+        hash_state(self.grid.as_slice())
+    }
+}
+
+/// The puzzle's 10 by 10 octopuses, following the puzzle's rules. An octopus at 0 has already
+/// flashed in the step.
+#[derive(Debug, Clone)]
+struct FixedOctopuses {
+    grid: ArrayGrid<u8, 10, 10>,
+}
+
+impl FixedOctopuses {
+    fn new(grid: &Grid<u8>) -> Option<Self> {
+        if grid.width() != 10 {
+            return None;
+        }
+        Some(Self {
+            grid: ArrayGrid::try_from_slice(grid.as_slice())?,
+        })
+    }
+}
+
+impl Simulation for FixedOctopuses {
+    type Output = usize;
+
+    fn step(&mut self) -> usize {
+        const THRESHOLD: u8 = Rules::PUZZLE.threshold;
+        const RESET: u8 = Rules::PUZZLE.reset;
+        let mut stack = [[0; 2]; 100];
+        let mut len = 0;
+        for row in 0..10 {
+            for col in 0..10 {
+                let cell = &mut self.grid[[row, col]];
+                *cell += 1;
+                if *cell == THRESHOLD {
+                    *cell = RESET;
+                    stack[len] = [row, col];
+                    len += 1;
+                }
+            }
+        }
+        let mut flashes = len;
+        while len > 0 {
+            len -= 1;
+            let pos = stack[len];
+            for neighbor_pos in self.grid.neighbors8(pos) {
+                let neighbor = &mut self.grid[neighbor_pos];
+                if *neighbor != RESET {
+                    *neighbor += 1;
+                    if *neighbor == THRESHOLD {
+                        *neighbor = RESET;
+                        stack[len] = neighbor_pos;
+                        len += 1;
+                        flashes += 1;
+                    }
+                }
+            }
+        }
+        flashes
+    }
+
+    fn state_hash(&self) -> u64 {
+        hash_state(&self.grid)
+    }
+}
+
+/// The number of flashes in the first `steps` steps.
+#[must_use]
+pub fn total_flashes(grid: &Grid<u8>, steps: usize) -> usize {
+    let mut octopuses = Octopuses::new(grid.clone());
+    octopuses
+        .steps()
+        .take(steps)
+        .map(|report| report.flashes())
+        .sum()
+}
+
+#[aoc(day11, part1)]
+fn part_1(grid: &Grid<u8>) -> usize {
+    total_flashes(grid, 100)
+}
+
+#[aoc(day11, part2)]
+fn part_2(grid: &Grid<u8>) -> usize {
+    let mut octopuses = Octopuses::new(grid.clone());
+    octopuses.run_until(Octopuses::synchronized)
+}
+
+/// Steps the octopuses of `grid` once, and returns how many flashed. Each wave of flashes is
+/// spread at once, by counting the octopuses around each one that flashed in the wave before.
+fn step_convolved(grid: &mut Grid<u8>) -> usize {
+    let Rules { threshold, reset } = Rules::PUZZLE;
+    let mut wave = grid.map(|_| false);
+    for (energy, flashed) in grid.as_mut_slice().iter_mut().zip(wave.as_mut_slice()) {
+        *flashed = charge(energy, threshold);
+    }
+    let mut flashes = 0;
+    loop {
+        let count = wave.as_slice().iter().filter(|&&flashed| flashed).count();
+        if count == 0 {
+            break;
+        }
+        flashes += count;
+        let around = wave.convolve3x3(0, &Border::Constant(false), |block| {
+            block.iter().flatten().filter(|&&flashed| flashed).count()
+        });
+        for ((energy, flashed), &around) in grid
+            .as_mut_slice()
+            .iter_mut()
+            .zip(wave.as_mut_slice())
+            .zip(around.as_slice())
+        {
+            *flashed = (0..around).fold(false, |reached, _| charge(energy, threshold) || reached);
+        }
+    }
+    for energy in grid.as_mut_slice() {
+        if *energy == threshold {
+            *energy = reset;
+        }
+    }
+    flashes
+}
+
+#[aoc(day11, part1, convolve)]
+fn part_1_convolve(grid: &Grid<u8>) -> usize {
+    let mut grid = grid.clone();
+    (0..100).map(|_| step_convolved(&mut grid)).sum()
+}
+
+#[aoc(day11, part1, fixed)]
+fn part_1_fixed(grid: &Grid<u8>) -> Option<usize> {
+    let mut octopuses = FixedOctopuses::new(grid)?;
+    Some(octopuses.steps().take(100).sum())
+}
+
+#[aoc(day11, part2, fixed)]
+fn part_2_fixed(grid: &Grid<u8>) -> Option<usize> {
+    let mut octopuses = FixedOctopuses::new(grid)?;
+    Some(octopuses.run_until(|_, &flashes| flashes == 100))
+}
+
+/// Both answers from one simulation. The octopuses may already flash together within the first
+/// 100 steps.
+#[must_use]
+pub fn solve_both(grid: &Grid<u8>) -> (usize, usize) {
+    let mut octopuses = Octopuses::new(grid.clone());
+    let mut total = 0;
+    let mut synchronized = None;
+    for step in 1..=100 {
+        let report = octopuses.step();
+        total += report.flashes();
+        if octopuses.synchronized(&report) {
+            synchronized.get_or_insert(step);
+        }
+    }
+    let synchronized =
+        synchronized.unwrap_or_else(|| 100 + octopuses.run_until(Octopuses::synchronized));
+    (total, synchronized)
+}
+
+/// Saves the steps as a GIF animation, until every octopus flashes at once or `max_steps` have
+/// passed. Octopuses glow brighter as their energy builds, and flash in yellow.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+#[cfg(feature = "image")]
+pub fn export_gif(
+    grid: &Grid<u8>,
+    max_steps: usize,
+    path: &std::path::Path,
+) -> image::ImageResult<()> {
+    use image::Rgb;
+
+    use crate::util::export::{self, DARK, LIGHT};
+
+    const GLOW: Rgb<u8> = Rgb([0x40, 0x60, 0xc0]);
+    let mut octopuses = Octopuses::new(grid.clone());
+    let mut frames = vec![grid.clone()];
+    for _ in 0..max_steps {
+        let report = octopuses.step();
+        frames.push(octopuses.grid.clone());
+        if octopuses.synchronized(&report) {
+            break;
+        }
+    }
+    export::save_gif(path, frames, 100, |&energy| match energy {
+        0 => LIGHT,
+        energy => export::blend(DARK, GLOW, energy, Rules::PUZZLE.threshold - 1),
+    })
+}
+
+/// How an energy is drawn: `0`-`9`, then `a`-`z` for 10 to 35, and `+` above that.
+fn glyph(energy: u8) -> char {
+    char::from_digit(u32::from(energy), 36).unwrap_or('+')
+}
+
+/// One frame of the animation: a status line, then the grid with the octopuses that flashed in
+/// `report` in bright yellow and the others in grey.
+fn frame(step: usize, report: &StepReport, grid: &Grid<u8>, style: Style) -> String {
+    let mut result = format!("Step {step}: {} flashes\x1b[K\n", report.flashes());
+    let mut flashed = grid.map(|_| false);
+    for pos in report.flashed() {
+        flashed[pos] = true;
+    }
+    for (cells, flashed) in grid.rows().zip(flashed.rows()) {
+        for (&cell, &flashed) in cells.iter().zip(flashed) {
+            let color = if flashed { "1;93" } else { "90" };
+            // Writing to a `String` can not fail.
+            let _ = write!(result, "{}{}", style.set(color), glyph(cell));
+        }
+        let _ = writeln!(result, "{}", style.reset());
+    }
+    result
+}
+
+/// Animates the octopuses in the terminal, redrawing the grid in place at the given pace, until
+/// every octopus flashes at once or `max_steps` have passed.
+///
+/// # Errors
+///
+/// Returns an error if `out` can not be written to.
+pub fn visualize(
+    grid: &Grid<u8>,
+    max_steps: usize,
+    pace: Pace,
+    style: Style,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut octopuses = Octopuses::new(grid.clone());
+    write!(
+        out,
+        "\x1b[2J\x1b[H{}",
+        frame(0, &StepReport::default(), &octopuses.grid, style)
+    )?;
+    for step in 1..=max_steps {
+        out.flush()?;
+        pace.wait()?;
+        let report = octopuses.step();
+        write!(
+            out,
+            "\x1b[H{}",
+            frame(step, &report, &octopuses.grid, style)
+        )?;
+        if octopuses.synchronized(&report) {
+            break;
+        }
+    }
+    out.flush()
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Parsed = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input.as_bytes()).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day11::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &[u8] = b"\
+        5483143223\n\
+        2745854711\n\
+        5264556173\n\
+        6141336146\n\
+        6357385478\n\
+        4167524645\n\
+        2176841721\n\
+        6882881134\n\
+        4846848554\n\
+        5283751526\
+    ";
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(b"123\n45").is_err());
+        assert!(parse(b"123\n4a6").is_err());
+        assert!(parse(b"123\n456\n\n").is_err());
+        assert!(parse(b"").is_err());
+    }
+
+    #[test]
+    fn test_part_1() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1(&grid);
+        assert_eq!(result, 1656);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_2(&grid);
+        assert_eq!(result, 195);
+    }
+
+    #[test]
+    fn test_step_report() {
+        let grid = parse(b"11111\n19991\n19191\n19991\n11111").unwrap();
+        let mut octopuses = Octopuses::new(grid);
+        let report = octopuses.step();
+        assert_eq!(report.waves.len(), 2);
+        assert_eq!(report.waves[0].len(), 8);
+        assert_eq!(report.waves[1], [[2, 2]]);
+        assert_eq!(report.flashes(), 9);
+        assert_eq!(report.flashed().last(), Some([2, 2]));
+        assert_eq!(
+            octopuses.grid().map(|&energy| glyph(energy)).to_string(),
+            "34543\n40004\n50005\n40004\n34543\n"
+        );
+        assert_eq!(octopuses.step(), StepReport::default());
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = Rules {
+            threshold: 4,
+            reset: 1,
+        };
+        let mut octopuses = Octopuses::with_rules(parse(b"000\n030\n000").unwrap(), rules);
+        assert_eq!(octopuses.step().waves, [[[1, 1]]]);
+        assert_eq!(octopuses.grid().as_slice(), [2, 2, 2, 2, 1, 2, 2, 2, 2]);
+        assert_eq!(octopuses.step().flashes(), 0);
+        let report = octopuses.step();
+        assert_eq!(report.waves.len(), 2);
+        assert_eq!(report.waves[1], [[1, 1]]);
+        assert!(octopuses.synchronized(&report));
+        assert!(
+            octopuses
+                .grid()
+                .as_slice()
+                .iter()
+                .all(|&energy| energy == 1)
+        );
+
+        let rules = Rules {
+            threshold: 12,
+            reset: 0,
+        };
+        let mut octopuses = Octopuses::with_rules(parse(b"9").unwrap(), rules);
+        let flashes = octopuses.steps().take(4).map(|report| report.flashes());
+        assert_eq!(flashes.collect::<Vec<_>>(), [0, 0, 1, 0]);
+        assert_eq!(octopuses.grid().as_slice(), [1]);
+        assert_eq!(glyph(11), 'b');
+    }
+
+    #[test]
+    fn test_total_flashes() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!(total_flashes(&grid, 10), 204);
+        assert_eq!(total_flashes(&grid, 100), 1656);
+    }
+
+    #[test]
+    fn test_part_1_convolve() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1_convolve(&grid);
+        assert_eq!(result, 1656);
+    }
+
+    #[test]
+    fn test_part_1_fixed() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1_fixed(&grid);
+        assert_eq!(result, Some(1656));
+    }
+
+    #[test]
+    fn test_part_2_fixed() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_2_fixed(&grid);
+        assert_eq!(result, Some(195));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let octopuses = Octopuses::new(parse(EXAMPLE).unwrap());
+        let cycle = octopuses.find_cycle(1_000);
+        assert_eq!(cycle, Some((195, 10)));
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let mut octopuses = Octopuses::new(parse(EXAMPLE).unwrap());
+        octopuses.fast_forward(1_000_000_000, 1_000);
+        let mut expected = Octopuses::new(parse(EXAMPLE).unwrap());
+        expected.run_for(200);
+        assert_eq!(octopuses.grid, expected.grid);
+    }
+
+    #[test]
+    fn test_solve_both_early_sync() {
+        let grid = parse(b"999\n999\n999").unwrap();
+        assert_eq!(solve_both(&grid), (part_1(&grid), 1));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let grid = parse(b"11111\n19991\n19191\n19991\n11111").unwrap();
+        let mut out = Vec::new();
+        visualize(&grid, 1, Pace::Auto(Duration::ZERO), Style::COLOR, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let (first, second) = out
+            .split_once("\x1b[H")
+            .unwrap()
+            .1
+            .split_once("\x1b[H")
+            .unwrap();
+        assert!(first.starts_with("Step 0: 0 flashes"));
+        assert!(second.starts_with("Step 1: 9 flashes"));
+        assert_eq!(second.matches("\x1b[1;93m0").count(), 9);
+        assert_eq!(second.matches("\x1b[90m").count(), 16);
+        let mut out = Vec::new();
+        visualize(&grid, 1, Pace::Auto(Duration::ZERO), Style::PLAIN, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.ends_with("Step 1: 9 flashes\x1b[K\n34543\n40004\n50005\n40004\n34543\n"));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(11) {
+            let grid = parse(input.as_bytes()).unwrap();
+            assert_eq!(part_1_fixed(&grid), Some(part_1(&grid)), "seed {seed}");
+            assert_eq!(part_1_convolve(&grid), part_1(&grid), "seed {seed}");
+            assert_eq!(part_2_fixed(&grid), Some(part_2(&grid)), "seed {seed}");
+            assert_eq!(
+                solve_both(&grid),
+                (part_1(&grid), part_2(&grid)),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy(EXAMPLE)).unwrap(),
+            solve(std::str::from_utf8(EXAMPLE).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,699 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use smallvec::SmallVec;
+use thiserror::Error;
+use tracing::instrument;
+
+#[cfg(feature = "arena")]
+use crate::solver::Arena;
+use crate::solver::Solver;
+use crate::util::bitset::BitSet;
+use crate::util::graph::Graph;
+use crate::util::par_search::par_dfs_sum;
+
+/// The small caves a path has been through, by index.
+type Visited = BitSet<1>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cave {
+    Start,
+    End,
+    Large(u8),
+    Small(u8),
+}
+
+impl Cave {
+    #[must_use]
+    pub fn into_index(self) -> usize {
+        match self {
+            Self::Start => 0,
+            Self::End => 1,
+            Self::Large(ix) | Self::Small(ix) => usize::from(ix),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_large(self) -> bool {
+        matches!(self, Self::Large(..))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaveSystem {
+    graph: Graph,
+    caves: SmallVec<[Cave; 16]>,
+    /// The connections as they were read, by cave index, so that the input can be written back
+    /// out the same.
+    edges: Vec<[usize; 2]>,
+}
+
+impl CaveSystem {
+    /// Builds a cave system from `(cave, cave)` connections given by name.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there are more than 64 caves, or two large caves are connected.
+    pub fn from_edges<'a>(
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ParseError> {
+        Self::from_edges_into(Graph::new(), edges)
+    }
+
+    /// Like `str::parse`, with the edges collected in `arena` first, so that the graph is
+    /// allocated with room for every cave.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a line is not an edge, there are more than 64 caves, or two large caves are
+    /// connected.
+    #[cfg(feature = "arena")]
+    pub fn parse_in(s: &str, arena: &Arena) -> Result<Self, ParseError> {
+        let mut edges = bumpalo::collections::Vec::new_in(arena);
+        for line in s.lines() {
+            edges.push(line.split_once('-').ok_or(ParseError::SyntaxError)?);
+        }
+        // Each edge brings at most two new caves, and more than 64 are refused anyway.
+        let caves = (2 * edges.len() + 2).min(Visited::CAPACITY + 1);
+        Self::from_edges_into(Graph::with_capacity(caves), edges)
+    }
+
+    /// Adds the caves and their connections to `graph`, which is empty but may have room
+    /// already.
+    fn from_edges_into<'a>(
+        mut graph: Graph,
+        edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ParseError> {
+        let start = graph.intern("start");
+        graph.intern("end");
+        let edges = edges.into_iter();
+        let mut pairs = Vec::with_capacity(edges.size_hint().0);
+        for (first, second) in edges {
+            let first = graph.intern(first);
+            let second = graph.intern(second);
+            graph.add_edge(first, second);
+            if first != start {
+                graph.add_edge(second, first);
+            }
+            pairs.push([first, second]);
+        }
+        if graph.len() > Visited::CAPACITY {
+            return Err(ParseError::TooManyCaves(graph.len()));
+        }
+        let caves = (0..)
+            .zip(graph.nodes())
+            .map(|(ix, (_, name))| {
+                let large = name.bytes().all(|b| b.is_ascii_uppercase());
+                match (ix, large) {
+                    (0, _) => Cave::Start,
+                    (1, _) => Cave::End,
+                    (_, true) => Cave::Large(ix),
+                    (_, false) => Cave::Small(ix),
+                }
+            })
+            .collect::<SmallVec<[Cave; 16]>>();
+        // A path could go back and forth between two large caves forever.
+        if let Some(&[first, second]) = pairs
+            .iter()
+            .find(|&&[first, second]| caves[first].is_large() && caves[second].is_large())
+        {
+            return Err(ParseError::LargeCavesConnected(
+                graph.name(first).to_string(),
+                graph.name(second).to_string(),
+            ));
+        }
+        Ok(Self {
+            graph,
+            caves,
+            edges: pairs,
+        })
+    }
+
+    #[must_use]
+    pub const fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    #[must_use]
+    pub fn caves(&self) -> &[Cave] {
+        &self.caves
+    }
+
+    #[must_use]
+    pub fn name(&self, cave: Cave) -> &str {
+        self.graph.name(cave.into_index())
+    }
+}
+
+impl Display for CaveSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, &[first, second]) in self.edges.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}-{}", self.graph.name(first), self.graph.name(second))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Too many caves: {0} (at most 64 are supported)")]
+    TooManyCaves(usize),
+    #[error("Large caves {0} and {1} are connected, so there are endless paths")]
+    LargeCavesConnected(String, String),
+}
+
+impl FromStr for CaveSystem {
+    type Err = ParseError;
+
+    /// Hands the edges to [`CaveSystem::from_edges`] as they are read, without collecting them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut valid = true;
+        let caves = Self::from_edges(s.lines().map_while(|line| {
+            let edge = line.split_once('-');
+            valid &= edge.is_some();
+            edge
+        }));
+        if !valid {
+            return Err(ParseError::SyntaxError);
+        }
+        caves
+    }
+}
+
+#[aoc_generator(day12)]
+fn parse(input: &str) -> Result<CaveSystem, ParseError> {
+    input.parse()
+}
+
+#[aoc(day12, part1)]
+fn part_1(caves: &CaveSystem) -> usize {
+    PathCounter::new(caves).count(Cave::Start, Visited::EMPTY, false)
+}
+
+#[aoc(day12, part2)]
+fn part_2(caves: &CaveSystem) -> usize {
+    PathCounter::new(caves).count(Cave::Start, Visited::EMPTY, true)
+}
+
+#[aoc(day12, part1, dfs)]
+fn part_1_dfs(caves: &CaveSystem) -> usize {
+    count_paths(caves, false)
+}
+
+#[aoc(day12, part2, dfs)]
+fn part_2_dfs(caves: &CaveSystem) -> usize {
+    count_paths(caves, true)
+}
+
+#[aoc(day12, part1, bfs)]
+fn part_1_bfs(caves: &CaveSystem) -> usize {
+    count_paths_bfs(caves, false)
+}
+
+#[aoc(day12, part2, bfs)]
+fn part_2_bfs(caves: &CaveSystem) -> usize {
+    count_paths_bfs(caves, true)
+}
+
+#[aoc(day12, part1, parallel)]
+fn part_1_parallel(caves: &CaveSystem) -> usize {
+    count_paths_parallel(caves, false)
+}
+
+#[aoc(day12, part2, parallel)]
+fn part_2_parallel(caves: &CaveSystem) -> usize {
+    count_paths_parallel(caves, true)
+}
+
+/// Counts the paths to the end from each state of the search at most once. A state is the cave,
+/// the small caves visited so far, and whether one of them may still be visited again.
+struct PathCounter<'a> {
+    caves: &'a CaveSystem,
+    memo: HashMap<(usize, Visited, bool), usize>,
+}
+
+impl<'a> PathCounter<'a> {
+    fn new(caves: &'a CaveSystem) -> Self {
+        Self {
+            caves,
+            memo: HashMap::new(),
+        }
+    }
+
+    fn count(&mut self, cave: Cave, visited: Visited, can_revisit: bool) -> usize {
+        if cave == Cave::End {
+            return 1;
+        }
+        let key = (cave.into_index(), visited, can_revisit);
+        if let Some(&count) = self.memo.get(&key) {
+            return count;
+        }
+        let caves = self.caves;
+        let mut count = 0;
+        for &next in caves.graph.neighbors(cave.into_index()) {
+            let next = caves.caves[next];
+            let index = next.into_index();
+            if next == Cave::Start {
+                continue;
+            }
+            if next.is_large() {
+                count += self.count(next, visited, can_revisit);
+            } else if !visited.contains(index) {
+                count += self.count(next, visited.with(index), can_revisit);
+            } else if can_revisit {
+                count += self.count(next, visited, false);
+            }
+        }
+        self.memo.insert(key, count);
+        count
+    }
+}
+
+#[instrument(level = "debug", skip(caves), ret)]
+fn count_paths(caves: &CaveSystem, visit_twice: bool) -> usize {
+    par_dfs_sum(
+        (Cave::Start, Visited::EMPTY, visit_twice),
+        3,
+        &|(cave, visited, visit_twice), pending: &mut Vec<_>| {
+            if cave == Cave::End {
+                return 1;
+            }
+            for &next in caves.graph.neighbors(cave.into_index()) {
+                let next = caves.caves[next];
+                if next == Cave::Start {
+                    continue;
+                }
+                let index = next.into_index();
+                if next.is_large() || !visited.contains(index) {
+                    pending.push((next, visited.with(index), visit_twice));
+                } else if visit_twice {
+                    pending.push((next, visited, false));
+                }
+            }
+            0
+        },
+    )
+}
+
+/// A state of the search: the cave, the small caves visited so far, and whether one of them may
+/// still be visited again.
+type State = (Cave, Visited, bool);
+
+/// Takes one step along every path, merging the paths that end up in the same state. Returns how
+/// many of the paths had already reached the end, and the states of the rest, with how many paths
+/// reach each.
+fn advance(caves: &CaveSystem, frontier: HashMap<State, usize>) -> (usize, HashMap<State, usize>) {
+    let mut next_frontier = HashMap::new();
+    let mut finished = 0;
+    for ((cave, visited, can_revisit), ways) in frontier {
+        if cave == Cave::End {
+            finished += ways;
+            continue;
+        }
+        for &next in caves.graph.neighbors(cave.into_index()) {
+            let next = caves.caves[next];
+            let index = next.into_index();
+            let state = if next == Cave::Start {
+                continue;
+            } else if next.is_large() {
+                (next, visited, can_revisit)
+            } else if !visited.contains(index) {
+                (next, visited.with(index), can_revisit)
+            } else if can_revisit {
+                (next, visited, false)
+            } else {
+                continue;
+            };
+            *next_frontier.entry(state).or_default() += ways;
+        }
+    }
+    (finished, next_frontier)
+}
+
+/// Walks every path breadth first, one step at a time, keeping only how many paths are in each
+/// state rather than the paths themselves.
+#[instrument(level = "debug", skip(caves), ret)]
+fn count_paths_bfs(caves: &CaveSystem, visit_twice: bool) -> usize {
+    let mut frontier = HashMap::from([((Cave::Start, Visited::EMPTY, visit_twice), 1)]);
+    let mut finished = 0;
+    while !frontier.is_empty() {
+        let (done, next_frontier) = advance(caves, frontier);
+        finished += done;
+        frontier = next_frontier;
+    }
+    finished
+}
+
+/// How many steps [`count_paths_parallel`] takes before handing the search to the rayon threads.
+const SPLIT_DEPTH: usize = 3;
+
+/// Takes the first few steps of every path, merging the paths that end up in the same state, and
+/// then counts the rest of the paths on the rayon threads.
+///
+/// The states are split into one chunk per thread, and each chunk gets a [`PathCounter`] of its
+/// own. States reached from more than one chunk are counted once per chunk, but each chunk still
+/// reuses its own counts.
+#[instrument(level = "debug", skip(caves), ret)]
+fn count_paths_parallel(caves: &CaveSystem, visit_twice: bool) -> usize {
+    let mut frontier = HashMap::from([((Cave::Start, Visited::EMPTY, visit_twice), 1)]);
+    let mut finished = 0;
+    for _ in 0..SPLIT_DEPTH {
+        let (done, next_frontier) = advance(caves, frontier);
+        finished += done;
+        frontier = next_frontier;
+    }
+    let frontier = frontier.into_iter().collect::<Vec<_>>();
+    let chunk_size = frontier.len().div_ceil(rayon::current_num_threads()).max(1);
+    let rest = frontier
+        .par_chunks(chunk_size)
+        .map(|chunk| {
+            let mut counter = PathCounter::new(caves);
+            chunk
+                .iter()
+                .map(|&((cave, visited, can_revisit), ways)| {
+                    ways * counter.count(cave, visited, can_revisit)
+                })
+                .sum::<usize>()
+        })
+        .sum::<usize>();
+    finished + rest
+}
+
+/// Every path from the start to the end, as the names of the caves along it. With `allow_twice`,
+/// one small cave may be visited twice. The paths are found one at a time, depth first.
+pub fn paths(caves: &CaveSystem, allow_twice: bool) -> impl Iterator<Item = Vec<&str>> {
+    let mut pending = vec![(vec![Cave::Start], Visited::EMPTY, allow_twice)];
+    std::iter::from_fn(move || {
+        while let Some((path, visited, can_revisit)) = pending.pop() {
+            let &cave = path.last()?;
+            if cave == Cave::End {
+                return Some(path.into_iter().map(|cave| caves.name(cave)).collect());
+            }
+            for &next in caves.graph.neighbors(cave.into_index()).iter().rev() {
+                let next = caves.caves[next];
+                let index = next.into_index();
+                let (next_visited, next_can_revisit) = if next == Cave::Start {
+                    continue;
+                } else if next.is_large() {
+                    (visited, can_revisit)
+                } else if !visited.contains(index) {
+                    (visited.with(index), can_revisit)
+                } else if can_revisit {
+                    (visited, false)
+                } else {
+                    continue;
+                };
+                let mut path = path.clone();
+                path.push(next);
+                pending.push((path, next_visited, next_can_revisit));
+            }
+        }
+        None
+    })
+}
+
+/// Both answers from one memo: the part 2 search passes through every state of the part 1 search
+/// once it has used up its revisit.
+#[must_use]
+#[instrument(level = "debug", skip_all, ret)]
+pub fn solve_both(caves: &CaveSystem) -> (usize, usize) {
+    let mut counter = PathCounter::new(caves);
+    let part_2 = counter.count(Cave::Start, Visited::EMPTY, true);
+    let part_1 = counter.count(Cave::Start, Visited::EMPTY, false);
+    (part_1, part_2)
+}
+
+/// Both answers, each counted by [`count_paths_parallel`].
+#[must_use]
+pub fn solve_both_parallel(caves: &CaveSystem) -> (usize, usize) {
+    rayon::join(|| part_1_parallel(caves), || part_2_parallel(caves))
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Parsed = CaveSystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    #[cfg(feature = "arena")]
+    fn parse_in(input: &str, arena: &Arena) -> Result<Self::Parsed, crate::Error> {
+        CaveSystem::parse_in(input, arena).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day12::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+    use test_case::test_case;
+
+    const EXAMPLE1: &str = "\
+        start-A\n\
+        start-b\n\
+        A-c\n\
+        A-b\n\
+        b-d\n\
+        A-end\n\
+        b-end\
+    ";
+
+    const EXAMPLE2: &str = "\
+        dc-end\n\
+        HN-start\n\
+        start-kj\n\
+        dc-start\n\
+        dc-HN\n\
+        LN-dc\n\
+        HN-end\n\
+        kj-sa\n\
+        kj-HN\n\
+        kj-dc\
+    ";
+
+    const EXAMPLE3: &str = "\
+        fs-end\n\
+        he-DX\n\
+        fs-he\n\
+        start-DX\n\
+        pj-DX\n\
+        end-zg\n\
+        zg-sl\n\
+        zg-pj\n\
+        pj-he\n\
+        RW-he\n\
+        fs-DX\n\
+        pj-RW\n\
+        zg-RW\n\
+        start-pj\n\
+        he-WI\n\
+        zg-he\n\
+        pj-fs\n\
+        start-RW\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let result = parse(EXAMPLE1).unwrap();
+        assert_eq!(
+            result.caves.as_slice(),
+            [
+                Cave::Start,
+                Cave::End,
+                Cave::Large(2),
+                Cave::Small(3),
+                Cave::Small(4),
+                Cave::Small(5)
+            ]
+        );
+        assert_eq!(
+            (0..6)
+                .map(|ix| result.graph.neighbor_mask(ix).unwrap())
+                .collect::<Vec<_>>(),
+            [
+                //dc_bA$^
+                0b00_1100, // start -> A(2), b(3)
+                0b00_1100, // end -> A(2), b(3)
+                0b01_1010, // A(2) -> end, b(3), c(4)
+                0b10_0110, // b(3) -> end, A(2), d(5)
+                0b00_0100, // c(4) -> A(2)
+                0b00_1000, // d(5) -> b(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_caves_connected() {
+        for input in ["start-A\nA-B\nB-end", "start-A\nA-A\nA-end"] {
+            assert!(matches!(
+                parse(input),
+                Err(ParseError::LargeCavesConnected(..))
+            ));
+            assert!(solve(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        for example in [EXAMPLE1, EXAMPLE2, EXAMPLE3] {
+            assert_eq!(parse(example).unwrap().to_string(), example);
+        }
+    }
+
+    #[test]
+    fn test_from_edges() {
+        let caves = CaveSystem::from_edges([("start", "A"), ("A", "b"), ("A", "end")]).unwrap();
+        assert_eq!(
+            caves.caves(),
+            [Cave::Start, Cave::End, Cave::Large(2), Cave::Small(3)]
+        );
+        assert_eq!(caves.name(Cave::Small(3)), "b");
+        assert_eq!(caves, parse("start-A\nA-b\nA-end").unwrap());
+    }
+
+    #[test]
+    fn test_many_caves() {
+        let names = (0..40).map(|ix| format!("c{ix}")).collect::<Vec<_>>();
+        let chain = std::iter::once("start")
+            .chain(names.iter().map(String::as_str))
+            .chain(["end"])
+            .collect::<Vec<_>>();
+        let caves =
+            CaveSystem::from_edges(chain.windows(2).map(|pair| (pair[0], pair[1]))).unwrap();
+        assert_eq!(caves.caves().len(), 42);
+        assert_eq!(solve_both(&caves), (1, 1));
+        assert_eq!((part_1_dfs(&caves), part_2_dfs(&caves)), (1, 1));
+        let too_many = (0..63)
+            .map(|ix| format!("start-c{ix}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(matches!(
+            parse(&too_many),
+            Err(ParseError::TooManyCaves(65))
+        ));
+    }
+
+    #[test]
+    fn test_paths() {
+        let caves = parse(EXAMPLE1).unwrap();
+        let paths = paths(&caves, false)
+            .map(|path| path.join(","))
+            .collect::<Vec<_>>();
+        assert_eq!(paths.len(), 10);
+        assert!(paths.contains(&"start,A,end".to_string()));
+        assert!(paths.contains(&"start,A,b,A,c,A,end".to_string()));
+        assert!(paths.contains(&"start,b,end".to_string()));
+        let twice = super::paths(&caves, true)
+            .map(|path| path.join(","))
+            .collect::<HashSet<_>>();
+        assert_eq!(twice.len(), 36);
+        assert!(twice.contains("start,A,c,A,c,A,b,end"));
+        assert!(paths.iter().all(|path| twice.contains(path)));
+    }
+
+    #[test_case(EXAMPLE1, false => 10)]
+    #[test_case(EXAMPLE2, false => 19)]
+    #[test_case(EXAMPLE3, false => 226)]
+    #[test_case(EXAMPLE1, true => 36)]
+    fn test_count_paths(input: &str, visit_twice: bool) -> usize {
+        let caves = parse(input).unwrap();
+        count_paths(&caves, visit_twice)
+    }
+
+    #[test_case(EXAMPLE1 => (10, 36))]
+    #[test_case(EXAMPLE2 => (19, 103))]
+    #[test_case(EXAMPLE3 => (226, 3_509))]
+    fn test_parts(input: &str) -> (usize, usize) {
+        let caves = parse(input).unwrap();
+        (part_1(&caves), part_2(&caves))
+    }
+
+    #[test_case(EXAMPLE1 => (10, 36))]
+    #[test_case(EXAMPLE2 => (19, 103))]
+    #[test_case(EXAMPLE3 => (226, 3_509))]
+    #[test_case("start-end" => (1, 1))]
+    fn test_parts_parallel(input: &str) -> (usize, usize) {
+        let caves = parse(input).unwrap();
+        (part_1_parallel(&caves), part_2_parallel(&caves))
+    }
+
+    #[test_case(EXAMPLE1 => (10, 36))]
+    #[test_case(EXAMPLE3 => (226, 3_509))]
+    #[test_case("start-end" => (1, 1))]
+    fn test_parts_bfs(input: &str) -> (usize, usize) {
+        let caves = parse(input).unwrap();
+        (part_1_bfs(&caves), part_2_bfs(&caves))
+    }
+
+    #[test_case(EXAMPLE1 => (10, 36))]
+    #[test_case(EXAMPLE2 => (19, 103))]
+    #[test_case(EXAMPLE3 => (226, 3_509))]
+    fn test_solve_both(input: &str) -> (usize, usize) {
+        let caves = parse(input).unwrap();
+        solve_both(&caves)
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(12) {
+            let caves = parse(&input).unwrap();
+            let expected = (part_1(&caves), part_2(&caves));
+            assert_eq!(solve_both(&caves), expected, "seed {seed}");
+            assert_eq!(
+                (part_1_dfs(&caves), part_2_dfs(&caves)),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(
+                (part_1_bfs(&caves), part_2_bfs(&caves)),
+                expected,
+                "seed {seed}"
+            );
+            assert_eq!(solve_both_parallel(&caves), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        for example in [EXAMPLE1, EXAMPLE2, EXAMPLE3] {
+            assert_eq!(solve(&untidy(example)).unwrap(), solve(example).unwrap());
+        }
+    }
+}
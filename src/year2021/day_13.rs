@@ -0,0 +1,794 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::answer::BinaryImage;
+use crate::explain::Explain;
+use crate::solver::Solver;
+use crate::util::geometry::Point;
+use crate::util::grid::Grid;
+use crate::util::ocr;
+use crate::util::parse::{NumberError, byte_lines, parse_unsigned, split_once_bytes};
+use crate::util::render::{self, Backend};
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] NumberError),
+}
+
+/// Why the manual page could not be folded into an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FoldError {
+    #[error("There are no folds")]
+    NoFolds,
+    #[error("The folded dots do not spell letters")]
+    Unreadable,
+    #[error("The dot at {},{} lies on the line of `{instruction}`", .point.x, .point.y)]
+    OnFoldLine {
+        point: Point<i32>,
+        instruction: Instruction,
+    },
+}
+
+/// A dot on a fold line makes the input invalid, while the others only leave it without answer.
+fn solver_error(error: FoldError) -> crate::Error {
+    match error {
+        FoldError::NoFolds | FoldError::Unreadable => crate::Error::NoSolution,
+        FoldError::OnFoldLine { .. } => crate::Error::invalid_input(error),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
+    FoldAlongX(i32),
+    FoldAlongY(i32),
+}
+
+impl Instruction {
+    /// Where `point` ends up after the fold. Points past the line are mirrored across it, and
+    /// points before it stay put.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the point lies on the fold line, where the puzzle never puts a dot.
+    pub const fn apply(self, point: Point<i32>) -> Result<Point<i32>, FoldError> {
+        let (coordinate, line) = match self {
+            Self::FoldAlongX(x) => (point.x, x),
+            Self::FoldAlongY(y) => (point.y, y),
+        };
+        if coordinate == line {
+            return Err(FoldError::OnFoldLine {
+                point,
+                instruction: self,
+            });
+        }
+        if coordinate < line {
+            return Ok(point);
+        }
+        // `line - (coordinate - line)`, as `2 * line` could overflow.
+        let mirrored = line - (coordinate - line);
+        Ok(match self {
+            Self::FoldAlongX(_) => Point {
+                x: mirrored,
+                ..point
+            },
+            Self::FoldAlongY(_) => Point {
+                y: mirrored,
+                ..point
+            },
+        })
+    }
+
+    const fn is_along_x(self) -> bool {
+        matches!(self, Self::FoldAlongX(_))
+    }
+
+    const fn line(self) -> i32 {
+        match self {
+            Self::FoldAlongX(line) | Self::FoldAlongY(line) => line,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FoldAlongX(x) => write!(f, "fold along x={x}"),
+            Self::FoldAlongY(y) => write!(f, "fold along y={y}"),
+        }
+    }
+}
+
+/// Applies every fold in turn.
+fn fold_all<'a>(
+    instructions: impl IntoIterator<Item = &'a Instruction>,
+    point: Point<i32>,
+) -> Result<Point<i32>, FoldError> {
+    instructions
+        .into_iter()
+        .try_fold(point, |pt, instr| instr.apply(pt))
+}
+
+impl Instruction {
+    /// Reads an instruction such as `fold along y=7`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless `s` is a fold along x or y, at a coordinate.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, ParseError> {
+        let (key, value) = split_once_bytes(s, b"=").ok_or(ParseError::SyntaxError)?;
+        Ok(match key {
+            b"fold along x" => Self::FoldAlongX(parse_unsigned(value)?),
+            b"fold along y" => Self::FoldAlongY(parse_unsigned(value)?),
+            _ => return Err(ParseError::SyntaxError),
+        })
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+/// A sequence of folds composed into one lookup per axis, of where each coordinate ends up. A fold
+/// along x never moves a point along y, so each axis only depends on its own folds.
+///
+/// Coordinates that land on a fold line have no entry, and are folded one instruction at a time
+/// to find which fold they land on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldTransform {
+    xs: Vec<Option<i32>>,
+    ys: Vec<Option<i32>>,
+    instructions: Vec<Instruction>,
+}
+
+impl FoldTransform {
+    /// Composes the folds for coordinates from 0 up to `width` and `height`, or up to twice the
+    /// furthest fold line on each axis if that is less. Points outside of that are still folded,
+    /// one instruction at a time.
+    #[must_use]
+    pub fn new(instructions: &[Instruction], width: i32, height: i32) -> Self {
+        let along_x = || instructions.iter().filter(|instr| instr.is_along_x());
+        let along_y = || instructions.iter().filter(|instr| !instr.is_along_x());
+        // Folding along `n` sends anything past `2 * n` below zero, where the lookup cannot help.
+        let reach = |along_x: bool| {
+            instructions
+                .iter()
+                .filter(|instr| instr.is_along_x() == along_x)
+                .map(|instr| instr.line().saturating_mul(2).saturating_add(1))
+                .max()
+                .unwrap_or(0)
+        };
+        let width = width.min(reach(true));
+        let height = height.min(reach(false));
+        Self {
+            xs: (0..width)
+                .map(|x| fold_all(along_x(), Point { x, y: 0 }).ok().map(|pt| pt.x))
+                .collect(),
+            ys: (0..height)
+                .map(|y| fold_all(along_y(), Point { x: 0, y }).ok().map(|pt| pt.y))
+                .collect(),
+            instructions: instructions.to_vec(),
+        }
+    }
+
+    /// Composes the folds for every coordinate up to the largest among `points`, as far as the
+    /// folds reach.
+    #[must_use]
+    pub fn covering(instructions: &[Instruction], points: &[Point<i32>]) -> Self {
+        let end = |coordinate: fn(&Point<i32>) -> i32| {
+            points
+                .iter()
+                .map(coordinate)
+                .max()
+                .map_or(0, |max| max.saturating_add(1))
+        };
+        Self::new(instructions, end(|pt| pt.x), end(|pt| pt.y))
+    }
+
+    /// Where `point` ends up after every fold.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the point lands on a fold line.
+    pub fn apply(&self, point: Point<i32>) -> Result<Point<i32>, FoldError> {
+        let lookup = |table: &[Option<i32>], v: i32| {
+            usize::try_from(v)
+                .ok()
+                .and_then(|ix| table.get(ix).copied().flatten())
+        };
+        match (lookup(&self.xs, point.x), lookup(&self.ys, point.y)) {
+            (Some(x), Some(y)) => Ok(Point { x, y }),
+            _ => fold_all(&self.instructions, point),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManualPage {
+    points: Vec<Point<i32>>,
+    instructions: Vec<Instruction>,
+}
+
+impl ManualPage {
+    #[must_use]
+    pub const fn new(points: Vec<Point<i32>>, instructions: Vec<Instruction>) -> Self {
+        Self {
+            points,
+            instructions,
+        }
+    }
+
+    #[must_use]
+    pub fn points(&self) -> &[Point<i32>] {
+        &self.points
+    }
+
+    pub const fn points_mut(&mut self) -> &mut Vec<Point<i32>> {
+        &mut self.points
+    }
+
+    #[must_use]
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub const fn instructions_mut(&mut self) -> &mut Vec<Instruction> {
+        &mut self.instructions
+    }
+}
+
+impl ManualPage {
+    /// Every fold, composed.
+    #[must_use]
+    pub fn transform(&self) -> FoldTransform {
+        FoldTransform::covering(&self.instructions, &self.points)
+    }
+
+    /// The points after applying every fold.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a point lands on a fold line.
+    pub fn folded(&self) -> Result<HashSet<Point<i32>>, FoldError> {
+        let transform = self.transform();
+        self.points
+            .iter()
+            .map(|&point| transform.apply(point))
+            .collect()
+    }
+
+    /// The folded paper, with a pixel lit for each dot, or `None` if there are no dots.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a point lands on a fold line.
+    pub fn image(&self) -> Result<Option<Grid<bool>>, FoldError> {
+        Ok(render::from_points(self.folded()?))
+    }
+
+    /// Like `folded`, but applies the folds to each point one at a time.
+    fn folded_one_by_one(&self) -> Result<HashSet<Point<i32>>, FoldError> {
+        self.points
+            .iter()
+            .map(|&point| fold_all(&self.instructions, point))
+            .collect()
+    }
+}
+
+impl fmt::Display for ManualPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for point in &self.points {
+            writeln!(f, "{},{}", point.x, point.y)?;
+        }
+        for instruction in &self.instructions {
+            write!(f, "\n{instruction}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ManualPage {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = byte_lines(s.as_bytes());
+        let points = lines
+            .by_ref()
+            .take_while(|l| !l.is_empty())
+            .map(|line| {
+                let (x, y) = split_once_bytes(line, b",").ok_or(ParseError::SyntaxError)?;
+                Ok(Point {
+                    x: parse_unsigned(x)?,
+                    y: parse_unsigned(y)?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        let instructions = lines
+            .map(Instruction::from_bytes)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            points,
+            instructions,
+        })
+    }
+}
+
+#[aoc_generator(day13)]
+fn parse(input: &str) -> Result<ManualPage, ParseError> {
+    input.parse()
+}
+
+#[aoc(day13, part1)]
+fn part_1(manual_page: &ManualPage) -> Result<usize, FoldError> {
+    let &first = manual_page.instructions.first().ok_or(FoldError::NoFolds)?;
+    let points = manual_page
+        .points
+        .iter()
+        .map(|&point| first.apply(point))
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(points.len())
+}
+
+#[aoc(day13, part2)]
+fn part_2(manual_page: &ManualPage) -> Result<String, FoldError> {
+    ocr::recognize(manual_page.folded()?).ok_or(FoldError::Unreadable)
+}
+
+#[aoc(day13, part2, one_by_one)]
+fn part_2_one_by_one(manual_page: &ManualPage) -> Result<String, FoldError> {
+    ocr::recognize(manual_page.folded_one_by_one()?).ok_or(FoldError::Unreadable)
+}
+
+/// Both answers from a single folding of each point. The points after the first fold are
+/// counted on the way, so part 1 still has its answer when a later fold fails.
+pub fn solve_both(
+    manual_page: &ManualPage,
+) -> (Result<usize, FoldError>, Result<String, FoldError>) {
+    let (part_1, folded) = fold_both(manual_page);
+    let read = |points: HashSet<_>| ocr::recognize(points).ok_or(FoldError::Unreadable);
+    (part_1, folded.and_then(read))
+}
+
+/// The dots left on the paper after some folds.
+type Dots = HashSet<Point<i32>>;
+
+/// The number of points after the first fold, and the points after every fold.
+fn fold_both(manual_page: &ManualPage) -> (Result<usize, FoldError>, Result<Dots, FoldError>) {
+    let Some((&first, rest)) = manual_page.instructions.split_first() else {
+        let points = manual_page.points.iter().copied().collect();
+        return (Err(FoldError::NoFolds), Ok(points));
+    };
+    let once = manual_page
+        .points
+        .iter()
+        .map(|&point| first.apply(point))
+        .collect::<Result<HashSet<_>, _>>();
+    let once = match once {
+        Ok(once) => once,
+        Err(error) => return (Err(error), Err(error)),
+    };
+    let part_1 = once.len();
+    let once = once.into_iter().collect::<Vec<_>>();
+    let transform = FoldTransform::covering(rest, &once);
+    let folded = once
+        .into_iter()
+        .map(|point| transform.apply(point))
+        .collect::<Result<HashSet<_>, _>>();
+    (Ok(part_1), folded)
+}
+
+#[aoc(day13, part2, art)]
+fn part_2_art(manual_page: &ManualPage) -> Result<String, FoldError> {
+    Ok(render(&manual_page.folded()?, Backend::HalfBlocks))
+}
+
+#[aoc(day13, part2, braille)]
+fn part_2_braille(manual_page: &ManualPage) -> Result<String, FoldError> {
+    Ok(render(&manual_page.folded()?, Backend::Braille))
+}
+
+#[aoc(day13, part2, plain)]
+fn part_2_plain(manual_page: &ManualPage) -> Result<String, FoldError> {
+    Ok(render(&manual_page.folded()?, Backend::Plain))
+}
+
+/// Draws the points, starting on a new line so that the picture lines up under the label.
+#[must_use]
+pub fn render<S: BuildHasher>(points: &HashSet<Point<i32>, S>, backend: Backend) -> String {
+    render::from_points(points.iter().copied()).map_or_else(String::new, |grid| {
+        format!("\n{}", render::render(&grid, backend))
+    })
+}
+
+/// Saves the folded paper, as given by [`ManualPage::folded`], as a PNG image.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+#[cfg(feature = "image")]
+pub fn export_png<S: BuildHasher>(
+    folded: &HashSet<Point<i32>, S>,
+    path: &std::path::Path,
+) -> image::ImageResult<()> {
+    let grid =
+        render::from_points(folded.iter().copied()).unwrap_or_else(|| Grid::new(vec![false], 1, 1));
+    crate::util::export::save_bool_png(path, &grid)
+}
+
+/// The widest paper that `Day13::explain` draws. Before the last folds, it is hundreds of columns
+/// wide.
+const EXPLAIN_WIDTH: usize = 80;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Parsed = ManualPage;
+    type Part1 = usize;
+    /// The folded paper, which reads as its letters.
+    type Part2 = BinaryImage;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).map_err(solver_error)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        parsed
+            .image()
+            .map_err(solver_error)?
+            .ok_or(crate::Error::NoSolution)
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        let (part_1, folded) = fold_both(parsed);
+        let part_2 = render::from_points(folded.map_err(solver_error)?);
+        Ok((
+            part_1.map_err(solver_error)?,
+            part_2.ok_or(crate::Error::NoSolution)?,
+        ))
+    }
+
+    /// No picture when a dot lands on a fold line, as there is no folded paper to draw.
+    fn visualize(parsed: &Self::Parsed, backend: Backend) -> Option<String> {
+        parsed.folded().ok().map(|points| render(&points, backend))
+    }
+
+    /// The paper after each fold, drawn once it is narrow enough. Stops at a fold that lands a
+    /// dot on its line.
+    fn explain(parsed: &Self::Parsed, explain: &mut Explain) {
+        let mut dots = parsed.points.iter().copied().collect::<Dots>();
+        for &instruction in &parsed.instructions {
+            let folded = dots
+                .iter()
+                .map(|&point| instruction.apply(point))
+                .collect::<Result<Dots, _>>();
+            dots = match folded {
+                Ok(folded) => folded,
+                Err(error) => {
+                    explain.step(instruction.to_string(), error);
+                    return;
+                }
+            };
+            let mut detail = format!("{} dots", dots.len());
+            if let Some(grid) = render::from_points(dots.iter().copied())
+                && grid.width() <= EXPLAIN_WIDTH
+            {
+                detail = format!("{detail}\n{}", render::render(&grid, Backend::Plain));
+            }
+            explain.step(instruction.to_string(), detail);
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(usize, BinaryImage), crate::Error> {
+    Day13::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+    use crate::{Answer, geninput};
+
+    const EXAMPLE: &str = "\
+        6,10\n\
+        0,14\n\
+        9,10\n\
+        0,3\n\
+        10,4\n\
+        4,11\n\
+        6,0\n\
+        6,12\n\
+        4,1\n\
+        0,13\n\
+        10,12\n\
+        3,4\n\
+        3,0\n\
+        8,4\n\
+        1,10\n\
+        2,14\n\
+        8,10\n\
+        9,0\n\
+        \n\
+        fold along y=7\n\
+        fold along x=5\
+    ";
+
+    #[test]
+    fn test_parse() {
+        macro_rules! pt {
+            ($x:expr,$y:expr) => {
+                Point { x: $x, y: $y }
+            };
+        }
+        let result = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            result.points,
+            [
+                pt!(6, 10),
+                pt!(0, 14),
+                pt!(9, 10),
+                pt!(0, 3),
+                pt!(10, 4),
+                pt!(4, 11),
+                pt!(6, 0),
+                pt!(6, 12),
+                pt!(4, 1),
+                pt!(0, 13),
+                pt!(10, 12),
+                pt!(3, 4),
+                pt!(3, 0),
+                pt!(8, 4),
+                pt!(1, 10),
+                pt!(2, 14),
+                pt!(8, 10),
+                pt!(9, 0)
+            ]
+        );
+        assert_eq!(
+            result.instructions,
+            [Instruction::FoldAlongY(7), Instruction::FoldAlongX(5)]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        assert_eq!(manual_page.to_string(), EXAMPLE);
+        let folds_only = ManualPage::new(Vec::new(), manual_page.instructions);
+        assert_eq!(parse(&folds_only.to_string()).unwrap(), folds_only);
+    }
+
+    #[test]
+    fn test_part_1_no_folds() {
+        let manual_page = parse("6,10\n0,14\n\n").unwrap();
+        assert_eq!(part_1(&manual_page), Err(FoldError::NoFolds));
+    }
+
+    #[test]
+    fn test_part_1() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        let result = part_1(&manual_page);
+        assert_eq!(result, Ok(17));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&manual_page), Err(FoldError::Unreadable));
+        let result = part_2_art(&manual_page).unwrap();
+        assert_eq!(
+            result,
+            "\n\
+            █▀▀▀█\n\
+            █   █\n\
+            ▀▀▀▀▀\
+            "
+        );
+    }
+
+    #[test]
+    fn test_part_2_braille() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2_braille(&manual_page).unwrap(), "\n⡏⠉⡇\n⠉⠉⠁");
+    }
+
+    #[test]
+    fn test_image() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        let image = manual_page.image().unwrap().unwrap();
+        assert_eq!((image.width(), image.height()), (5, 5));
+        assert_eq!(
+            part_2_plain(&manual_page).unwrap(),
+            "\n#####\n#...#\n#...#\n#...#\n#####"
+        );
+        assert_eq!(parse("\nfold along x=1").unwrap().image(), Ok(None));
+    }
+
+    #[test]
+    fn test_part_2_letters() {
+        let manual_page =
+            parse("0,0\n1,0\n2,0\n3,0\n0,1\n0,2\n1,2\n2,2\n0,3\n0,4\n0,5\n\n").unwrap();
+        assert_eq!(part_2(&manual_page), Ok("F".to_string()));
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            solve_both(&manual_page),
+            (Ok(17), Err(FoldError::Unreadable))
+        );
+        let manual_page =
+            parse("0,0\n1,0\n2,0\n3,0\n0,1\n0,2\n1,2\n2,2\n0,3\n0,4\n0,5\n\nfold along x=5")
+                .unwrap();
+        let expected = (part_1(&manual_page), part_2(&manual_page));
+        assert_eq!(expected, (Ok(11), Ok("F".to_string())));
+        assert_eq!(solve_both(&manual_page), expected);
+    }
+
+    #[test]
+    fn test_fold_transform() {
+        let manual_page = parse(EXAMPLE).unwrap();
+        let transform = manual_page.transform();
+        assert_eq!(transform.xs.len(), 11);
+        assert_eq!(transform.ys.len(), 15);
+        assert_eq!(
+            transform.apply(Point { x: 6, y: 10 }),
+            Ok(Point { x: 4, y: 4 })
+        );
+        assert_eq!(
+            transform.apply(Point { x: 3, y: 0 }),
+            Ok(Point { x: 3, y: 0 })
+        );
+        // Outside the lookup, the folds are applied one at a time.
+        assert_eq!(
+            transform.apply(Point { x: 12, y: 16 }),
+            Ok(Point { x: -2, y: -2 })
+        );
+        assert_eq!(
+            transform.apply(Point { x: -1, y: 3 }),
+            Ok(Point { x: -1, y: 3 })
+        );
+        assert_eq!(manual_page.folded(), manual_page.folded_one_by_one());
+    }
+
+    #[test]
+    fn test_solver_answers() {
+        use crate::solver::DynSolver;
+
+        let [part_1, part_2] = DynSolver::solve(&Day13, EXAMPLE).unwrap();
+        assert_eq!(part_1, Answer::Int(17));
+        assert_eq!(part_2.to_string(), "\n#####\n#...#\n#...#\n#...#\n#####");
+        assert_eq!(part_2.submittable(), None);
+        let letters = "0,0\n1,0\n2,0\n3,0\n0,1\n0,2\n1,2\n2,2\n0,3\n0,4\n0,5\n\nfold along x=5";
+        let part_2 = Day13.solve_part(letters, 2).unwrap();
+        assert!(matches!(part_2, Answer::Grid(_)));
+        assert_eq!(part_2.to_string(), "F");
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut explain = Explain::new();
+        Day13::explain(&parse(EXAMPLE).unwrap(), &mut explain);
+        let steps = explain.steps();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].title, "fold along y=7");
+        assert_eq!(steps[0].detail.lines().nth(1), Some("#.##..#..#."));
+        assert_eq!(
+            steps[1].detail,
+            "16 dots\n#####\n#...#\n#...#\n#...#\n#####"
+        );
+        let mut explain = Explain::new();
+        Day13::explain(&parse("1,2\n\nfold along y=2").unwrap(), &mut explain);
+        assert_eq!(
+            explain.to_string(),
+            "fold along y=2: The dot at 1,2 lies on the line of `fold along y=2`\n"
+        );
+    }
+
+    #[test]
+    fn test_on_fold_line() {
+        let on_line = |point, instruction| FoldError::OnFoldLine { point, instruction };
+        let fold = Instruction::FoldAlongX(5);
+        assert_eq!(
+            fold.apply(Point { x: 5, y: 2 }),
+            Err(on_line(Point { x: 5, y: 2 }, fold))
+        );
+        // 8 folds to 2 along x=5, which lies on the line of the second fold.
+        let manual_page = parse("8,1\n1,1\n\nfold along x=5\nfold along x=2").unwrap();
+        let expected = on_line(Point { x: 2, y: 1 }, Instruction::FoldAlongX(2));
+        assert_eq!(part_1(&manual_page), Ok(2));
+        assert_eq!(manual_page.folded(), Err(expected));
+        assert_eq!(manual_page.folded_one_by_one(), Err(expected));
+        assert_eq!(solve_both(&manual_page), (Ok(2), Err(expected)));
+        assert_eq!(
+            expected.to_string(),
+            "The dot at 2,1 lies on the line of `fold along x=2`"
+        );
+        assert!(matches!(
+            Day13::solve("8,1\n\nfold along x=8"),
+            Err(crate::Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let fold = Instruction::FoldAlongY(i32::MAX - 1);
+        assert_eq!(
+            fold.apply(Point { x: 0, y: i32::MAX }),
+            Ok(Point {
+                x: 0,
+                y: i32::MAX - 2
+            })
+        );
+        let manual_page = parse("100000,0\n\nfold along x=70000").unwrap();
+        assert_eq!(
+            manual_page.folded(),
+            Ok(HashSet::from([Point { x: 40_000, y: 0 }]))
+        );
+    }
+
+    #[test]
+    fn test_far_out_points() {
+        let manual_page = parse("2000000000,0\n1,1\n\nfold along x=5").unwrap();
+        let transform = manual_page.transform();
+        assert_eq!((transform.xs.len(), transform.ys.len()), (11, 0));
+        assert_eq!(
+            manual_page.folded(),
+            Ok(HashSet::from([
+                Point {
+                    x: 10 - 2_000_000_000,
+                    y: 0
+                },
+                Point { x: 1, y: 1 }
+            ]))
+        );
+        let manual_page = parse("0,2000000000\n1,1\n\nfold along y=3\nfold along x=4").unwrap();
+        let transform = manual_page.transform();
+        assert_eq!((transform.xs.len(), transform.ys.len()), (2, 7));
+        assert_eq!(manual_page.folded(), manual_page.folded_one_by_one());
+        assert_eq!(solve_both(&manual_page).0, Ok(2));
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(13) {
+            let manual_page = parse(&input).unwrap();
+            assert_eq!(
+                manual_page.folded(),
+                manual_page.folded_one_by_one(),
+                "seed {seed}"
+            );
+            let expected = (part_1(&manual_page), part_2(&manual_page));
+            assert!(expected.1.is_ok(), "seed {seed}");
+            assert_eq!(part_2_one_by_one(&manual_page), expected.1, "seed {seed}");
+            assert_eq!(solve_both(&manual_page), expected, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
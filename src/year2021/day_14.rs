@@ -0,0 +1,707 @@
+use std::fmt::{self, Display};
+use std::mem;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::explain::Explain;
+use crate::solver::Solver;
+use crate::util::counter::DenseCounter;
+use crate::util::matrix::SquareMatrix;
+use crate::util::simulation::{Simulation, hash_state};
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("The template needs at least two elements")]
+    ShortTemplate,
+    #[error("No rule, or more than one, for {0}{1}")]
+    MissingRule(char, char),
+}
+
+/// An element, by its index among the letters of `Instructions::elements`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Element(u8);
+
+impl Element {
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The letters seen so far, each numbered in the order it was first seen.
+#[derive(Debug, Default)]
+struct Alphabet {
+    letters: Vec<char>,
+}
+
+impl Alphabet {
+    fn intern(&mut self, letter: u8) -> Result<Element, ParseError> {
+        if !letter.is_ascii_uppercase() {
+            return Err(ParseError::SyntaxError);
+        }
+        let letter = char::from(letter);
+        let index = self
+            .letters
+            .iter()
+            .position(|&l| l == letter)
+            .unwrap_or_else(|| {
+                self.letters.push(letter);
+                self.letters.len() - 1
+            });
+        // There are only 26 uppercase letters.
+        Ok(Element(
+            u8::try_from(index).map_err(|_| ParseError::SyntaxError)?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rule {
+    pair: (Element, Element),
+    to_insert: Element,
+}
+
+impl Rule {
+    #[must_use]
+    pub const fn new(pair: (Element, Element), to_insert: Element) -> Self {
+        Self { pair, to_insert }
+    }
+
+    #[must_use]
+    pub const fn pair(self) -> (Element, Element) {
+        self.pair
+    }
+
+    #[must_use]
+    pub const fn to_insert(self) -> Element {
+        self.to_insert
+    }
+
+    fn parse(s: &str, alphabet: &mut Alphabet) -> Result<Self, ParseError> {
+        let [a, b, b' ', b'-', b'>', b' ', insert] = *s.as_bytes() else {
+            return Err(ParseError::SyntaxError);
+        };
+        Ok(Self {
+            pair: (alphabet.intern(a)?, alphabet.intern(b)?),
+            to_insert: alphabet.intern(insert)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instructions {
+    elements: Vec<char>,
+    initial: Vec<Element>,
+    rules: Vec<Rule>,
+}
+
+impl Instructions {
+    #[must_use]
+    pub const fn new(elements: Vec<char>, initial: Vec<Element>, rules: Vec<Rule>) -> Self {
+        Self {
+            elements,
+            initial,
+            rules,
+        }
+    }
+
+    /// The letter of each element, by index.
+    #[must_use]
+    pub fn elements(&self) -> &[char] {
+        &self.elements
+    }
+
+    #[must_use]
+    pub fn letter(&self, element: Element) -> char {
+        self.elements[element.index()]
+    }
+
+    #[must_use]
+    pub fn initial(&self) -> &[Element] {
+        &self.initial
+    }
+
+    pub const fn initial_mut(&mut self) -> &mut Vec<Element> {
+        &mut self.initial
+    }
+
+    #[must_use]
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub const fn rules_mut(&mut self) -> &mut Vec<Rule> {
+        &mut self.rules
+    }
+}
+
+impl Display for Instructions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &element in &self.initial {
+            write!(f, "{}", self.letter(element))?;
+        }
+        writeln!(f)?;
+        for rule in &self.rules {
+            let (first, second) = rule.pair;
+            write!(
+                f,
+                "\n{}{} -> {}",
+                self.letter(first),
+                self.letter(second),
+                self.letter(rule.to_insert)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Instructions {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut alphabet = Alphabet::default();
+        let mut lines = s.lines();
+        let initial = lines
+            .next()
+            .ok_or(ParseError::SyntaxError)?
+            .bytes()
+            .map(|letter| alphabet.intern(letter))
+            .collect::<Result<Vec<_>, _>>()?;
+        if lines.next() != Some("") {
+            return Err(ParseError::SyntaxError);
+        }
+        let rules = lines
+            .map(|line| Rule::parse(line, &mut alphabet))
+            .collect::<Result<Vec<_>, _>>()?;
+        if initial.len() < 2 {
+            return Err(ParseError::ShortTemplate);
+        }
+        // Every pair of elements that can ever be next to each other needs exactly one rule. Those
+        // are all the elements seen.
+        let elements = alphabet.letters;
+        let n = elements.len();
+        let mut rule_counts = vec![0; n * n];
+        for rule in &rules {
+            rule_counts[pair_index(n, rule.pair.0, rule.pair.1)] += 1;
+        }
+        if let Some(ix) = rule_counts.iter().position(|&count| count != 1) {
+            return Err(ParseError::MissingRule(elements[ix / n], elements[ix % n]));
+        }
+        Ok(Self {
+            elements,
+            initial,
+            rules,
+        })
+    }
+}
+
+#[aoc_generator(day14)]
+fn parse(input: &str) -> Result<Instructions, ParseError> {
+    input.parse()
+}
+
+#[aoc(day14, part1)]
+fn part_1(instructions: &Instructions) -> u64 {
+    spread_after(instructions, 10)
+}
+
+#[aoc(day14, part2)]
+fn part_2(instructions: &Instructions) -> u64 {
+    spread_after(instructions, 40)
+}
+
+/// Raises the transition matrix of the pair counts to the 40th power.
+#[aoc(day14, part2, matrix)]
+fn part_2_matrix(instructions: &Instructions) -> Option<u128> {
+    simulate(instructions, 40)
+}
+
+#[aoc(day14, part1, naive)]
+fn part_1_naive(instructions: &Instructions) -> u64 {
+    let n = instructions.elements.len();
+    let mut table = vec![None; n * n];
+    for rule in &instructions.rules {
+        table[pair_index(n, rule.pair.0, rule.pair.1)] = Some(rule.to_insert);
+    }
+    let mut polymer = instructions.initial.clone();
+    for _ in 0..10 {
+        let mut next = Vec::with_capacity(2 * polymer.len());
+        next.push(polymer[0]);
+        for (&a, &b) in polymer.iter().zip(&polymer[1..]) {
+            if let Some(inserted) = table[pair_index(n, a, b)] {
+                next.push(inserted);
+            }
+            next.push(b);
+        }
+        polymer = next;
+    }
+    let mut element_counts = DenseCounter::<usize, u64>::new(n);
+    for element in polymer {
+        element_counts.increment(element.index());
+    }
+    let (_, max) = element_counts.most_common(1)[0];
+    let (_, min) = element_counts.least_common(1)[0];
+    max - min
+}
+
+/// The elements of the polymer after `rounds` rounds, one at a time.
+///
+/// Only the pairs still to be expanded are kept, one for each round at most, so the polymer
+/// never has to fit in memory. It does double in length every round, though.
+pub fn expand(instructions: &Instructions, rounds: usize) -> impl Iterator<Item = Element> + '_ {
+    let n = instructions.elements.len();
+    let mut table = vec![None; n * n];
+    for rule in &instructions.rules {
+        table[pair_index(n, rule.pair.0, rule.pair.1)] = Some(rule.to_insert);
+    }
+    let mut pairs = instructions.initial.windows(2);
+    let mut pending = Vec::new();
+    let first = instructions.initial.first().copied();
+    first.into_iter().chain(std::iter::from_fn(move || {
+        loop {
+            let (a, b, rounds) = pending
+                .pop()
+                .or_else(|| pairs.next().map(|pair| (pair[0], pair[1], rounds)))?;
+            match table[pair_index(n, a, b)] {
+                Some(inserted) if rounds > 0 => {
+                    pending.push((inserted, b, rounds - 1));
+                    pending.push((a, inserted, rounds - 1));
+                }
+                _ => return Some(b),
+            }
+        }
+    }))
+}
+
+/// The index of the pair `e1` `e2` among the pairs of `n` elements.
+const fn pair_index(n: usize, e1: Element, e2: Element) -> usize {
+    e1.index() * n + e2.index()
+}
+
+#[derive(Debug, Clone)]
+struct Polymer {
+    elements: usize,
+    rules: Vec<Vec<usize>>,
+    counts: DenseCounter<usize, u64>,
+    next: DenseCounter<usize, u64>,
+    leading: usize,
+}
+
+impl Polymer {
+    fn new(instructions: &Instructions) -> Self {
+        let n = instructions.elements.len();
+        let mut rules = vec![vec![]; n * n];
+        for rule in &instructions.rules {
+            let ix_pair = pair_index(n, rule.pair.0, rule.pair.1);
+            let ix_left = pair_index(n, rule.pair.0, rule.to_insert);
+            let ix_right = pair_index(n, rule.to_insert, rule.pair.1);
+            rules[ix_pair].push(ix_left);
+            rules[ix_pair].push(ix_right);
+        }
+        let mut counts = DenseCounter::new(n * n);
+        for (&a, &b) in instructions.initial.iter().zip(&instructions.initial[1..]) {
+            counts.increment(pair_index(n, a, b));
+        }
+        let leading = pair_index(n, instructions.initial[0], instructions.initial[1]);
+        Self {
+            elements: n,
+            rules,
+            counts,
+            next: DenseCounter::new(n * n),
+            leading,
+        }
+    }
+
+    fn element_counts(&self) -> DenseCounter<usize, u64> {
+        let n = self.elements;
+        let mut element_counts = DenseCounter::new(n);
+        element_counts.increment(self.leading / n);
+        for (pair_ix, count) in self.counts.iter() {
+            element_counts.add(pair_ix % n, count);
+        }
+        element_counts
+    }
+}
+
+impl Simulation for Polymer {
+    type Output = ();
+
+    fn step(&mut self) {
+        self.next.clear();
+        self.leading = self.rules[self.leading][0];
+        for (ix, count) in self.counts.iter() {
+            for &ix2 in &self.rules[ix] {
+                self.next.add(ix2, count);
+            }
+        }
+        mem::swap(&mut self.counts, &mut self.next);
+    }
+
+    fn state_hash(&self) -> u64 {
+        hash_state(&(self.leading, &self.counts))
+    }
+}
+
+fn spread_after(instructions: &Instructions, rounds: usize) -> u64 {
+    let mut polymer = Polymer::new(instructions);
+    polymer.run_for(rounds);
+    spread(&polymer)
+}
+
+/// How the pair counts change in one round: entry `(to, from)` is how many `to` pairs each
+/// `from` pair turns into.
+fn transition(instructions: &Instructions) -> SquareMatrix<u64> {
+    let n = instructions.elements.len();
+    let mut matrix = SquareMatrix::zero(n * n);
+    for rule in &instructions.rules {
+        let from = pair_index(n, rule.pair.0, rule.pair.1);
+        matrix[(pair_index(n, rule.pair.0, rule.to_insert), from)] += 1;
+        matrix[(pair_index(n, rule.to_insert, rule.pair.1), from)] += 1;
+    }
+    matrix
+}
+
+fn pair_counts(instructions: &Instructions) -> Vec<u64> {
+    let n = instructions.elements.len();
+    let mut counts = vec![0; n * n];
+    for (&a, &b) in instructions.initial.iter().zip(&instructions.initial[1..]) {
+        counts[pair_index(n, a, b)] += 1;
+    }
+    counts
+}
+
+/// The difference between the most and least common elements after `rounds` rounds.
+///
+/// Raises the transition matrix to that power, in time logarithmic in `rounds`. Returns `None`
+/// if the counts overflow a `u128`.
+#[must_use]
+pub fn simulate(instructions: &Instructions, rounds: u64) -> Option<u128> {
+    let mut overflow = false;
+    let matrix = transition(instructions)
+        .map(|&entry| u128::from(entry))
+        .pow_by(rounds, |a, b| {
+            a.checked_mul(b).unwrap_or_else(|| {
+                overflow = true;
+                SquareMatrix::zero(a.size())
+            })
+        });
+    if overflow {
+        return None;
+    }
+    let pairs = pair_counts(instructions)
+        .into_iter()
+        .map(u128::from)
+        .collect::<Vec<_>>();
+    let pairs = matrix.checked_mul_vec(&pairs)?;
+    // Each element is counted as the second of its pairs, and the first element of the polymer,
+    // which never changes, once more.
+    let n = instructions.elements.len();
+    let mut counts = vec![0_u128; n];
+    counts[instructions.initial[0].index()] = 1;
+    for (ix, count) in pairs.into_iter().enumerate() {
+        counts[ix % n] = counts[ix % n].checked_add(count)?;
+    }
+    let counts = counts.into_iter().filter(|&count| count > 0);
+    Some(counts.clone().max()? - counts.min()?)
+}
+
+/// The count of each element after `rounds` rounds, modulo `modulus`, for when there are far too
+/// many to count. The counts are in the order of `Instructions::elements`.
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+#[must_use]
+pub fn element_counts_mod(instructions: &Instructions, rounds: u64, modulus: u64) -> Vec<u64> {
+    let pairs = transition(instructions)
+        .pow_mod(rounds, modulus)
+        .mul_vec_mod(&pair_counts(instructions), modulus);
+    let n = instructions.elements.len();
+    let mut counts = vec![0; n];
+    counts[instructions.initial[0].index()] = 1 % modulus;
+    for (ix, count) in pairs.into_iter().enumerate() {
+        let total = u128::from(counts[ix % n]) + u128::from(count);
+        counts[ix % n] = u64::try_from(total % u128::from(modulus)).unwrap_or_default();
+    }
+    counts
+}
+
+/// The difference between the most and least common elements.
+fn spread(polymer: &Polymer) -> u64 {
+    let element_counts = polymer.element_counts();
+    let (_, max) = element_counts.most_common(1)[0];
+    let (_, min) = element_counts.least_common(1)[0];
+    max - min
+}
+
+/// Both answers from one simulation, which continues from round 10 to round 40.
+#[must_use]
+pub fn solve_both(instructions: &Instructions) -> (u64, u64) {
+    let mut polymer = Polymer::new(instructions);
+    polymer.run_for(10);
+    let part_1 = spread(&polymer);
+    polymer.run_for(40 - 10);
+    (part_1, spread(&polymer))
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Parsed = Instructions;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        Ok(solve_both(parsed))
+    }
+
+    /// How many of each element there are after 10 steps and after 40, most common first.
+    fn explain(parsed: &Self::Parsed, explain: &mut Explain) {
+        let mut polymer = Polymer::new(parsed);
+        let mut steps = 0;
+        for rounds in [10, 40] {
+            polymer.run_for(rounds - steps);
+            steps = rounds;
+            let counts = polymer.element_counts();
+            let counts = counts
+                .most_common(counts.len())
+                .into_iter()
+                .filter(|&(_, count)| count > 0)
+                .map(|(element, count)| format!("{} {count}", parsed.elements[element]))
+                .collect::<Vec<_>>();
+            explain.step(format!("after {rounds} steps"), counts.join(", "));
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u64, u64), crate::Error> {
+    Day14::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geninput;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        NNCB\n\
+        \n\
+        CH -> B\n\
+        HH -> N\n\
+        CB -> H\n\
+        NH -> C\n\
+        HB -> C\n\
+        HC -> B\n\
+        HN -> C\n\
+        NN -> C\n\
+        BH -> H\n\
+        NC -> B\n\
+        NB -> B\n\
+        BN -> B\n\
+        BB -> N\n\
+        BC -> B\n\
+        CC -> N\n\
+        CN -> C\
+    ";
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("N\n\nNN -> N").is_err());
+        assert!(parse("NC\n\nNN -> N\nNC -> N\nCN -> N").is_err());
+        assert!(parse("NN\n\nNN -> C\nNC -> N\nCN -> N\nCC -> N\nNN -> N").is_err());
+        assert!(parse("NN\n\nNN -> C\nNC -> N\nCN -> N\nCC -> N").is_ok());
+    }
+
+    #[test]
+    fn test_parse() {
+        let result = parse(EXAMPLE).unwrap();
+        assert_eq!(result.elements(), ['N', 'C', 'B', 'H']);
+        let letters = |elements: &[Element]| {
+            elements
+                .iter()
+                .map(|&e| result.letter(e))
+                .collect::<String>()
+        };
+        assert_eq!(letters(&result.initial), "NNCB");
+        let rules = result
+            .rules
+            .iter()
+            .map(|rule| {
+                let pair = <[Element; 2]>::from(rule.pair);
+                format!("{} -> {}", letters(&pair), letters(&[rule.to_insert]))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            [
+                "CH -> B", "HH -> N", "CB -> H", "NH -> C", "HB -> C", "HC -> B", "HN -> C",
+                "NN -> C", "BH -> H", "NC -> B", "NB -> B", "BN -> B", "BB -> N", "BC -> B",
+                "CC -> N", "CN -> C",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(parse(EXAMPLE).unwrap().to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn test_other_elements() {
+        let instructions = parse(
+            "AZQ\n\n\
+            AA -> Z\nAZ -> Q\nAQ -> A\n\
+            ZA -> Z\nZZ -> A\nZQ -> Q\n\
+            QA -> A\nQZ -> Z\nQQ -> Q",
+        )
+        .unwrap();
+        assert_eq!(instructions.elements(), ['A', 'Z', 'Q']);
+        assert_eq!(part_1(&instructions), part_1_naive(&instructions));
+        assert!(matches!(
+            parse("AZ\n\nAA -> Z\nAZ -> Z\nZA -> Z"),
+            Err(ParseError::MissingRule('Z', 'Z'))
+        ));
+        assert!(parse("Az\n\nAA -> A").is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut explain = Explain::new();
+        Day14::explain(&parse(EXAMPLE).unwrap(), &mut explain);
+        let steps = explain.steps();
+        assert_eq!(steps[0].title, "after 10 steps");
+        assert_eq!(steps[0].detail, "B 1749, N 865, C 298, H 161");
+        assert!(steps[1].detail.starts_with("B 2192039569602, "));
+        assert!(steps[1].detail.ends_with(", H 3849876073"));
+    }
+
+    #[test]
+    fn test_part_1() {
+        let instructions = parse(EXAMPLE).unwrap();
+        let result = part_1(&instructions);
+        assert_eq!(result, 1_588);
+    }
+
+    #[test]
+    fn test_part_1_naive() {
+        let instructions = parse(EXAMPLE).unwrap();
+        let result = part_1_naive(&instructions);
+        assert_eq!(result, 1_588);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let instructions = parse(EXAMPLE).unwrap();
+        let result = part_2(&instructions);
+        assert_eq!(result, 2_188_189_693_529);
+    }
+
+    #[test]
+    fn test_expand() {
+        let instructions = parse(EXAMPLE).unwrap();
+        let polymer = |rounds| {
+            expand(&instructions, rounds)
+                .map(|element| instructions.letter(element))
+                .collect::<String>()
+        };
+        assert_eq!(polymer(0), "NNCB");
+        assert_eq!(polymer(1), "NCNBCHB");
+        assert_eq!(polymer(2), "NBCCNBBBCBHCB");
+        assert_eq!(
+            polymer(4),
+            "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB"
+        );
+        let mut counts = DenseCounter::<usize, u64>::new(instructions.elements().len());
+        for element in expand(&instructions, 10) {
+            counts.increment(element.index());
+        }
+        assert_eq!(counts.total(), 3_073);
+        let (_, max) = counts.most_common(1)[0];
+        let (_, min) = counts.least_common(1)[0];
+        assert_eq!(max - min, part_1(&instructions));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let instructions = parse(EXAMPLE).unwrap();
+        assert_eq!(simulate(&instructions, 0), Some(1));
+        assert_eq!(simulate(&instructions, 10), Some(1_588));
+        assert_eq!(simulate(&instructions, 40), Some(2_188_189_693_529));
+        // The polymer roughly doubles every round, so a `u128` lasts a little over 120 rounds.
+        assert!(simulate(&instructions, 100).is_some());
+        assert_eq!(simulate(&instructions, 1_000), None);
+        let modulus = 1_000_000_007;
+        let counts = element_counts_mod(&instructions, 40, modulus);
+        let exact = [
+            ('B', 2_192_039_569_602),
+            ('C', 6_597_635_301),
+            ('H', 3_849_876_073),
+            ('N', 1_096_047_802_353),
+        ];
+        for (letter, count) in exact {
+            let ix = instructions
+                .elements()
+                .iter()
+                .position(|&l| l == letter)
+                .unwrap();
+            assert_eq!(counts[ix], count % modulus, "{letter}");
+        }
+        let counts = element_counts_mod(&instructions, 1_000_000_000_000, modulus);
+        assert_eq!(counts.len(), 4);
+        assert!(counts.iter().all(|&count| count < modulus));
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(EXAMPLE).unwrap(), (1588, 2_188_189_693_529));
+        assert!(solve("NNCB\n\nCH => ").is_err());
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        for (seed, input) in geninput::samples(14) {
+            let instructions = parse(&input).unwrap();
+            assert_eq!(
+                part_1_naive(&instructions),
+                part_1(&instructions),
+                "seed {seed}"
+            );
+            let expected = (part_1(&instructions), part_2(&instructions));
+            assert_eq!(solve_both(&instructions), expected, "seed {seed}");
+            assert_eq!(
+                part_2_matrix(&instructions),
+                Some(u128::from(expected.1)),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::grid::{Grid, GridError};
+use crate::util::pathfinding;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidGrid(#[from] GridError),
+}
+
+#[aoc_generator(day15)]
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    Grid::from_bytes(input)?.try_map(|&ch| match ch {
+        b'1'..=b'9' => Ok(ch - b'0'),
+        _ => Err(ParseError::SyntaxError),
+    })
+}
+
+/// Lowest total risk from the top left to the bottom right of the cave, tiled `tiles` times in
+/// each direction. Every tile step away from the original adds one to the risk, wrapping from
+/// 9 back to 1.
+fn lowest_risk(grid: &Grid<u8>, tiles: usize) -> Option<usize> {
+    let width = grid.width() * tiles;
+    let height = grid.height() * tiles;
+    let risk = |r: usize, c: usize| {
+        let base = usize::from(grid[[r % grid.height(), c % grid.width()]]);
+        (base - 1 + r / grid.height() + c / grid.width()) % 9 + 1
+    };
+    let goal = height.checked_sub(1)? * width + width.checked_sub(1)?;
+    pathfinding::dijkstra_buckets(
+        width * height,
+        0,
+        |node| {
+            let (r, c) = (node / width, node % width);
+            [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ]
+            .into_iter()
+            .filter(|&(r, c)| r < height && c < width)
+            .map(move |(r, c)| (r * width + c, risk(r, c)))
+        },
+        |node| node == goal,
+    )
+}
+
+#[aoc(day15, part1)]
+fn part_1(grid: &Grid<u8>) -> Option<usize> {
+    lowest_risk(grid, 1)
+}
+
+#[aoc(day15, part2)]
+fn part_2(grid: &Grid<u8>) -> Option<usize> {
+    lowest_risk(grid, 5)
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Parsed = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input.as_bytes()).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day15::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &[u8] = b"\
+        1163751742\n\
+        1381373672\n\
+        2136511328\n\
+        3694931569\n\
+        7463417111\n\
+        1319128137\n\
+        1359912421\n\
+        3125421639\n\
+        1293138521\n\
+        2311944581\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (10, 10));
+        assert_eq!(grid[[0, 1]], 1);
+        assert_eq!(grid[[9, 9]], 1);
+        assert!(parse(b"12\n3").is_err());
+        assert!(parse(b"12\n3x").is_err());
+    }
+
+    #[test]
+    fn test_part_1() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1(&grid);
+        assert_eq!(result, Some(40));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_2(&grid);
+        assert_eq!(result, Some(315));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy(EXAMPLE)).unwrap(),
+            solve(std::str::from_utf8(EXAMPLE).unwrap()).unwrap()
+        );
+    }
+}
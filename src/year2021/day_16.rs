@@ -0,0 +1,574 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Invalid hex digit: {0:?}")]
+    InvalidHex(char),
+    #[error("Unexpected end of transmission")]
+    UnexpectedEnd,
+    #[error("Field of {0} bits does not fit")]
+    FieldTooWide(usize),
+    #[error("Unknown packet type: {0}")]
+    UnknownType(u8),
+    #[error("Operator without sub-packets")]
+    EmptyOperator,
+    #[error("Comparison with {0} sub-packets")]
+    WrongOperandCount(usize),
+    #[error("Packets nested more than {MAX_DEPTH} deep")]
+    TooDeep,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EvaluateError {
+    #[error("The value of a packet does not fit in 64 bits")]
+    Overflow,
+}
+
+/// The deepest sub-packets [`Packet::read`] accepts. Real transmissions nest a few dozen deep,
+/// and reading, evaluating and dropping a packet all recurse once per level.
+pub const MAX_DEPTH: usize = 256;
+
+/// Reads big-endian bit fields from a hex-encoded transmission.
+#[derive(Debug, Clone)]
+pub struct BitReader {
+    bytes: Vec<u8>,
+    position: usize,
+}
+
+impl BitReader {
+    /// # Errors
+    ///
+    /// Returns an error if `hex` contains anything but hex digits.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseError> {
+        let nibbles = hex
+            .trim()
+            .chars()
+            .map(|ch| {
+                ch.to_digit(16)
+                    .and_then(|d| u8::try_from(d).ok())
+                    .ok_or(ParseError::InvalidHex(ch))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytes = nibbles
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+            .collect();
+        Ok(Self { bytes, position: 0 })
+    }
+
+    /// The number of bits read so far.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Reads the next `count` bits, at most 64, as an unsigned number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fewer than `count` bits remain.
+    pub fn read(&mut self, count: usize) -> Result<u64, ParseError> {
+        debug_assert!(count <= 64);
+        if self.position + count > self.bytes.len() * 8 {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        let mut value = 0;
+        for _ in 0..count {
+            let bit = self.bytes[self.position / 8] >> (7 - self.position % 8) & 1;
+            value = value << 1 | u64::from(bit);
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    fn read_into<T: TryFrom<u64>>(&mut self, count: usize) -> Result<T, ParseError> {
+        T::try_from(self.read(count)?).map_err(|_| ParseError::FieldTooWide(count))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Comparison {
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Body {
+    Literal(u64),
+    Operator(Operator, Vec<Packet>),
+    Comparison(Comparison, Box<[Packet; 2]>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Packet {
+    version: u8,
+    body: Body,
+}
+
+impl Packet {
+    #[must_use]
+    pub const fn new(version: u8, body: Body) -> Self {
+        Self { version, body }
+    }
+
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        self.version
+    }
+
+    #[must_use]
+    pub const fn body(&self) -> &Body {
+        &self.body
+    }
+
+    /// Reads one packet, including all its sub-packets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transmission ends early or contains an invalid packet, a literal
+    /// too wide for a `u64`, or sub-packets nested deeper than [`MAX_DEPTH`].
+    pub fn read(reader: &mut BitReader) -> Result<Self, ParseError> {
+        Self::read_nested(reader, 0)
+    }
+
+    /// Reads a packet with `depth` packets around it.
+    fn read_nested(reader: &mut BitReader, depth: usize) -> Result<Self, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(ParseError::TooDeep);
+        }
+        let version = reader.read_into(3)?;
+        let type_id: u8 = reader.read_into(3)?;
+        if type_id == 4 {
+            let mut value = 0;
+            for groups in 1.. {
+                if groups > 16 {
+                    return Err(ParseError::FieldTooWide(4 * groups));
+                }
+                let group = reader.read(5)?;
+                value = value << 4 | group & 0xF;
+                if group & 0x10 == 0 {
+                    break;
+                }
+            }
+            return Ok(Self::new(version, Body::Literal(value)));
+        }
+        let mut children = Vec::new();
+        if reader.read(1)? == 0 {
+            let length: usize = reader.read_into(15)?;
+            let end = reader.position() + length;
+            while reader.position() < end {
+                children.push(Self::read_nested(reader, depth + 1)?);
+            }
+            if reader.position() != end {
+                return Err(ParseError::UnexpectedEnd);
+            }
+        } else {
+            let count: usize = reader.read_into(11)?;
+            for _ in 0..count {
+                children.push(Self::read_nested(reader, depth + 1)?);
+            }
+        }
+        let operator = match type_id {
+            0 => Operator::Sum,
+            1 => Operator::Product,
+            2 => Operator::Minimum,
+            3 => Operator::Maximum,
+            5..=7 => {
+                let comparison = match type_id {
+                    5 => Comparison::GreaterThan,
+                    6 => Comparison::LessThan,
+                    _ => Comparison::EqualTo,
+                };
+                let len = children.len();
+                let operands = <Box<[Self; 2]>>::try_from(children)
+                    .map_err(|_| ParseError::WrongOperandCount(len))?;
+                return Ok(Self::new(version, Body::Comparison(comparison, operands)));
+            }
+            _ => return Err(ParseError::UnknownType(type_id)),
+        };
+        if children.is_empty() {
+            return Err(ParseError::EmptyOperator);
+        }
+        Ok(Self::new(version, Body::Operator(operator, children)))
+    }
+
+    /// The sum of the version numbers of this packet and all its sub-packets.
+    #[must_use]
+    pub fn version_sum(&self) -> u64 {
+        u64::from(self.version)
+            + match &self.body {
+                Body::Literal(_) => 0,
+                Body::Operator(_, children) => children.iter().map(Self::version_sum).sum(),
+                Body::Comparison(_, operands) => operands.iter().map(Self::version_sum).sum(),
+            }
+    }
+
+    /// The value of the expression the packet encodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sum or product does not fit in a `u64`.
+    pub fn evaluate(&self) -> Result<u64, EvaluateError> {
+        Ok(match &self.body {
+            &Body::Literal(value) => value,
+            Body::Operator(operator, children) => {
+                let mut values = children.iter().map(Self::evaluate);
+                match operator {
+                    Operator::Sum => values.try_fold(0_u64, |sum, value| {
+                        sum.checked_add(value?).ok_or(EvaluateError::Overflow)
+                    })?,
+                    Operator::Product => values.try_fold(1_u64, |product, value| {
+                        product.checked_mul(value?).ok_or(EvaluateError::Overflow)
+                    })?,
+                    Operator::Minimum => values
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter()
+                        .min()
+                        .unwrap_or(0),
+                    Operator::Maximum => values
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter()
+                        .max()
+                        .unwrap_or(0),
+                }
+            }
+            Body::Comparison(comparison, operands) => {
+                let [left, right] = operands.as_ref();
+                let (left, right) = (left.evaluate()?, right.evaluate()?);
+                u64::from(match comparison {
+                    Comparison::GreaterThan => left > right,
+                    Comparison::LessThan => left < right,
+                    Comparison::EqualTo => left == right,
+                })
+            }
+        })
+    }
+}
+
+/// Appends the low `count` bits of `value` to `bits`, most significant first.
+fn push_bits(bits: &mut Vec<bool>, value: u64, count: usize) {
+    bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+impl Packet {
+    /// Appends the packet to `bits` the way [`Packet::read`] reads it. Sub-packets are counted,
+    /// unless there are too many to count in 11 bits.
+    fn write_bits(&self, bits: &mut Vec<bool>) {
+        push_bits(bits, self.version.into(), 3);
+        let (type_id, children): (u64, &[Self]) = match &self.body {
+            &Body::Literal(value) => {
+                push_bits(bits, 4, 3);
+                let groups = (u64::BITS - value.leading_zeros()).div_ceil(4).max(1);
+                for group in (0..groups).rev() {
+                    push_bits(bits, u64::from(group > 0), 1);
+                    push_bits(bits, value >> (4 * group), 4);
+                }
+                return;
+            }
+            Body::Operator(operator, children) => {
+                let type_id = match operator {
+                    Operator::Sum => 0,
+                    Operator::Product => 1,
+                    Operator::Minimum => 2,
+                    Operator::Maximum => 3,
+                };
+                (type_id, children)
+            }
+            Body::Comparison(comparison, operands) => {
+                let type_id = match comparison {
+                    Comparison::GreaterThan => 5,
+                    Comparison::LessThan => 6,
+                    Comparison::EqualTo => 7,
+                };
+                (type_id, operands.as_ref())
+            }
+        };
+        push_bits(bits, type_id, 3);
+        if let Ok(count) = u64::try_from(children.len())
+            && count < 1 << 11
+        {
+            push_bits(bits, 1, 1);
+            push_bits(bits, count, 11);
+            for child in children {
+                child.write_bits(bits);
+            }
+        } else {
+            let mut inner = Vec::new();
+            for child in children {
+                child.write_bits(&mut inner);
+            }
+            push_bits(bits, 0, 1);
+            push_bits(bits, inner.len() as u64, 15);
+            bits.append(&mut inner);
+        }
+    }
+}
+
+/// Writes the packet as a hex transmission, padded with zeros to a whole hex digit.
+impl Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bits = Vec::new();
+        self.write_bits(&mut bits);
+        for nibble in bits.chunks(4) {
+            let digit = nibble
+                .iter()
+                .fold(0_u8, |digit, &bit| digit << 1 | u8::from(bit))
+                << (4 - nibble.len());
+            write!(f, "{digit:X}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::read(&mut BitReader::from_hex(s)?)
+    }
+}
+
+#[aoc_generator(day16)]
+fn parse(input: &str) -> Result<Packet, ParseError> {
+    input.parse()
+}
+
+#[aoc(day16, part1)]
+fn part_1(packet: &Packet) -> u64 {
+    packet.version_sum()
+}
+
+#[aoc(day16, part2)]
+fn part_2(packet: &Packet) -> Result<u64, EvaluateError> {
+    packet.evaluate()
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Parsed = Packet;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).map_err(crate::Error::invalid_input)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or its value does not fit in a `u64`.
+pub fn solve(input: &str) -> Result<(u64, u64), crate::Error> {
+    Day16::solve(input)
+}
+
+#[cfg(all(kani, feature = "verification"))]
+mod verification {
+    use super::*;
+
+    const BITS: usize = 24;
+
+    fn any_reader() -> BitReader {
+        BitReader {
+            bytes: kani::any::<[u8; BITS / 8]>().to_vec(),
+            position: 0,
+        }
+    }
+
+    fn any_count() -> usize {
+        let count: usize = kani::any();
+        kani::assume(count <= BITS);
+        count
+    }
+
+    #[kani::proof]
+    #[kani::unwind(26)]
+    fn read_takes_count_bits() {
+        let mut reader = any_reader();
+        let count = any_count();
+        let value = reader.read(count).unwrap();
+        assert!(value < 1 << count);
+        assert_eq!(reader.position(), count);
+        assert!(reader.read(BITS - count + 1).is_err());
+        assert_eq!(reader.position(), count);
+    }
+
+    /// Reading the bits in two parts gives the same number as reading them at once.
+    #[kani::proof]
+    #[kani::unwind(26)]
+    fn reads_split_anywhere() {
+        let mut whole = any_reader();
+        let mut split = whole.clone();
+        let first = any_count();
+        let all = whole.read(BITS).unwrap();
+        let high = split.read(first).unwrap();
+        let low = split.read(BITS - first).unwrap();
+        assert_eq!(high << (BITS - first) | low, all);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+    use test_case::test_case;
+
+    #[test]
+    fn test_literal() {
+        let packet = parse("D2FE28").unwrap();
+        assert_eq!(packet, Packet::new(6, Body::Literal(2021)));
+        assert_eq!(packet.to_string(), "D2FE28");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            parse("EE00D40C823060").unwrap().to_string(),
+            "EE00D40C82306"
+        );
+        let wide = Packet::new(0, Body::Literal(u64::MAX));
+        assert_eq!(parse(&wide.to_string()), Ok(wide));
+        let crowded = Packet::new(
+            3,
+            Body::Operator(Operator::Sum, vec![Packet::new(1, Body::Literal(0)); 2048]),
+        );
+        assert_eq!(parse(&crowded.to_string()), Ok(crowded));
+    }
+
+    #[test]
+    fn test_operators() {
+        let packet = parse("38006F45291200").unwrap();
+        assert_eq!(
+            packet,
+            Packet::new(
+                1,
+                Body::Comparison(
+                    Comparison::LessThan,
+                    Box::new([
+                        Packet::new(6, Body::Literal(10)),
+                        Packet::new(2, Body::Literal(20)),
+                    ])
+                )
+            )
+        );
+        let packet = parse("EE00D40C823060").unwrap();
+        assert_eq!(
+            packet,
+            Packet::new(
+                7,
+                Body::Operator(
+                    Operator::Maximum,
+                    vec![
+                        Packet::new(2, Body::Literal(1)),
+                        Packet::new(4, Body::Literal(2)),
+                        Packet::new(1, Body::Literal(3)),
+                    ]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("D2FE2G"), Err(ParseError::InvalidHex('G')));
+        assert_eq!(parse("D2"), Err(ParseError::UnexpectedEnd));
+        // A literal that goes on past 16 groups of 4 bits.
+        let wide = format!("93{}C", "F".repeat(19));
+        assert_eq!(parse(&wide), Err(ParseError::FieldTooWide(68)));
+    }
+
+    /// `inner` wrapped in `depth` sums of one sub-packet.
+    fn nested(depth: usize, inner: Packet) -> Packet {
+        (0..depth).fold(inner, |packet, _| {
+            Packet::new(0, Body::Operator(Operator::Sum, vec![packet]))
+        })
+    }
+
+    #[test]
+    fn test_nesting() {
+        let deepest = nested(MAX_DEPTH, Packet::new(0, Body::Literal(7)));
+        assert_eq!(parse(&deepest.to_string()), Ok(deepest));
+        let too_deep = nested(MAX_DEPTH + 1, Packet::new(0, Body::Literal(7)));
+        assert_eq!(parse(&too_deep.to_string()), Err(ParseError::TooDeep));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let operation = |operator, values: &[u64]| {
+            let operands = values
+                .iter()
+                .map(|&value| Packet::new(0, Body::Literal(value)))
+                .collect();
+            Packet::new(0, Body::Operator(operator, operands))
+        };
+        let sum = operation(Operator::Sum, &[u64::MAX, 1]);
+        assert_eq!(sum.evaluate(), Err(EvaluateError::Overflow));
+        let product = operation(Operator::Product, &[1 << 32, 1 << 32]);
+        assert_eq!(product.evaluate(), Err(EvaluateError::Overflow));
+        let fits = operation(Operator::Product, &[1 << 31, 1 << 32]);
+        assert_eq!(fits.evaluate(), Ok(1 << 63));
+        assert_eq!(operation(Operator::Minimum, &[]).evaluate(), Ok(0));
+        assert!(matches!(
+            solve(&sum.to_string()),
+            Err(crate::Error::InvalidInput(_))
+        ));
+    }
+
+    #[test_case("8A004A801A8002F478" => 16)]
+    #[test_case("620080001611562C8802118E34" => 12)]
+    #[test_case("C0015000016115A2E0802F182340" => 23)]
+    #[test_case("A0016C880162017C3686B18A3D4780" => 31)]
+    fn test_part_1(input: &str) -> u64 {
+        let packet = parse(input).unwrap();
+        part_1(&packet)
+    }
+
+    #[test_case("C200B40A82" => 3)]
+    #[test_case("04005AC33890" => 54)]
+    #[test_case("880086C3E88112" => 7)]
+    #[test_case("CE00C43D881120" => 9)]
+    #[test_case("D8005AC2A8F0" => 1)]
+    #[test_case("F600BC2D8F" => 0)]
+    #[test_case("9C005AC2F8F0" => 0)]
+    #[test_case("9C0141080250320F1802104A08" => 1)]
+    fn test_part_2(input: &str) -> u64 {
+        let packet = parse(input).unwrap();
+        part_2(&packet).unwrap()
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy("9C0141080250320F1802104A08")).unwrap(),
+            solve("9C0141080250320F1802104A08").unwrap()
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Target area must be right of and below the launcher")]
+    UnsupportedTarget,
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetArea {
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+impl TargetArea {
+    /// Follows the probe in `i64`, where neither its position nor its speed can overflow for any
+    /// target that fits in an `i32`.
+    const fn hit(&self, vx: i32, vy: i32) -> bool {
+        let (mut vx, mut vy) = (vx as i64, vy as i64);
+        let (mut x, mut y) = (0, 0);
+        while x <= self.x_max as i64 && y >= self.y_min as i64 {
+            if x >= self.x_min as i64 && y <= self.y_max as i64 {
+                return true;
+            }
+            x += vx;
+            y += vy;
+            vx -= vx.signum();
+            vy -= 1;
+        }
+        false
+    }
+}
+
+fn parse_range(s: &str) -> Result<(i32, i32), ParseError> {
+    let (min, max) = s.split_once("..").ok_or(ParseError::SyntaxError)?;
+    Ok((min.parse()?, max.parse()?))
+}
+
+impl Display for TargetArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "target area: x={}..{}, y={}..{}",
+            self.x_min, self.x_max, self.y_min, self.y_max
+        )
+    }
+}
+
+impl FromStr for TargetArea {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .trim()
+            .strip_prefix("target area: x=")
+            .ok_or(ParseError::SyntaxError)?;
+        let (x, y) = s.split_once(", y=").ok_or(ParseError::SyntaxError)?;
+        let (x_min, x_max) = parse_range(x)?;
+        let (y_min, y_max) = parse_range(y)?;
+        if x_min <= 0 || x_min > x_max || y_max >= 0 || y_min > y_max {
+            return Err(ParseError::UnsupportedTarget);
+        }
+        Ok(Self {
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        })
+    }
+}
+
+#[aoc_generator(day17)]
+fn parse(input: &str) -> Result<TargetArea, ParseError> {
+    input.parse()
+}
+
+/// Launched upwards with `vy`, the probe passes `y = 0` again with speed `-vy - 1`, so the
+/// fastest launch that can still hit the target has `vy = -y_min - 1`.
+#[aoc(day17, part1)]
+const fn part_1(target: &TargetArea) -> i64 {
+    let vy = -(target.y_min as i64) - 1;
+    vy * (vy + 1) / 2
+}
+
+/// Horizontal speeds range from the smallest that reaches `x_min` before stalling up to
+/// `x_max`; vertical speeds range from `y_min` up to the maximum from part 1.
+#[aoc(day17, part2)]
+fn part_2(target: &TargetArea) -> usize {
+    let vx_min = (1..=target.x_max)
+        .find(|&vx| i64::from(vx) * (i64::from(vx) + 1) / 2 >= i64::from(target.x_min))
+        .unwrap_or(1);
+    let vy_max = -(target.y_min + 1);
+    (vx_min..=target.x_max)
+        .flat_map(|vx| (target.y_min..=vy_max).map(move |vy| (vx, vy)))
+        .filter(|&(vx, vy)| target.hit(vx, vy))
+        .count()
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Parsed = TargetArea;
+    type Part1 = i64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(i64, usize), crate::Error> {
+    Day17::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn test_parse() {
+        let target = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            target,
+            TargetArea {
+                x_min: 20,
+                x_max: 30,
+                y_min: -10,
+                y_max: -5,
+            }
+        );
+        assert!(parse("target area: x=20..30, y=5..10").is_err());
+        assert!(parse("target area: x=20..30").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(parse(EXAMPLE).unwrap().to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let target = parse(EXAMPLE).unwrap();
+        let result = part_1(&target);
+        assert_eq!(result, 45);
+        let deep = parse("target area: x=20..30, y=-100000..-5").unwrap();
+        assert_eq!(part_1(&deep), 4_999_950_000);
+        let deepest = TargetArea {
+            y_min: i32::MIN,
+            ..deep
+        };
+        assert_eq!(part_1(&deepest), 2_305_843_008_139_952_128);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let target = parse(EXAMPLE).unwrap();
+        let result = part_2(&target);
+        assert_eq!(result, 112);
+        let wide = parse("target area: x=1..2147483647, y=-5..-5").unwrap();
+        assert!(wide.hit((1 << 30) + 1, -5));
+        assert!(!wide.hit((1 << 30) + 1, 0));
+        let corner = TargetArea {
+            y_min: i32::MIN,
+            y_max: i32::MIN,
+            ..wide
+        };
+        assert!(corner.hit(i32::MAX, i32::MIN));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,383 @@
+use std::fmt::{self, Display};
+use std::ops::Add;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Unbalanced brackets")]
+    UnbalancedBrackets,
+    #[error("Pairs nested more than five deep")]
+    TooDeep,
+}
+
+/// A snailfish number stored as its regular numbers in order, each with the number of pairs
+/// enclosing it. For full binary trees this sequence determines the tree.
+///
+/// Explosions can pile more than 255 onto one regular number before it is split, so the values
+/// are wider than digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Number {
+    elements: Vec<(u16, u8)>,
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut elements = Vec::new();
+        let mut depth = 0_u8;
+        for ch in s.trim().bytes() {
+            match ch {
+                b'[' if depth == 5 => return Err(ParseError::TooDeep),
+                b'[' => depth += 1,
+                b']' => depth = depth.checked_sub(1).ok_or(ParseError::UnbalancedBrackets)?,
+                b',' => {}
+                b'0'..=b'9' => elements.push((u16::from(ch - b'0'), depth)),
+                _ => return Err(ParseError::SyntaxError),
+            }
+        }
+        if depth != 0 {
+            return Err(ParseError::UnbalancedBrackets);
+        }
+        if !is_full_tree(&elements) {
+            return Err(ParseError::SyntaxError);
+        }
+        Ok(Self { elements })
+    }
+}
+
+/// Writes the pair or regular number at `depth` that starts with `elements[*next]`, moving
+/// `next` past it.
+fn write_element(
+    f: &mut fmt::Formatter<'_>,
+    elements: &[(u16, u8)],
+    next: &mut usize,
+    depth: u8,
+) -> fmt::Result {
+    let Some(&(value, element_depth)) = elements.get(*next) else {
+        return Ok(());
+    };
+    if element_depth <= depth {
+        *next += 1;
+        return write!(f, "{value}");
+    }
+    f.write_str("[")?;
+    write_element(f, elements, next, depth + 1)?;
+    f.write_str(",")?;
+    write_element(f, elements, next, depth + 1)?;
+    f.write_str("]")
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_element(f, &self.elements, &mut 0, 0)
+    }
+}
+
+/// Whether the depths describe a full binary tree: merging neighbours of equal depth into their
+/// parent must end with a single root.
+fn is_full_tree(elements: &[(u16, u8)]) -> bool {
+    let mut stack = Vec::new();
+    for &(_, depth) in elements {
+        let mut depth = depth;
+        while stack.last() == Some(&depth) && depth > 0 {
+            stack.pop();
+            depth -= 1;
+        }
+        stack.push(depth);
+    }
+    stack == [0]
+}
+
+impl Number {
+    /// Explodes the leftmost pair of regular numbers nested inside four pairs. Returns `false` if
+    /// there is none.
+    ///
+    /// Sums of reduced numbers nest no pair deeper than that, but a pair nested five deep in the
+    /// input ends up inside a pair itself. The first two neighbours of equal depth are always a
+    /// pair, so that is the one to explode.
+    fn explode(&mut self) -> bool {
+        let Some(i) = self
+            .elements
+            .windows(2)
+            .position(|pair| pair[0].1 > 4 && pair[0].1 == pair[1].1)
+        else {
+            return false;
+        };
+        let (left, depth) = self.elements[i];
+        let (right, _) = self.elements.remove(i + 1);
+        if i > 0 {
+            self.elements[i - 1].0 += left;
+        }
+        if let Some((value, _)) = self.elements.get_mut(i + 1) {
+            *value += right;
+        }
+        self.elements[i] = (0, depth - 1);
+        true
+    }
+
+    /// Splits the leftmost regular number of 10 or more. Returns `false` if there is none.
+    fn split(&mut self) -> bool {
+        let Some(i) = self.elements.iter().position(|&(value, _)| value >= 10) else {
+            return false;
+        };
+        let (value, depth) = self.elements[i];
+        self.elements[i] = (value / 2, depth + 1);
+        self.elements.insert(i + 1, (value - value / 2, depth + 1));
+        true
+    }
+
+    fn reduce(&mut self) {
+        loop {
+            while self.explode() {}
+            if !self.split() {
+                break;
+            }
+        }
+    }
+
+    fn magnitude(&self) -> u32 {
+        let mut stack: Vec<(u32, u8)> = Vec::new();
+        for &(value, depth) in &self.elements {
+            let mut top = (u32::from(value), depth);
+            while let Some(&(left, left_depth)) = stack.last()
+                && left_depth == top.1
+            {
+                stack.pop();
+                top = (3 * left + 2 * top.0, top.1 - 1);
+            }
+            stack.push(top);
+        }
+        stack.first().map_or(0, |&(value, _)| value)
+    }
+}
+
+impl Add for &Number {
+    type Output = Number;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut sum = Number {
+            elements: self
+                .elements
+                .iter()
+                .chain(&rhs.elements)
+                .map(|&(value, depth)| (value, depth + 1))
+                .collect(),
+        };
+        sum.reduce();
+        sum
+    }
+}
+
+#[aoc_generator(day18)]
+fn parse(input: &str) -> Result<Vec<Number>, ParseError> {
+    input.lines().map(str::parse).collect()
+}
+
+fn sum(numbers: &[Number]) -> Option<Number> {
+    let (first, rest) = numbers.split_first()?;
+    Some(rest.iter().fold(first.clone(), |acc, n| &acc + n))
+}
+
+#[aoc(day18, part1)]
+fn part_1(numbers: &[Number]) -> Option<u32> {
+    sum(numbers).map(|n| n.magnitude())
+}
+
+#[aoc(day18, part2)]
+fn part_2(numbers: &[Number]) -> Option<u32> {
+    numbers
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| {
+            numbers
+                .iter()
+                .enumerate()
+                .filter(move |&(j, _)| i != j)
+                .map(move |(_, b)| (a + b).magnitude())
+        })
+        .max()
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Parsed = Vec<Number>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(u32, u32), crate::Error> {
+    Day18::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::join_lines;
+    use crate::util::parse::untidy;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "\
+        [[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]\n\
+        [[[5,[2,8]],4],[5,[[9,9],0]]]\n\
+        [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]\n\
+        [[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]\n\
+        [[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]\n\
+        [[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]\n\
+        [[[[5,4],[7,7]],8],[[8,3],8]]\n\
+        [[9,3],[[9,9],[6,[4,9]]]]\n\
+        [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]\n\
+        [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]\
+    ";
+
+    fn number(s: &str) -> Number {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(number("[[1,2],3]").elements, [(1, 2), (2, 2), (3, 1)]);
+        assert!("[1,2".parse::<Number>().is_err());
+        assert!("[1,2]]".parse::<Number>().is_err());
+        assert!("[1,x]".parse::<Number>().is_err());
+        assert!("[[[[[[1,2],3],4],5],6],7]".parse::<Number>().is_err());
+        assert!("[1]".parse::<Number>().is_err());
+        assert!("[1,2,3]".parse::<Number>().is_err());
+        assert!("[]".parse::<Number>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let numbers = parse(EXAMPLE).unwrap();
+        assert_eq!(join_lines(&numbers), EXAMPLE);
+        let sum = numbers[1..]
+            .iter()
+            .fold(numbers[0].clone(), |sum, number| &sum + number);
+        assert_eq!(
+            sum.to_string(),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+        );
+    }
+
+    #[test_case("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]")]
+    #[test_case("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]")]
+    #[test_case("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]")]
+    #[test_case(
+        "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+        "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
+    )]
+    #[test_case("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]", "[[3,[2,[8,0]]],[9,[5,[7,0]]]]")]
+    fn test_explode(input: &str, expected: &str) {
+        let mut n = number(input);
+        assert!(n.explode());
+        assert_eq!(n, number(expected));
+    }
+
+    #[test]
+    fn test_add() {
+        let sum = &number("[[[[4,3],4],4],[7,[[8,4],9]]]") + &number("[1,1]");
+        assert_eq!(sum, number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"));
+    }
+
+    #[test_case("[1,1]\n[2,2]\n[3,3]\n[4,4]", "[[[[1,1],[2,2]],[3,3]],[4,4]]")]
+    #[test_case("[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]", "[[[[3,0],[5,3]],[4,4]],[5,5]]")]
+    #[test_case(
+        "[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]\n[6,6]",
+        "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+    )]
+    fn test_sum(input: &str, expected: &str) {
+        let numbers = parse(input).unwrap();
+        assert_eq!(sum(&numbers), Some(number(expected)));
+    }
+
+    #[test]
+    fn test_sum_larger() {
+        let numbers = parse(
+            "\
+            [[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]\n\
+            [7,[[[3,7],[4,3]],[[6,3],[8,8]]]]\n\
+            [[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]\n\
+            [[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]\n\
+            [7,[5,[[3,8],[1,4]]]]\n\
+            [[2,[2,2]],[8,[8,1]]]\n\
+            [2,9]\n\
+            [1,[[[9,3],9],[[9,0],[0,7]]]]\n\
+            [[[5,[7,4]],7],1]\n\
+            [[[[4,2],2],6],[8,7]]\
+            ",
+        )
+        .unwrap();
+        let total = sum(&numbers).unwrap();
+        assert_eq!(
+            total,
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+        );
+    }
+
+    #[test_case("[[1,2],[[3,4],5]]" => 143)]
+    #[test_case("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]" => 1_384)]
+    #[test_case("[[[[1,1],[2,2]],[3,3]],[4,4]]" => 445)]
+    #[test_case("[[[[3,0],[5,3]],[4,4]],[5,5]]" => 791)]
+    #[test_case("[[[[5,0],[7,4]],[5,5]],[6,6]]" => 1_137)]
+    #[test_case("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]" => 3_488)]
+    fn test_magnitude(input: &str) -> u32 {
+        number(input).magnitude()
+    }
+
+    #[test]
+    fn test_part_1() {
+        let numbers = parse(EXAMPLE).unwrap();
+        let result = part_1(&numbers);
+        assert_eq!(result, Some(4_140));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let numbers = parse(EXAMPLE).unwrap();
+        let result = part_2(&numbers);
+        assert_eq!(result, Some(3_993));
+    }
+
+    #[test]
+    fn test_unreduced_inputs() {
+        let numbers = parse("[1,[2,[3,[4,[5,6]]]]]\n[[[[[7,8],1],1],1],1]").unwrap();
+        assert_eq!(sum(&numbers), Some(number("[[1,[8,[9,5]]],[[[7,0],9],1]]")));
+        assert_eq!(part_2(&numbers), Some(1_087));
+        let numbers = parse("[[[[1,[2,3]],4],5],6]\n[1,1]").unwrap();
+        assert_eq!(sum(&numbers), Some(number("[[[[0,7],5],6],[1,1]]")));
+        assert_eq!(part_2(&numbers), Some(514));
+        let nines = (0..5).fold("9".to_string(), |n, _| format!("[{n},{n}]"));
+        let numbers = parse(&format!("{nines}\n{nines}")).unwrap();
+        assert_eq!(part_1(&numbers), Some(4_072));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
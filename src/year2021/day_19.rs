@@ -0,0 +1,340 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use thiserror::Error;
+use tracing::debug;
+
+use crate::solver::Solver;
+use crate::util::symmetry::CubeRotation;
+
+/// Beacons two scanners must share to be considered overlapping.
+const MIN_OVERLAP: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+type Point = [i32; 3];
+
+const fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+const fn add(a: Point, b: Point) -> Point {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn squared_distance(a: Point, b: Point) -> i64 {
+    sub(a, b).iter().map(|&d| i64::from(d) * i64::from(d)).sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scanner {
+    beacons: Vec<Point>,
+    /// Squared distances between every pair of beacons, which do not depend on orientation.
+    fingerprint: Vec<i64>,
+}
+
+impl Scanner {
+    fn new(beacons: Vec<Point>) -> Self {
+        let mut fingerprint = beacons
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| {
+                beacons[i + 1..]
+                    .iter()
+                    .map(move |&b| squared_distance(a, b))
+            })
+            .collect::<Vec<_>>();
+        fingerprint.sort_unstable();
+        Self {
+            beacons,
+            fingerprint,
+        }
+    }
+
+    /// The number of pairwise distances the two scanners have in common, counting duplicates.
+    fn shared_distances(&self, other: &Self) -> usize {
+        let (mut i, mut j, mut count) = (0, 0, 0);
+        while let (Some(a), Some(b)) = (self.fingerprint.get(i), other.fingerprint.get(j)) {
+            match a.cmp(b) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    count += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Finds the rotation and offset that map at least [`MIN_OVERLAP`] of `other`'s beacons
+    /// onto this scanner's beacons.
+    fn align(&self, other: &Self) -> Option<(CubeRotation, Point)> {
+        if self.shared_distances(other) < MIN_OVERLAP * (MIN_OVERLAP - 1) / 2 {
+            return None;
+        }
+        let mut offsets = HashMap::new();
+        CubeRotation::all().into_iter().find_map(|rotation| {
+            offsets.clear();
+            for &b in &other.beacons {
+                let rotated = rotation.apply(b);
+                for &a in &self.beacons {
+                    let count = offsets.entry(sub(a, rotated)).or_insert(0);
+                    *count += 1;
+                    if *count >= MIN_OVERLAP {
+                        return Some((rotation, sub(a, rotated)));
+                    }
+                }
+            }
+            None
+        })
+    }
+
+    fn transformed(&self, rotation: CubeRotation, offset: Point) -> Self {
+        Self::new(
+            self.beacons
+                .iter()
+                .map(|&b| add(rotation.apply(b), offset))
+                .collect(),
+        )
+    }
+}
+
+/// Writes the beacons one per line, without the header, which holds the scanner's number.
+impl Display for Scanner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, [x, y, z]) in self.beacons.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{x},{y},{z}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The scanners as the puzzle input lists them, numbered from 0.
+#[must_use]
+pub fn join_scanners(scanners: &[Scanner]) -> String {
+    scanners
+        .iter()
+        .enumerate()
+        .map(|(number, scanner)| format!("--- scanner {number} ---\n{scanner}"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+impl FromStr for Scanner {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let header = lines.next().ok_or(ParseError::SyntaxError)?;
+        if !header.starts_with("--- scanner ") {
+            return Err(ParseError::SyntaxError);
+        }
+        let beacons = lines
+            .map(|line| {
+                let mut coords = line.split(',').map(str::parse);
+                let point = [
+                    coords.next().ok_or(ParseError::SyntaxError)??,
+                    coords.next().ok_or(ParseError::SyntaxError)??,
+                    coords.next().ok_or(ParseError::SyntaxError)??,
+                ];
+                if coords.next().is_some() {
+                    return Err(ParseError::SyntaxError);
+                }
+                Ok(point)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(beacons))
+    }
+}
+
+#[aoc_generator(day19)]
+fn parse(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    input
+        .split("\n\n")
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+}
+
+/// Scanner positions and beacons in the frame of the first scanner, or `None` if some scanner
+/// cannot be connected to it.
+fn locate(scanners: &[Scanner]) -> Option<(Vec<Point>, HashSet<Point>)> {
+    let (first, rest) = scanners.split_first()?;
+    let mut placed = vec![first.clone()];
+    let mut positions = vec![[0; 3]];
+    let mut pending = rest.iter().collect::<Vec<_>>();
+    let mut next = 0;
+    while !pending.is_empty() {
+        let anchor = placed.get(next)?.clone();
+        next += 1;
+        pending.retain(|&scanner| {
+            let Some((rotation, offset)) = anchor.align(scanner) else {
+                return true;
+            };
+            placed.push(scanner.transformed(rotation, offset));
+            positions.push(offset);
+            false
+        });
+        debug!(
+            anchor = next - 1,
+            placed = placed.len(),
+            pending = pending.len(),
+            "Aligned"
+        );
+    }
+    let beacons = placed
+        .iter()
+        .flat_map(|scanner| scanner.beacons.iter().copied())
+        .collect();
+    Some((positions, beacons))
+}
+
+#[aoc(day19, part1)]
+fn part_1(scanners: &[Scanner]) -> Option<usize> {
+    let (_, beacons) = locate(scanners)?;
+    Some(beacons.len())
+}
+
+#[aoc(day19, part2)]
+fn part_2(scanners: &[Scanner]) -> Option<u32> {
+    let (positions, _) = locate(scanners)?;
+    positions
+        .iter()
+        .flat_map(|&a| {
+            positions
+                .iter()
+                .map(move |&b| sub(a, b).iter().map(|d| d.unsigned_abs()).sum())
+        })
+        .max()
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Parsed = Vec<Scanner>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(usize, u32), crate::Error> {
+    Day19::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    /// Beacons scattered with a simple linear congruential generator.
+    fn beacons(count: usize) -> Vec<Point> {
+        let mut state = 12_345_u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            i32::try_from(state >> 16).unwrap() % 2_000 - 1_000
+        };
+        (0..count).map(|_| [next(), next(), next()]).collect()
+    }
+
+    /// Renders what a scanner at `position`, turned by `rotation`, reports for `beacons`.
+    fn scanner_text(
+        index: usize,
+        position: Point,
+        rotation: CubeRotation,
+        beacons: &[Point],
+    ) -> String {
+        let inverse = rotation.inverse();
+        let lines = beacons.iter().map(|&b| {
+            let [x, y, z] = inverse.apply(sub(b, position));
+            format!("\n{x},{y},{z}")
+        });
+        format!("--- scanner {index} ---") + &lines.collect::<String>()
+    }
+
+    fn example() -> String {
+        let all = beacons(36);
+        let rotations = CubeRotation::all();
+        [
+            scanner_text(0, [0; 3], CubeRotation::IDENTITY, &all[0..20]),
+            scanner_text(1, [68, -1_246, -43], rotations[5], &all[8..28]),
+            scanner_text(2, [1_105, -1_205, 1_229], rotations[17], &all[16..36]),
+        ]
+        .join("\n\n")
+    }
+
+    #[test]
+    fn test_parse() {
+        let scanners =
+            parse("--- scanner 0 ---\n1,2,3\n-4,5,-6\n\n--- scanner 1 ---\n7,8,9").unwrap();
+        assert_eq!(scanners.len(), 2);
+        assert_eq!(scanners[0].beacons, [[1, 2, 3], [-4, 5, -6]]);
+        assert_eq!(scanners[0].fingerprint, [25 + 9 + 81]);
+        assert!(parse("--- scanner 0 ---\n1,2").is_err());
+        assert!(parse("1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let input = example();
+        assert_eq!(join_scanners(&parse(&input).unwrap()), input);
+    }
+
+    #[test]
+    fn test_align() {
+        let scanners = parse(&example()).unwrap();
+        let (rotation, offset) = scanners[0].align(&scanners[1]).unwrap();
+        assert_eq!(offset, [68, -1_246, -43]);
+        assert_eq!(rotation, CubeRotation::all()[5]);
+        assert!(scanners[0].align(&scanners[2]).is_none());
+    }
+
+    #[test]
+    fn test_part_1() {
+        let scanners = parse(&example()).unwrap();
+        let result = part_1(&scanners);
+        assert_eq!(result, Some(36));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let scanners = parse(&example()).unwrap();
+        let result = part_2(&scanners);
+        assert_eq!(result, Some(1_105 + 1_205 + 1_229));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        let input = example();
+        assert_eq!(solve(&untidy(&input)).unwrap(), solve(&input).unwrap());
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt::{self, Display, Write};
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+use crate::util::grid::{Border, Grid, GridError};
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Enhancement algorithm must have 512 entries, found {0}")]
+    InvalidAlgorithm(usize),
+    #[error(transparent)]
+    InvalidGrid(#[from] GridError),
+}
+
+/// A finite image on an infinite background where every pixel has the same value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Image {
+    grid: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    /// Applies the enhancement once. The image grows by one pixel on each side, and the
+    /// background becomes whatever the algorithm makes of a neighbourhood of background.
+    fn enhance(&self, algorithm: &[bool; 512]) -> Self {
+        let border = Border::Constant(self.background);
+        let grid = self.grid.convolve3x3(1, &border, |block| {
+            let index = block
+                .iter()
+                .flatten()
+                .fold(0, |index, &lit| index << 1 | usize::from(lit));
+            algorithm[index]
+        });
+        let background = algorithm[if self.background { 511 } else { 0 }];
+        Self { grid, background }
+    }
+
+    /// The number of lit pixels, or `None` if infinitely many are lit.
+    fn lit(&self) -> Option<usize> {
+        (!self.background).then(|| self.grid.as_slice().iter().filter(|&&lit| lit).count())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Puzzle {
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::boxed"))]
+    algorithm: Box<[bool; 512]>,
+    image: Image,
+}
+
+const fn parse_pixel(ch: u8) -> Result<bool, ParseError> {
+    match ch {
+        b'#' => Ok(true),
+        b'.' => Ok(false),
+        _ => Err(ParseError::SyntaxError),
+    }
+}
+
+const fn pixel(lit: bool) -> char {
+    if lit { '#' } else { '.' }
+}
+
+/// Writes the algorithm and the image as the puzzle input has them. The background is not
+/// written, as inputs always start with a dark one.
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &lit in self.algorithm.iter() {
+            f.write_char(pixel(lit))?;
+        }
+        f.write_char('\n')?;
+        for row in self.image.grid.rows() {
+            f.write_char('\n')?;
+            for &lit in row {
+                f.write_char(pixel(lit))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[aoc_generator(day20)]
+fn parse(input: &[u8]) -> Result<Puzzle, ParseError> {
+    let mut lines = input.split(|&ch| ch == b'\n');
+    let algorithm = lines
+        .next()
+        .ok_or(ParseError::SyntaxError)?
+        .iter()
+        .map(|&ch| parse_pixel(ch))
+        .collect::<Result<Vec<_>, _>>()?;
+    let algorithm = <Box<[bool; 512]>>::try_from(algorithm)
+        .map_err(|v| ParseError::InvalidAlgorithm(v.len()))?;
+    if lines.next() != Some(b"") {
+        return Err(ParseError::SyntaxError);
+    }
+    let image = lines.collect::<Vec<_>>().join(&b'\n');
+    let grid = Grid::from_bytes(&image)?.try_map(|&ch| parse_pixel(ch))?;
+    Ok(Puzzle {
+        algorithm,
+        image: Image {
+            grid,
+            background: false,
+        },
+    })
+}
+
+fn enhance_times(puzzle: &Puzzle, times: usize) -> Option<usize> {
+    (0..times)
+        .fold(puzzle.image.clone(), |image, _| {
+            image.enhance(&puzzle.algorithm)
+        })
+        .lit()
+}
+
+#[aoc(day20, part1)]
+fn part_1(puzzle: &Puzzle) -> Option<usize> {
+    enhance_times(puzzle, 2)
+}
+
+#[aoc(day20, part2)]
+fn part_2(puzzle: &Puzzle) -> Option<usize> {
+    enhance_times(puzzle, 50)
+}
+
+/// Both answers from one run of enhancements, which continues from the 2nd to the 50th.
+fn solve_both(puzzle: &Puzzle) -> (Option<usize>, Option<usize>) {
+    let enhance =
+        |image: Image, times| (0..times).fold(image, |image, _| image.enhance(&puzzle.algorithm));
+    let image = enhance(puzzle.image.clone(), 2);
+    let part_1 = image.lit();
+    (part_1, enhance(image, 50 - 2).lit())
+}
+
+/// Saves the image after enhancing it `times` times as a PNG image. The infinite background
+/// around it is left out.
+///
+/// # Errors
+///
+/// Returns an error if the file can not be written.
+#[cfg(feature = "image")]
+pub fn export_png(puzzle: &Puzzle, times: usize, path: &std::path::Path) -> image::ImageResult<()> {
+    let image = (0..times).fold(puzzle.image.clone(), |image, _| {
+        image.enhance(&puzzle.algorithm)
+    });
+    crate::util::export::save_bool_png(path, &image.grid)
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Parsed = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input.as_bytes()).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn solve_both(parsed: &Self::Parsed) -> Result<(Self::Part1, Self::Part2), crate::Error> {
+        match solve_both(parsed) {
+            (Some(part_1), Some(part_2)) => Ok((part_1, part_2)),
+            _ => Err(crate::Error::NoSolution),
+        }
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(usize, usize), crate::Error> {
+    Day20::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        ..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..##\
+        #..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###\
+        .######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#.\
+        .#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#.....\
+        .#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#..\
+        ...####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.....\
+        ..##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#\n\
+        \n\
+        #..#.\n\
+        #....\n\
+        ##..#\n\
+        ..#..\n\
+        ..###\
+    ";
+
+    /// An algorithm that applies `rule` to each neighbourhood, given as a 3×3 bit pattern.
+    fn algorithm(rule: impl Fn(usize) -> bool) -> String {
+        (0..512).map(|i| if rule(i) { '#' } else { '.' }).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let puzzle = parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(
+            (puzzle.image.grid.width(), puzzle.image.grid.height()),
+            (5, 5)
+        );
+        assert_eq!(puzzle.image.lit(), Some(10));
+        assert!(puzzle.algorithm[34]);
+        assert!(parse(b"#.#\n\n#.").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let puzzle = parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(puzzle.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn test_growing() {
+        let input = algorithm(|i| i != 0) + "\n\n#";
+        let puzzle = parse(input.as_bytes()).unwrap();
+        assert_eq!(enhance_times(&puzzle, 1), Some(9));
+        assert_eq!(enhance_times(&puzzle, 2), Some(25));
+    }
+
+    #[test]
+    fn test_flipping_background() {
+        let input = algorithm(|i| i & 0b10000 == 0) + "\n\n#.\n.#";
+        let puzzle = parse(input.as_bytes()).unwrap();
+        assert_eq!(enhance_times(&puzzle, 1), None);
+        assert_eq!(enhance_times(&puzzle, 2), Some(2));
+        assert_eq!(enhance_times(&puzzle, 50), Some(2));
+    }
+
+    #[test]
+    fn test_part_1() {
+        let puzzle = parse(EXAMPLE.as_bytes()).unwrap();
+        let result = part_1(&puzzle);
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let puzzle = parse(EXAMPLE.as_bytes()).unwrap();
+        let result = part_2(&puzzle);
+        assert_eq!(result, Some(3_351));
+    }
+
+    #[test]
+    fn test_solve_both() {
+        let puzzle = parse(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(solve_both(&puzzle), (Some(35), Some(3_351)));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
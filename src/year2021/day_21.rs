@@ -0,0 +1,241 @@
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Starting position must be between 1 and 10, found {0}")]
+    InvalidPosition(u8),
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+}
+
+/// Sums of three rolls of a three-sided Dirac die, and the number of universes producing each.
+const DIRAC_ROLLS: [(u8, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// Positions and scores of both players, with the player about to move first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameState {
+    positions: [u8; 2],
+    scores: [u32; 2],
+}
+
+impl GameState {
+    /// A new game with both scores at zero. Positions are on the board `1..=10`.
+    #[must_use]
+    pub const fn new(positions: [u8; 2]) -> Self {
+        Self {
+            positions,
+            scores: [0, 0],
+        }
+    }
+
+    #[must_use]
+    pub const fn positions(&self) -> [u8; 2] {
+        self.positions
+    }
+
+    #[must_use]
+    pub const fn scores(&self) -> [u32; 2] {
+        self.scores
+    }
+
+    /// Moves the current player `steps` forward and scores their new position, then swaps the
+    /// players so the other one is first.
+    #[must_use]
+    pub fn advance(self, steps: u8) -> Self {
+        let position = (self.positions[0] - 1 + steps % 10) % 10 + 1;
+        Self {
+            positions: [self.positions[1], position],
+            scores: [self.scores[1], self.scores[0] + u32::from(position)],
+        }
+    }
+}
+
+/// Writes the starting positions as the puzzle input has them. The scores are not part of it.
+impl Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, second] = self.positions;
+        write!(
+            f,
+            "Player 1 starting position: {first}\nPlayer 2 starting position: {second}"
+        )
+    }
+}
+
+#[aoc_generator(day21)]
+fn parse(input: &str) -> Result<GameState, ParseError> {
+    let mut positions = [0; 2];
+    let mut lines = input.lines();
+    for (player, position) in (1..).zip(&mut positions) {
+        let line = lines.next().ok_or(ParseError::SyntaxError)?;
+        let start = line
+            .strip_prefix(&format!("Player {player} starting position: "))
+            .ok_or(ParseError::SyntaxError)?
+            .parse()?;
+        if !(1..=10).contains(&start) {
+            return Err(ParseError::InvalidPosition(start));
+        }
+        *position = start;
+    }
+    if lines.next().is_some() {
+        return Err(ParseError::SyntaxError);
+    }
+    Ok(GameState::new(positions))
+}
+
+/// Plays with the deterministic 100-sided die until someone reaches 1000, and returns the
+/// losing score times the number of rolls.
+#[must_use]
+pub fn play_deterministic(start: GameState) -> u32 {
+    let mut state = start;
+    let mut die = 0_u8;
+    let mut roll = || {
+        die = die % 100 + 1;
+        die % 10
+    };
+    let mut rolls = 0;
+    loop {
+        let steps = roll() + roll() + roll();
+        rolls += 3;
+        state = state.advance(steps);
+        if state.scores[1] >= 1000 {
+            return state.scores[0] * rolls;
+        }
+    }
+}
+
+/// The number of universes in which each player wins when playing to 21 with the Dirac die,
+/// in the order of `start`.
+#[must_use]
+pub fn count_quantum_wins(start: GameState) -> [u64; 2] {
+    let mut memo = vec![None; 10 * 10 * 21 * 21];
+    quantum_wins(start, &mut memo)
+}
+
+fn quantum_wins(state: GameState, memo: &mut [Option<[u64; 2]>]) -> [u64; 2] {
+    let key = [
+        usize::from(state.positions[0] - 1),
+        usize::from(state.positions[1] - 1),
+        state.scores[0] as usize,
+        state.scores[1] as usize,
+    ]
+    .iter()
+    .zip([10, 10, 21, 21])
+    .fold(0, |acc, (&digit, base)| acc * base + digit);
+    if let Some(wins) = memo[key] {
+        return wins;
+    }
+    let mut wins = [0; 2];
+    for (steps, universes) in DIRAC_ROLLS {
+        let next = state.advance(steps);
+        if next.scores[1] >= 21 {
+            wins[0] += universes;
+        } else {
+            let [other, this] = quantum_wins(next, memo);
+            wins[0] += universes * this;
+            wins[1] += universes * other;
+        }
+    }
+    memo[key] = Some(wins);
+    wins
+}
+
+#[aoc(day21, part1)]
+fn part_1(start: &GameState) -> u32 {
+    play_deterministic(*start)
+}
+
+#[aoc(day21, part2)]
+fn part_2(start: &GameState) -> u64 {
+    count_quantum_wins(*start).into_iter().max().unwrap_or(0)
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Parsed = GameState;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u32, u64), crate::Error> {
+    Day21::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        Player 1 starting position: 4\n\
+        Player 2 starting position: 8\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let state = parse(EXAMPLE).unwrap();
+        assert_eq!(state, GameState::new([4, 8]));
+        assert!(parse("Player 1 starting position: 4\nPlayer 2 starting position: 11").is_err());
+        assert!(parse("Player 1 starting position: 4").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(parse(EXAMPLE).unwrap().to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn test_advance() {
+        let state = GameState::new([4, 8]).advance(1 + 2 + 3);
+        assert_eq!(state.positions(), [8, 10]);
+        assert_eq!(state.scores(), [0, 10]);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let state = parse(EXAMPLE).unwrap();
+        let result = part_1(&state);
+        assert_eq!(result, 739_785);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let state = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            count_quantum_wins(state),
+            [444_356_092_776_315, 341_960_390_180_808]
+        );
+        let result = part_2(&state);
+        assert_eq!(result, 444_356_092_776_315);
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
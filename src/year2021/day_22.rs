@@ -0,0 +1,338 @@
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::solver::Solver;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Empty range")]
+    EmptyRange,
+    #[error(transparent)]
+    InvalidNumber(#[from] ParseIntError),
+    #[error("Coordinate {0} is out of bounds, the largest allowed is ±{MAX_COORDINATE}")]
+    OutOfBounds(i32),
+}
+
+/// The largest coordinate, either way, that a step may reach. Real inputs stay within about
+/// `±100000`, and at this bound the volume of any cuboid fits in an `i64`.
+pub const MAX_COORDINATE: i32 = 1 << 19;
+
+/// An axis-aligned box of cubes, with inclusive bounds on each axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Cuboid {
+    min: [i32; 3],
+    max: [i32; 3],
+}
+
+impl Cuboid {
+    const fn new(min: [i32; 3], max: [i32; 3]) -> Self {
+        Self { min, max }
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+        for axis in 0..3 {
+            result.min[axis] = self.min[axis].max(other.min[axis]);
+            result.max[axis] = self.max[axis].min(other.max[axis]);
+            if result.min[axis] > result.max[axis] {
+                return None;
+            }
+        }
+        Some(result)
+    }
+
+    fn volume(&self) -> i64 {
+        (0..3)
+            .map(|axis| i64::from(self.max[axis]) - i64::from(self.min[axis]) + 1)
+            .product()
+    }
+}
+
+impl Display for Cuboid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+            let separator = if axis > 0 { "," } else { "" };
+            write!(
+                f,
+                "{separator}{name}={}..{}",
+                self.min[axis], self.max[axis]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Cuboid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cuboid = Self::new([0; 3], [0; 3]);
+        let mut ranges = s.split(',');
+        for (axis, name) in ["x=", "y=", "z="].into_iter().enumerate() {
+            let range = ranges
+                .next()
+                .and_then(|r| r.strip_prefix(name))
+                .ok_or(ParseError::SyntaxError)?;
+            let (min, max) = range.split_once("..").ok_or(ParseError::SyntaxError)?;
+            cuboid.min[axis] = min.parse()?;
+            cuboid.max[axis] = max.parse()?;
+            for value in [cuboid.min[axis], cuboid.max[axis]] {
+                if !(-MAX_COORDINATE..=MAX_COORDINATE).contains(&value) {
+                    return Err(ParseError::OutOfBounds(value));
+                }
+            }
+            if cuboid.min[axis] > cuboid.max[axis] {
+                return Err(ParseError::EmptyRange);
+            }
+        }
+        if ranges.next().is_some() {
+            return Err(ParseError::SyntaxError);
+        }
+        Ok(cuboid)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.on { "on" } else { "off" };
+        write!(f, "{state} {}", self.cuboid)
+    }
+}
+
+impl FromStr for Step {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (state, cuboid) = s.split_once(' ').ok_or(ParseError::SyntaxError)?;
+        let on = match state {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::SyntaxError),
+        };
+        Ok(Self {
+            on,
+            cuboid: cuboid.parse()?,
+        })
+    }
+}
+
+#[aoc_generator(day22)]
+fn parse(input: &str) -> Result<Vec<Step>, ParseError> {
+    input.lines().map(str::parse).collect()
+}
+
+/// Counts the cubes left on, tracking the lit region as a list of signed cuboids. Each new step
+/// cancels its overlap with every listed cuboid, and "on" steps then add themselves.
+///
+/// The total fits in an `i64`, as the lit cubes lie within the bounds, but the running sum of
+/// the signed volumes may not.
+fn count_on(steps: impl IntoIterator<Item = Step>) -> i64 {
+    let mut signed: Vec<(Cuboid, i64)> = Vec::new();
+    for step in steps {
+        let overlaps = signed
+            .iter()
+            .filter_map(|(cuboid, sign)| Some((cuboid.intersection(&step.cuboid)?, -sign)))
+            .collect::<Vec<_>>();
+        signed.extend(overlaps);
+        if step.on {
+            signed.push((step.cuboid, 1));
+        }
+    }
+    let total = signed
+        .iter()
+        .map(|(cuboid, sign)| i128::from(sign * cuboid.volume()))
+        .sum::<i128>();
+    i64::try_from(total).expect("The lit cubes lie within the bounds")
+}
+
+#[aoc(day22, part1)]
+fn part_1(steps: &[Step]) -> i64 {
+    let region = Cuboid::new([-50; 3], [50; 3]);
+    count_on(steps.iter().filter_map(|step| {
+        Some(Step {
+            on: step.on,
+            cuboid: step.cuboid.intersection(&region)?,
+        })
+    }))
+}
+
+#[aoc(day22, part2)]
+fn part_2(steps: &[Step]) -> i64 {
+    count_on(steps.iter().copied())
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Parsed = Vec<Step>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        Ok(part_1(parsed))
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(part_2(parsed))
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(i64, i64), crate::Error> {
+    Day22::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::join_lines;
+    use crate::util::parse::untidy;
+
+    const SMALL_EXAMPLE: &str = "\
+        on x=10..12,y=10..12,z=10..12\n\
+        on x=11..13,y=11..13,z=11..13\n\
+        off x=9..11,y=9..11,z=9..11\n\
+        on x=10..10,y=10..10,z=10..10\
+    ";
+
+    const EXAMPLE: &str = "\
+        on x=-20..26,y=-36..17,z=-47..7\n\
+        on x=-20..33,y=-21..23,z=-26..28\n\
+        on x=-22..28,y=-29..23,z=-38..16\n\
+        on x=-46..7,y=-6..46,z=-50..-1\n\
+        on x=-49..1,y=-3..46,z=-24..28\n\
+        on x=2..47,y=-22..22,z=-23..27\n\
+        on x=-27..23,y=-28..26,z=-21..29\n\
+        on x=-39..5,y=-6..47,z=-3..44\n\
+        on x=-30..21,y=-8..43,z=-13..34\n\
+        on x=-22..26,y=-27..20,z=-29..19\n\
+        off x=-48..-32,y=26..41,z=-47..-37\n\
+        on x=-12..35,y=6..50,z=-50..-2\n\
+        off x=-48..-32,y=-32..-16,z=-15..-5\n\
+        on x=-18..26,y=-33..15,z=-7..46\n\
+        off x=-40..-22,y=-38..-28,z=23..41\n\
+        on x=-16..35,y=-41..10,z=-47..6\n\
+        off x=-32..-23,y=11..30,z=-14..3\n\
+        on x=-49..-5,y=-3..45,z=-29..18\n\
+        off x=18..30,y=-20..-8,z=-3..13\n\
+        on x=-41..9,y=-7..43,z=-33..15\n\
+        on x=-54112..-39298,y=-85059..-49293,z=-27449..7877\n\
+        on x=967..23432,y=45373..81175,z=27513..53682\
+    ";
+
+    /// Counts lit cubes one by one within the `±50` region.
+    fn brute_force(steps: &[Step]) -> usize {
+        let mut lit = vec![false; 101 * 101 * 101];
+        for step in steps {
+            let Some(c) = step.cuboid.intersection(&Cuboid::new([-50; 3], [50; 3])) else {
+                continue;
+            };
+            for x in c.min[0]..=c.max[0] {
+                for y in c.min[1]..=c.max[1] {
+                    for z in c.min[2]..=c.max[2] {
+                        let index = [x, y, z]
+                            .iter()
+                            .fold(0, |acc, &v| acc * 101 + usize::try_from(v + 50).unwrap());
+                        lit[index] = step.on;
+                    }
+                }
+            }
+        }
+        lit.iter().filter(|&&on| on).count()
+    }
+
+    #[test]
+    fn test_parse() {
+        let steps = parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(
+            steps[2],
+            Step {
+                on: false,
+                cuboid: Cuboid::new([9; 3], [11; 3]),
+            }
+        );
+        assert!(parse("on x=1..2,y=1..2").is_err());
+        assert!(parse("on x=2..1,y=1..2,z=1..2").is_err());
+        assert!(parse("toggle x=1..2,y=1..2,z=1..2").is_err());
+        assert!(matches!(
+            parse("on x=-2000000000..2000000000,y=1..2,z=1..2"),
+            Err(ParseError::OutOfBounds(-2_000_000_000))
+        ));
+    }
+
+    #[test]
+    fn test_largest_cuboids() {
+        let bound = MAX_COORDINATE;
+        let all = format!("on x=-{bound}..{bound},y=-{bound}..{bound},z=-{bound}..{bound}");
+        let steps = parse(&[all.as_str(); 3].join("\n")).unwrap();
+        let side = 2 * i64::from(bound) + 1;
+        assert_eq!(part_2(&steps), side * side * side);
+        assert_eq!(part_1(&steps), 101 * 101 * 101);
+    }
+
+    #[test]
+    fn test_display() {
+        let steps = parse(EXAMPLE).unwrap();
+        assert_eq!(join_lines(&steps), EXAMPLE);
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let a = Cuboid::new([10; 3], [12; 3]);
+        let b = Cuboid::new([11; 3], [13; 3]);
+        assert_eq!(a.volume(), 27);
+        assert_eq!(a.intersection(&b), Some(Cuboid::new([11; 3], [12; 3])));
+        assert_eq!(a.intersection(&Cuboid::new([13; 3], [14; 3])), None);
+    }
+
+    #[test]
+    fn test_small_example() {
+        let steps = parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(part_1(&steps), 39);
+        assert_eq!(part_2(&steps), 39);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let steps = parse(EXAMPLE).unwrap();
+        let result = part_1(&steps);
+        assert_eq!(result, 590_784);
+        assert_eq!(brute_force(&steps), 590_784);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let steps = parse(EXAMPLE).unwrap();
+        let far_cubes = Cuboid::new([-54_112, -85_059, -27_449], [-39_298, -49_293, 7_877])
+            .volume()
+            + Cuboid::new([967, 45_373, 27_513], [23_432, 81_175, 53_682]).volume();
+        let result = part_2(&steps);
+        assert_eq!(result, 590_784 + far_cubes);
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::{self, Display, Write};
+use std::sync::Mutex;
+
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::solver::Solver;
+use crate::util::par_search::par_branch_and_bound;
+use crate::util::pathfinding;
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Wrong number of amphipods of type {0}")]
+    WrongCount(char),
+}
+
+const HALLWAY: usize = 11;
+const EMPTY: u8 = 0;
+
+/// Hallway position just outside each room.
+const fn room_door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// Energy per step for amphipods `1..=4`, that is A to D.
+const fn energy(kind: u8) -> u32 {
+    match kind {
+        1 => 1,
+        2 => 10,
+        3 => 100,
+        _ => 1000,
+    }
+}
+
+/// A burrow with `DEPTH` places in each of the four side rooms, listed from the hallway down.
+/// Amphipods are stored as `1..=4` for A to D, and empty places as `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Burrow<const DEPTH: usize> {
+    hallway: [u8; HALLWAY],
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::serde_array::nested"))]
+    rooms: [[u8; DEPTH]; 4],
+}
+
+impl<const DEPTH: usize> Burrow<DEPTH> {
+    const SOLVED: Self = {
+        let mut rooms = [[EMPTY; DEPTH]; 4];
+        let mut kind = 1;
+        while kind <= 4 {
+            rooms[kind as usize - 1] = [kind; DEPTH];
+            kind += 1;
+        }
+        Self {
+            hallway: [EMPTY; HALLWAY],
+            rooms,
+        }
+    };
+
+    /// Whether the room only holds amphipods that belong there.
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .all(|&kind| kind == EMPTY || usize::from(kind) == room + 1)
+    }
+
+    /// Whether the hallway is empty strictly between `from` and `to`, and at `to` itself.
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(|&kind| kind == EMPTY)
+    }
+
+    /// A lower bound on the remaining energy: every amphipod that still has to move walks
+    /// straight to the top place of its room, ignoring everyone else.
+    fn heuristic(&self) -> u32 {
+        let mut total = 0;
+        for (position, &kind) in self.hallway.iter().enumerate() {
+            if kind != EMPTY {
+                let door = room_door(usize::from(kind) - 1);
+                total += energy(kind) * (u32::try_from(position.abs_diff(door)).unwrap_or(0) + 1);
+            }
+        }
+        for room in 0..4 {
+            let mut blocking = false;
+            for (depth, &kind) in self.rooms[room].iter().enumerate().rev() {
+                if kind == EMPTY {
+                    continue;
+                }
+                let home = usize::from(kind) - 1;
+                blocking |= home != room;
+                if blocking {
+                    let across = room_door(room).abs_diff(room_door(home)).max(2);
+                    let steps = depth + 1 + across + 1;
+                    total += energy(kind) * u32::try_from(steps).unwrap_or(0);
+                }
+            }
+        }
+        total
+    }
+
+    /// Every state reachable with a single move, and the energy the move costs.
+    fn moves(&self) -> Vec<(Self, u32)> {
+        let mut result = Vec::new();
+        for (position, &kind) in self.hallway.iter().enumerate() {
+            if kind == EMPTY {
+                continue;
+            }
+            let room = usize::from(kind) - 1;
+            let door = room_door(room);
+            if !self.is_settled(room) || !self.hallway_clear(position, door) {
+                continue;
+            }
+            let Some(depth) = self.rooms[room].iter().rposition(|&k| k == EMPTY) else {
+                continue;
+            };
+            let mut next = *self;
+            next.hallway[position] = EMPTY;
+            next.rooms[room][depth] = kind;
+            let steps = position.abs_diff(door) + depth + 1;
+            result.push((next, energy(kind) * u32::try_from(steps).unwrap_or(0)));
+        }
+        for room in 0..4 {
+            if self.is_settled(room) {
+                continue;
+            }
+            let Some(depth) = self.rooms[room].iter().position(|&k| k != EMPTY) else {
+                continue;
+            };
+            let kind = self.rooms[room][depth];
+            let door = room_door(room);
+            for target in [0, 1, 3, 5, 7, 9, 10] {
+                if !self.hallway_clear(door, target) {
+                    continue;
+                }
+                let mut next = *self;
+                next.rooms[room][depth] = EMPTY;
+                next.hallway[target] = kind;
+                let steps = depth + 1 + door.abs_diff(target);
+                result.push((next, energy(kind) * u32::try_from(steps).unwrap_or(0)));
+            }
+        }
+        result
+    }
+
+    /// The least energy needed to sort the amphipods, found with A*.
+    #[instrument(level = "debug", skip_all, fields(depth = DEPTH), ret)]
+    fn organize(&self) -> Option<u32> {
+        pathfinding::astar(*self, Self::moves, Self::heuristic, |&state| {
+            state == Self::SOLVED
+        })
+    }
+
+    /// Like `organize`, but searches every order of moves depth-first, in parallel near the
+    /// start, and drops those that can not beat the best total found so far. Each amphipod
+    /// moves at most twice, so every order of moves ends.
+    ///
+    /// An amphipod that can go home might as well go at once, as that only frees the hallway,
+    /// so such a move is the only one tried. Otherwise the most promising move is tried first.
+    /// A burrow that was already reached with no more energy is not searched again.
+    #[instrument(level = "debug", skip_all, fields(depth = DEPTH), ret)]
+    fn organize_branch_and_bound(&self) -> Option<u32> {
+        let bound = |burrow: &Self, energy: u64| energy + u64::from(burrow.heuristic());
+        let cheapest = Mutex::new(HashMap::new());
+        let best = par_branch_and_bound(
+            (*self, 0),
+            2,
+            &|(burrow, energy): (Self, u64), best, pending: &mut Vec<_>| {
+                if burrow == Self::SOLVED {
+                    return Some(energy);
+                }
+                if bound(&burrow, energy) >= best {
+                    return None;
+                }
+                match cheapest.lock().unwrap().entry(burrow) {
+                    Entry::Occupied(entry) if *entry.get() <= energy => return None,
+                    Entry::Occupied(mut entry) => *entry.get_mut() = energy,
+                    Entry::Vacant(entry) => _ = entry.insert(energy),
+                }
+                let in_hallway =
+                    |burrow: &Self| burrow.hallway.iter().filter(|&&k| k != EMPTY).count();
+                let mut moves = burrow
+                    .moves()
+                    .into_iter()
+                    .map(|(next, cost)| (next, energy + u64::from(cost)))
+                    .filter(|(next, energy)| bound(next, *energy) < best)
+                    .collect::<Vec<_>>();
+                if let Some(home) = moves
+                    .iter()
+                    .position(|(next, _)| in_hallway(next) < in_hallway(&burrow))
+                {
+                    pending.push(moves.swap_remove(home));
+                    return None;
+                }
+                // The stack is popped from the end, so the lowest bound goes last.
+                moves
+                    .sort_unstable_by_key(|(next, energy)| std::cmp::Reverse(bound(next, *energy)));
+                pending.extend(moves);
+                None
+            },
+        )?;
+        u32::try_from(best).ok()
+    }
+
+    /// Inserts `rows` between the top and the remaining places of every room.
+    fn unfold<const UNFOLDED: usize>(&self, rows: &[[u8; 4]]) -> Burrow<UNFOLDED> {
+        let mut rooms = [[EMPTY; UNFOLDED]; 4];
+        for (room, places) in rooms.iter_mut().enumerate() {
+            let (top, rest) = self.rooms[room].split_at(DEPTH.min(1));
+            let column = rows.iter().map(|row| row[room]);
+            let kinds = top
+                .iter()
+                .copied()
+                .chain(column)
+                .chain(rest.iter().copied());
+            for (place, kind) in places.iter_mut().zip(kinds) {
+                *place = kind;
+            }
+        }
+        Burrow {
+            hallway: self.hallway,
+            rooms,
+        }
+    }
+}
+
+/// The letter of amphipod `kind`, or `.` for an empty place.
+const fn kind_char(kind: u8) -> char {
+    match kind {
+        1 => 'A',
+        2 => 'B',
+        3 => 'C',
+        4 => 'D',
+        _ => '.',
+    }
+}
+
+/// Draws the burrow as the puzzle input does.
+impl<const DEPTH: usize> Display for Burrow<DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "#############")?;
+        f.write_char('#')?;
+        for &kind in &self.hallway {
+            f.write_char(kind_char(kind))?;
+        }
+        f.write_str("#\n")?;
+        for depth in 0..DEPTH {
+            f.write_str(if depth == 0 { "###" } else { "  #" })?;
+            for room in &self.rooms {
+                write!(f, "{}#", kind_char(room[depth]))?;
+            }
+            f.write_str(if depth == 0 { "##\n" } else { "\n" })?;
+        }
+        f.write_str("  #########")
+    }
+}
+
+#[aoc_generator(day23)]
+fn parse(input: &str) -> Result<Burrow<2>, ParseError> {
+    let mut rooms = [[EMPTY; 2]; 4];
+    let mut rows = Vec::new();
+    for line in input.lines().skip(2) {
+        let mut row = Vec::new();
+        for ch in line.bytes() {
+            match ch {
+                b'A'..=b'D' => row.push(ch - b'A' + 1),
+                b'#' | b' ' => {}
+                _ => return Err(ParseError::SyntaxError),
+            }
+        }
+        if !row.is_empty() {
+            rows.push(row);
+        }
+    }
+    if rows.len() != 2 || rows.iter().any(|row| row.len() != 4) {
+        return Err(ParseError::SyntaxError);
+    }
+    for (depth, row) in rows.iter().enumerate() {
+        for (room, &kind) in row.iter().enumerate() {
+            rooms[room][depth] = kind;
+        }
+    }
+    for (kind, name) in (1..=4).zip('A'..='D') {
+        if rooms.iter().flatten().filter(|&&k| k == kind).count() != 2 {
+            return Err(ParseError::WrongCount(name));
+        }
+    }
+    Ok(Burrow {
+        hallway: [EMPTY; HALLWAY],
+        rooms,
+    })
+}
+
+#[aoc(day23, part1)]
+fn part_1(burrow: &Burrow<2>) -> Option<u32> {
+    burrow.organize()
+}
+
+#[aoc(day23, part2)]
+fn part_2(burrow: &Burrow<2>) -> Option<u32> {
+    let unfolded: Burrow<4> = burrow.unfold(&[[4, 3, 2, 1], [4, 2, 1, 3]]);
+    unfolded.organize()
+}
+
+#[aoc(day23, part1, branch_and_bound)]
+fn part_1_branch_and_bound(burrow: &Burrow<2>) -> Option<u32> {
+    burrow.organize_branch_and_bound()
+}
+
+#[aoc(day23, part2, branch_and_bound)]
+fn part_2_branch_and_bound(burrow: &Burrow<2>) -> Option<u32> {
+    let unfolded: Burrow<4> = burrow.unfold(&[[4, 3, 2, 1], [4, 2, 1, 3]]);
+    unfolded.organize_branch_and_bound()
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Parsed = Burrow<2>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).ok_or(crate::Error::NoSolution)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or has no solution.
+pub fn solve(input: &str) -> Result<(u32, u32), crate::Error> {
+    Day23::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &str = "\
+        #############\n\
+        #...........#\n\
+        ###B#C#B#D###\n\
+        \x20 #A#D#C#A#\n\
+        \x20 #########\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let burrow = parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.rooms, [[2, 1], [3, 4], [2, 3], [4, 1]]);
+        assert!(
+            parse("#############\n#...........#\n###B#C#B#D###\n  #A#D#C#B#\n  #########").is_err()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let burrow = parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.to_string(), EXAMPLE);
+        let unfolded: Burrow<4> = burrow.unfold(&[[4, 3, 2, 1], [4, 2, 1, 3]]);
+        assert_eq!(unfolded.to_string().lines().nth(4), Some("  #D#B#A#C#"));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let burrow = parse(EXAMPLE).unwrap();
+        let unfolded: Burrow<4> = burrow.unfold(&[[4, 3, 2, 1], [4, 2, 1, 3]]);
+        assert_eq!(
+            unfolded.rooms,
+            [[2, 4, 4, 1], [3, 3, 2, 4], [2, 2, 1, 3], [4, 1, 3, 1]]
+        );
+    }
+
+    #[test]
+    fn test_moves() {
+        let burrow = parse(EXAMPLE).unwrap();
+        let moves = burrow.moves();
+        assert_eq!(moves.len(), 4 * 7);
+        assert_eq!(Burrow::<2>::SOLVED.moves(), []);
+        assert_eq!(Burrow::<2>::SOLVED.heuristic(), 0);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let burrow = parse(EXAMPLE).unwrap();
+        let result = part_1(&burrow);
+        assert_eq!(result, Some(12_521));
+        assert_eq!(part_1_branch_and_bound(&burrow), Some(12_521));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let burrow = parse(EXAMPLE).unwrap();
+        let result = part_2(&burrow);
+        assert_eq!(result, Some(44_169));
+        assert_eq!(part_2_branch_and_bound(&burrow), Some(44_169));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(solve(&untidy(EXAMPLE)).unwrap(), solve(EXAMPLE).unwrap());
+    }
+}
@@ -0,0 +1,233 @@
+use thiserror::Error;
+
+use crate::alu::{self, Instruction, Operand, Program, Register};
+use crate::solver::Solver;
+
+/// Instructions in each digit's block of MONAD.
+const BLOCK_LEN: usize = 18;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+enum AnalysisError {
+    #[error("Block {0} does not have the expected shape")]
+    UnexpectedBlock(usize),
+    #[error("Blocks that push and pop do not pair up")]
+    Unbalanced,
+    #[error("No digit pair satisfies the constraint between digits {0} and {1}")]
+    Unsatisfiable(usize, usize),
+    #[error("The interpreter rejected the model number {0}")]
+    Rejected(u64),
+}
+
+/// The constant operands of one digit's block: whether it pops from the base-26 stack in `z`,
+/// the offset compared against the digit, and the offset pushed with the digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    pops: bool,
+    check: i64,
+    offset: i64,
+}
+
+fn blocks(program: &Program) -> Result<Vec<Block>, AnalysisError> {
+    program
+        .instructions()
+        .chunks(BLOCK_LEN)
+        .enumerate()
+        .map(|(i, block)| {
+            if block.len() != BLOCK_LEN {
+                return Err(AnalysisError::UnexpectedBlock(i));
+            }
+            match (block[0], block[4], block[5], block[15]) {
+                (
+                    Instruction::Inp(Register::W),
+                    Instruction::Div(Register::Z, Operand::Literal(div @ (1 | 26))),
+                    Instruction::Add(Register::X, Operand::Literal(check)),
+                    Instruction::Add(Register::Y, Operand::Literal(offset)),
+                ) => Ok(Block {
+                    pops: div == 26,
+                    check,
+                    offset,
+                }),
+                _ => Err(AnalysisError::UnexpectedBlock(i)),
+            }
+        })
+        .collect()
+}
+
+/// Pairs each popping block with the pushing block it pops, as `(push, pop, difference)` where
+/// a valid model number has `digit[pop] = digit[push] + difference`.
+fn constraints(blocks: &[Block]) -> Result<Vec<(usize, usize, i64)>, AnalysisError> {
+    let mut stack = Vec::new();
+    let mut result = Vec::new();
+    for (i, block) in blocks.iter().enumerate() {
+        if block.pops {
+            let (j, offset) = stack.pop().ok_or(AnalysisError::Unbalanced)?;
+            result.push((j, i, offset + block.check));
+        } else {
+            stack.push((i, block.offset));
+        }
+    }
+    if stack.is_empty() {
+        Ok(result)
+    } else {
+        Err(AnalysisError::Unbalanced)
+    }
+}
+
+/// Finds the largest or smallest model number MONAD accepts, and checks it with the
+/// interpreter.
+fn model_number(program: &Program, largest: bool) -> Result<u64, AnalysisError> {
+    let blocks = blocks(program)?;
+    let mut digits = vec![0; blocks.len()];
+    for (push, pop, difference) in constraints(&blocks)? {
+        let candidates = (1..=9).filter(|&d| (1..=9).contains(&(d + difference)));
+        let digit = if largest {
+            candidates.max()
+        } else {
+            candidates.min()
+        };
+        let digit = digit.ok_or(AnalysisError::Unsatisfiable(push, pop))?;
+        digits[push] = digit;
+        digits[pop] = digit + difference;
+    }
+    let number = digits.iter().fold(0, |acc, &d| acc * 10 + d.unsigned_abs());
+    match program.run(&digits) {
+        Ok(regs) if regs[Register::Z] == 0 => Ok(number),
+        _ => Err(AnalysisError::Rejected(number)),
+    }
+}
+
+#[aoc_generator(day24)]
+fn parse(input: &str) -> Result<Program, alu::ParseError> {
+    input.parse()
+}
+
+#[aoc(day24, part1)]
+fn part_1(program: &Program) -> Result<u64, AnalysisError> {
+    model_number(program, true)
+}
+
+#[aoc(day24, part2)]
+fn part_2(program: &Program) -> Result<u64, AnalysisError> {
+    model_number(program, false)
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Parsed = Program;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).map_err(crate::Error::invalid_input)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        part_2(parsed).map_err(crate::Error::invalid_input)
+    }
+}
+
+/// Parses the input and solves both parts.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed.
+pub fn solve(input: &str) -> Result<(u64, u64), crate::Error> {
+    Day24::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    /// `(pops, check, offset)` for a MONAD-shaped program. Pushing blocks use checks above 9 so
+    /// they never match the digit.
+    const PARAMETERS: [(bool, i64, i64); 14] = [
+        (false, 12, 5),
+        (false, 11, 3),
+        (true, -2, 4),
+        (false, 13, 7),
+        (true, -10, 9),
+        (true, -4, 2),
+        (false, 10, 1),
+        (false, 14, 0),
+        (true, 0, 8),
+        (true, -5, 3),
+        (false, 11, 6),
+        (true, -6, 1),
+        (false, 15, 2),
+        (true, 3, 7),
+    ];
+
+    fn monad() -> Program {
+        PARAMETERS
+            .iter()
+            .map(|&(pops, check, offset)| {
+                let div = if pops { 26 } else { 1 };
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {div}\nadd x {check}\neql x w\n\
+                     eql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\n\
+                     add y {offset}\nmul y x\nadd z y\n"
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat()
+            .parse()
+            .unwrap()
+    }
+
+    fn digits(number: u64) -> Vec<i64> {
+        number
+            .to_string()
+            .bytes()
+            .map(|ch| i64::from(ch - b'0'))
+            .collect()
+    }
+
+    #[test]
+    fn test_constraints() {
+        let blocks = blocks(&monad()).unwrap();
+        assert_eq!(blocks.len(), 14);
+        let constraints = constraints(&blocks).unwrap();
+        assert_eq!(constraints[0], (1, 2, 1));
+        assert_eq!(constraints[2], (0, 5, 1));
+        assert_eq!(constraints.len(), 7);
+    }
+
+    #[test]
+    fn test_rejects_other_programs() {
+        let program = "inp w\nadd z w".parse().unwrap();
+        assert_eq!(part_1(&program), Err(AnalysisError::UnexpectedBlock(0)));
+    }
+
+    #[test]
+    fn test_part_1() {
+        let program = monad();
+        let result = part_1(&program);
+        assert_eq!(result, Ok(88_996_999_959_949));
+        let regs = program.run(&digits(88_996_999_959_949)).unwrap();
+        assert_eq!(regs[Register::Z], 0);
+        let regs = program.run(&digits(88_996_999_959_959)).unwrap();
+        assert_ne!(regs[Register::Z], 0);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let program = monad();
+        let result = part_2(&program);
+        assert_eq!(result, Ok(11_241_251_111_116));
+        let regs = program.run(&digits(11_241_251_111_116)).unwrap();
+        assert_eq!(regs[Register::Z], 0);
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        let input = monad().to_string();
+        assert_eq!(solve(&untidy(&input)).unwrap(), solve(&input).unwrap());
+    }
+}
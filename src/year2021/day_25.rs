@@ -0,0 +1,172 @@
+use std::mem;
+
+use thiserror::Error;
+use tracing::trace;
+
+use crate::solver::{NoAnswer, Solver};
+use crate::util::grid::{Grid, GridError};
+
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error(transparent)]
+    InvalidGrid(#[from] GridError),
+}
+
+#[aoc_generator(day25)]
+fn parse(input: &[u8]) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::from_bytes(input)?;
+    if grid.as_slice().iter().any(|ch| !b">v.".contains(ch)) {
+        return Err(ParseError::SyntaxError);
+    }
+    Ok(grid)
+}
+
+/// Moves every cucumber of `herd` one step along `next`, all at once. Reads from `grid` and
+/// writes the result to `buffer`, then swaps them. Returns whether anything moved.
+fn move_herd(
+    grid: &mut Grid<u8>,
+    buffer: &mut Grid<u8>,
+    herd: u8,
+    next: impl Fn(usize) -> usize,
+) -> bool {
+    let cells = grid.as_slice();
+    let moved_cells = buffer.as_mut_slice();
+    moved_cells.copy_from_slice(cells);
+    let mut moved = false;
+    for (i, &cell) in cells.iter().enumerate() {
+        let target = next(i);
+        if cell == herd && cells[target] == b'.' {
+            moved_cells[i] = b'.';
+            moved_cells[target] = herd;
+            moved = true;
+        }
+    }
+    mem::swap(grid, buffer);
+    moved
+}
+
+/// The first step on which no sea cucumber moves, or `None` if they keep moving forever.
+///
+/// The herds can only move forever by coming back to an earlier state. To notice that without
+/// keeping every state, the grid is saved after 1, 2, 4, 8, ... steps and compared to each
+/// later one, which finds any cycle by the time the gap between saves exceeds its length.
+#[aoc(day25, part1)]
+fn part_1(grid: &Grid<u8>) -> Option<usize> {
+    let mut grid = grid.clone();
+    let mut buffer = grid.clone();
+    let mut saved = grid.clone();
+    let (width, len) = (grid.width(), grid.as_slice().len());
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        let east = move_herd(&mut grid, &mut buffer, b'>', |i| {
+            if (i + 1) % width == 0 {
+                i + 1 - width
+            } else {
+                i + 1
+            }
+        });
+        let south = move_herd(&mut grid, &mut buffer, b'v', |i| (i + width) % len);
+        trace!(steps, east, south, "Step");
+        if !east && !south {
+            return Some(steps);
+        }
+        if grid == saved {
+            return None;
+        }
+        if steps.is_power_of_two() {
+            saved.clone_from(&grid);
+        }
+    }
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    type Parsed = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = NoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Parsed, crate::Error> {
+        parse(input.as_bytes()).map_err(crate::Error::invalid_input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Part1, crate::Error> {
+        part_1(parsed).ok_or(crate::Error::NoSolution)
+    }
+
+    fn part2(_parsed: &Self::Parsed) -> Result<Self::Part2, crate::Error> {
+        Ok(NoAnswer)
+    }
+}
+
+/// Parses the input and solves the only part. There is no second puzzle on the last day.
+///
+/// # Errors
+///
+/// Returns an error if the input is malformed, or the sea cucumbers never stop.
+pub fn solve(input: &str) -> Result<(usize, NoAnswer), crate::Error> {
+    Day25::solve(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse::untidy;
+
+    const EXAMPLE: &[u8] = b"\
+        v...>>.vv>\n\
+        .vv>>.vv..\n\
+        >>.>v>...v\n\
+        >>v>>.>.v.\n\
+        v>v.vv.v..\n\
+        >.>>..v...\n\
+        .vv..>.>v.\n\
+        v.v..>>v.v\n\
+        ....v..v.>\
+    ";
+
+    #[test]
+    fn test_parse() {
+        let grid = parse(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (10, 9));
+        assert!(parse(b"v.\n>").is_err());
+        assert!(parse(b"v.\n>x").is_err());
+    }
+
+    #[test]
+    fn test_step() {
+        let mut grid = parse(b"...>>>>>...").unwrap();
+        let mut buffer = grid.clone();
+        move_herd(&mut grid, &mut buffer, b'>', |i| (i + 1) % 11);
+        assert_eq!(grid.as_slice(), b"...>>>>.>..");
+        move_herd(&mut grid, &mut buffer, b'>', |i| (i + 1) % 11);
+        assert_eq!(grid.as_slice(), b"...>>>.>.>.");
+    }
+
+    #[test]
+    fn test_part_1() {
+        let grid = parse(EXAMPLE).unwrap();
+        let result = part_1(&grid);
+        assert_eq!(result, Some(58));
+    }
+
+    #[test]
+    fn test_never_stops() {
+        assert_eq!(part_1(&parse(b">.").unwrap()), None);
+        assert_eq!(part_1(&parse(b"v\n.\n.").unwrap()), None);
+        assert_eq!(part_1(&parse(b">..\n...\nv..").unwrap()), None);
+        assert!(matches!(solve(">."), Err(crate::Error::NoSolution)));
+        assert_eq!(part_1(&parse(b">>").unwrap()), Some(1));
+    }
+
+    #[test]
+    fn test_solve_untidy() {
+        assert_eq!(
+            solve(&untidy(EXAMPLE)).unwrap(),
+            solve(std::str::from_utf8(EXAMPLE).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! The 2021 puzzles, one module per day. Each day is behind a feature of the same name, so
+//! builds can leave out the days they do not need.
+
+#[cfg(feature = "day01")]
+pub mod day_01;
+#[cfg(feature = "day02")]
+pub mod day_02;
+#[cfg(feature = "day03")]
+pub mod day_03;
+#[cfg(feature = "day04")]
+pub mod day_04;
+#[cfg(feature = "day05")]
+pub mod day_05;
+#[cfg(feature = "day06")]
+pub mod day_06;
+#[cfg(feature = "day07")]
+pub mod day_07;
+#[cfg(feature = "day08")]
+pub mod day_08;
+#[cfg(feature = "day09")]
+pub mod day_09;
+#[cfg(feature = "day10")]
+pub mod day_10;
+#[cfg(feature = "day11")]
+pub mod day_11;
+#[cfg(feature = "day12")]
+pub mod day_12;
+#[cfg(feature = "day13")]
+pub mod day_13;
+#[cfg(feature = "day14")]
+pub mod day_14;
+#[cfg(feature = "day15")]
+pub mod day_15;
+#[cfg(feature = "day16")]
+pub mod day_16;
+#[cfg(feature = "day17")]
+pub mod day_17;
+#[cfg(feature = "day18")]
+pub mod day_18;
+#[cfg(feature = "day19")]
+pub mod day_19;
+#[cfg(feature = "day20")]
+pub mod day_20;
+#[cfg(feature = "day21")]
+pub mod day_21;
+#[cfg(feature = "day22")]
+pub mod day_22;
+#[cfg(feature = "day23")]
+pub mod day_23;
+#[cfg(feature = "day24")]
+pub mod day_24;
+#[cfg(feature = "day25")]
+pub mod day_25;
@@ -0,0 +1,54 @@
+//! Runs every case in `tests/data` through its day's solver. Add a case by dropping an input in
+//! `tests/data/dayNN/input/` and its answers in `tests/data/dayNN/expected/`, under the same name.
+//!
+//! Cases for days whose feature is disabled are skipped.
+
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2021::solver;
+use advent_of_code_2021::verify::{self, Outcome};
+
+#[test]
+fn golden_cases() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(verify::CASES_DIR);
+    let cases = verify::discover_cases(&dir).unwrap();
+    assert!(!cases.is_empty(), "no cases in {}", dir.display());
+    let failures = cases
+        .iter()
+        .filter(|case| solver::solver(case.day).is_some())
+        .flat_map(|case| {
+            verify::check_case(case)
+                .into_iter()
+                .filter(|report| report.outcome != Outcome::Pass)
+                .map(move |report| {
+                    format!(
+                        "day {} {} part {}: {:?}",
+                        case.day, case.name, report.part, report.outcome
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// The same cases with `\r\n` line endings and spaces at the ends of lines, which must make no
+/// difference to the answers.
+#[test]
+fn golden_cases_untidy() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(verify::CASES_DIR);
+    for case in verify::discover_cases(&dir).unwrap() {
+        let Some(solver) = solver::solver(case.day) else {
+            continue;
+        };
+        let input = fs::read_to_string(&case.input).unwrap();
+        let untidy = input.replace('\n', " \r\n");
+        assert_eq!(
+            solver.solve(&untidy).ok(),
+            solver.solve(&input).ok(),
+            "day {} {}",
+            case.day,
+            case.name
+        );
+    }
+}
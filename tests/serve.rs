@@ -0,0 +1,96 @@
+//! Runs `aoc serve` the way the binary does, with each request solved in a child process, and
+//! checks that inputs which are rejected, or solvers that crash, do not stop the server.
+
+#![cfg(all(feature = "day01", feature = "day12", feature = "day16"))]
+
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::thread;
+
+use advent_of_code_2021::serve::{self, Runner};
+
+fn start(program: PathBuf) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let runner = Runner::Child(program);
+    thread::spawn(move || serve::serve(&listener, &runner));
+    address
+}
+
+fn post(address: SocketAddr, path: &str, body: &str) -> String {
+    let mut client = TcpStream::connect(address).unwrap();
+    write!(
+        client,
+        "POST {path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).unwrap();
+    response
+}
+
+/// A day 16 transmission of operators nested `depth` deep around a literal, in hex.
+fn nested_packets(depth: usize) -> String {
+    // Version 0, a sum of one sub-packet, counted.
+    let mut bits = "000000100000000001".repeat(depth);
+    // Version 0, the literal 1.
+    bits.push_str("00010000001");
+    while !bits.len().is_multiple_of(4) {
+        bits.push('0');
+    }
+    bits.as_bytes()
+        .chunks(4)
+        .fold(String::new(), |mut hex, nibble| {
+            let nibble = u8::from_str_radix(std::str::from_utf8(nibble).unwrap(), 2).unwrap();
+            write!(hex, "{nibble:X}").unwrap();
+            hex
+        })
+}
+
+#[test]
+fn serve_survives_bad_inputs() {
+    let address = start(PathBuf::from(env!("CARGO_BIN_EXE_aoc")));
+    let response = post(address, "/solve/1/1", "1\n2\n3");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(
+        response.ends_with(r#""solved_together":false}"#),
+        "{response}"
+    );
+
+    let response = post(address, "/solve/16/1", &nested_packets(3));
+    assert!(response.contains(r#""answer":"0""#), "{response}");
+    let response = post(address, "/solve/16/1", &nested_packets(100_000));
+    assert!(response.starts_with("HTTP/1.1 422 "), "{response}");
+    assert!(response.contains("nested more than"), "{response}");
+
+    let response = post(address, "/solve/12/1", "start-A\nA-B\nB-end");
+    assert!(response.starts_with("HTTP/1.1 422 "), "{response}");
+    assert!(response.contains("Large caves A and B"), "{response}");
+
+    let response = post(address, "/solve/1/3", "1");
+    assert!(response.starts_with("HTTP/1.1 422 "), "{response}");
+
+    let response = post(address, "/solve/1/2", "199\n200\n208\n210");
+    assert!(response.contains(r#""answer":"1""#), "{response}");
+}
+
+/// A solver that aborts, standing in for one that overflows its stack.
+#[cfg(unix)]
+#[test]
+fn serve_survives_crashes() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let program = std::env::temp_dir().join(format!("aoc-crash-{}", std::process::id()));
+    std::fs::write(&program, "#!/bin/sh\nkill -ABRT $$\n").unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let address = start(program.clone());
+    for _ in 0..2 {
+        let response = post(address, "/solve/1/1", "1\n2\n3");
+        assert!(response.starts_with("HTTP/1.1 500 "), "{response}");
+        assert!(response.contains("The solver crashed"), "{response}");
+    }
+    std::fs::remove_file(program).unwrap();
+}